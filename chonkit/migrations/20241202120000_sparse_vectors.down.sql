@@ -0,0 +1,8 @@
+DROP TABLE vectors;
+DROP TABLE vector_collections;
+
+ALTER TABLE collections DROP CONSTRAINT dense_or_sparse_model;
+ALTER TABLE collections DROP COLUMN sparse_model;
+
+DELETE FROM collections WHERE model IS NULL;
+ALTER TABLE collections ALTER COLUMN model SET NOT NULL;
@@ -0,0 +1,49 @@
+-- Collections can hold dense vectors, sparse vectors or both.
+ALTER TABLE collections ALTER COLUMN model DROP NOT NULL;
+
+-- The model used to generate the sparse vectors.
+ALTER TABLE collections ADD COLUMN sparse_model TEXT;
+
+ALTER TABLE collections ADD CONSTRAINT dense_or_sparse_model CHECK (model IS NOT NULL OR sparse_model IS NOT NULL);
+
+-- Stores vector collection information for the postgres vector provider.
+CREATE TABLE vector_collections(
+    -- The name of the collection.
+    name TEXT PRIMARY KEY,
+
+    -- The ID of the collection in the collections table.
+    collection_id UUID NOT NULL,
+
+    -- Dense vector size, if the collection holds dense vectors.
+    size INT,
+
+    embedding_provider TEXT NOT NULL,
+
+    embedding_model TEXT,
+
+    sparse_model TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Stores vectors for the postgres vector provider.
+CREATE TABLE vectors(
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+
+    collection TEXT NOT NULL REFERENCES vector_collections ON DELETE CASCADE,
+
+    document_id UUID NOT NULL,
+
+    content TEXT NOT NULL,
+
+    dense REAL[],
+
+    -- Sparse vectors are stored as their non-zero dimensions and the respective values.
+    sparse_indices INT[],
+
+    sparse_values REAL[],
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX vectors_collection_document_id ON vectors(collection, document_id);
@@ -0,0 +1,2 @@
+ALTER TABLE vector_collections DROP COLUMN distance;
+ALTER TABLE collections DROP COLUMN index_config;
@@ -0,0 +1,6 @@
+-- Index parameters of the collection, i.e. the distance metric,
+-- HNSW parameters and whether the collection is stored on disk.
+ALTER TABLE collections ADD COLUMN index_config JSONB NOT NULL DEFAULT '{"distance": "cosine", "hnswM": null, "hnswEfConstruct": null, "onDisk": false}';
+
+-- The distance metric used to score dense vectors in the postgres vector provider.
+ALTER TABLE vector_collections ADD COLUMN distance TEXT NOT NULL DEFAULT 'cosine';
@@ -0,0 +1 @@
+ALTER TABLE vectors DROP COLUMN chunk_index;
@@ -0,0 +1,2 @@
+-- Position of the chunk in its document. NULL for vectors inserted before it was tracked.
+ALTER TABLE vectors ADD COLUMN chunk_index INT;
@@ -0,0 +1,13 @@
+-- Named aliases pointing to collections. Clients can address an alias instead of
+-- a collection and the alias can be swapped to another collection atomically.
+CREATE TABLE collection_aliases (
+    name TEXT PRIMARY KEY,
+
+    collection_id UUID NOT NULL REFERENCES collections ON DELETE CASCADE,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+SELECT manage_updated_at('collection_aliases');
@@ -0,0 +1,3 @@
+DROP TABLE eval_runs;
+DROP TABLE eval_questions;
+DROP TABLE eval_datasets;
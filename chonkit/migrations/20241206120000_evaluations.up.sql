@@ -0,0 +1,73 @@
+-- Evaluation datasets group questions used to measure retrieval quality.
+CREATE TABLE eval_datasets (
+    id UUID PRIMARY KEY,
+
+    name TEXT UNIQUE NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A question is expected to retrieve chunks of the given documents
+-- and/or chunks containing the given text spans.
+CREATE TABLE eval_questions (
+    id UUID PRIMARY KEY,
+
+    dataset_id UUID NOT NULL REFERENCES eval_datasets ON DELETE CASCADE,
+
+    question TEXT NOT NULL,
+
+    documents UUID[] NOT NULL DEFAULT '{}',
+
+    spans TEXT[] NOT NULL DEFAULT '{}',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Results of evaluating a dataset against a collection. The collection
+-- parameters are copied so runs remain comparable after it is deleted.
+CREATE TABLE eval_runs (
+    id UUID PRIMARY KEY,
+
+    dataset_id UUID NOT NULL REFERENCES eval_datasets ON DELETE CASCADE,
+
+    collection_id UUID REFERENCES collections ON DELETE SET NULL,
+
+    collection_name TEXT NOT NULL,
+
+    embedder TEXT NOT NULL,
+
+    model TEXT,
+
+    sparse_model TEXT,
+
+    provider TEXT NOT NULL,
+
+    -- Amount of results retrieved per question.
+    k INT NOT NULL,
+
+    recall FLOAT8 NOT NULL,
+
+    precision FLOAT8 NOT NULL,
+
+    mrr FLOAT8 NOT NULL,
+
+    ndcg FLOAT8 NOT NULL,
+
+    -- Chunking configurations of the documents involved in the run.
+    chunk_configs JSONB NOT NULL,
+
+    -- Metrics of every question.
+    questions JSONB NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ON eval_questions(dataset_id);
+CREATE INDEX ON eval_runs(dataset_id);
+
+SELECT manage_updated_at('eval_datasets');
+SELECT manage_updated_at('eval_questions');
@@ -0,0 +1,2 @@
+ALTER TABLE vectors DROP COLUMN chunk_id;
+DROP TABLE chunks;
@@ -0,0 +1,35 @@
+-- Chunks of documents, keyed by the fingerprint of the configuration that produced them.
+CREATE TABLE chunks (
+    id UUID PRIMARY KEY,
+
+    document_id UUID NOT NULL REFERENCES documents ON DELETE CASCADE,
+
+    -- Fingerprint of the document content and its parsing and chunking configurations.
+    config_hash TEXT NOT NULL,
+
+    -- Position of the chunk in its document.
+    chunk_index INT NOT NULL,
+
+    -- Byte offsets of the chunk in the parsed document. NULL if the chunker
+    -- changed the chunk's content so it cannot be located.
+    start_offset INT,
+
+    end_offset INT,
+
+    content TEXT NOT NULL,
+
+    content_hash TEXT NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE (document_id, config_hash, chunk_index)
+);
+
+CREATE INDEX chunks_content_search ON chunks USING GIN (to_tsvector('simple', content));
+
+SELECT manage_updated_at('chunks');
+
+-- Chunk the vector was created from. NULL for vectors inserted before chunks were tracked.
+ALTER TABLE vectors ADD COLUMN chunk_id UUID;
@@ -0,0 +1,2 @@
+ALTER TABLE documents DROP COLUMN preset_id;
+DROP TABLE presets;
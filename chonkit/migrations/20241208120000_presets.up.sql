@@ -0,0 +1,20 @@
+-- Named parsing and chunking configurations shared across documents.
+CREATE TABLE presets (
+    id UUID PRIMARY KEY,
+
+    name TEXT UNIQUE NOT NULL,
+
+    parse_config JSONB NOT NULL,
+
+    chunk_config JSONB NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+SELECT manage_updated_at('presets');
+
+-- Preset the document's parsing and chunking configurations are kept in sync with.
+-- Documents keep their last configurations when the preset is deleted.
+ALTER TABLE documents ADD COLUMN preset_id UUID REFERENCES presets ON DELETE SET NULL;
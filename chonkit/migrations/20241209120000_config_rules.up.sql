@@ -0,0 +1,25 @@
+-- Rules for choosing the initial parsing and chunking configurations of new documents.
+CREATE TABLE config_rules (
+    id UUID PRIMARY KEY,
+
+    name TEXT UNIQUE NOT NULL,
+
+    -- Rules with higher priorities are checked first.
+    priority INTEGER NOT NULL DEFAULT 0,
+
+    -- Conditions a document must satisfy for the rule to apply.
+    criteria JSONB NOT NULL,
+
+    -- Either a preset to link documents to, or the configurations to assign.
+    preset_id UUID REFERENCES presets ON DELETE CASCADE,
+
+    parse_config JSONB,
+
+    chunk_config JSONB,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+SELECT manage_updated_at('config_rules');
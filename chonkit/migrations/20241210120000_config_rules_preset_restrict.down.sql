@@ -0,0 +1,3 @@
+ALTER TABLE config_rules DROP CONSTRAINT config_rules_preset_id_fkey;
+ALTER TABLE config_rules ADD CONSTRAINT config_rules_preset_id_fkey
+    FOREIGN KEY (preset_id) REFERENCES presets ON DELETE CASCADE;
@@ -0,0 +1,5 @@
+-- Presets used by configuration rules cannot be deleted. Deleting them along with
+-- their rules would silently change the configurations new documents start with.
+ALTER TABLE config_rules DROP CONSTRAINT config_rules_preset_id_fkey;
+ALTER TABLE config_rules ADD CONSTRAINT config_rules_preset_id_fkey
+    FOREIGN KEY (preset_id) REFERENCES presets ON DELETE RESTRICT;
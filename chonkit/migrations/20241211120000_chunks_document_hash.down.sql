@@ -0,0 +1 @@
+ALTER TABLE chunks DROP COLUMN document_hash;
@@ -0,0 +1,5 @@
+-- Hash of the document content the chunks were created from. Manually curated chunk lists
+-- whose hash differs from the document's were saved for a previous version of its content.
+ALTER TABLE chunks ADD COLUMN document_hash TEXT;
+
+UPDATE chunks ch SET document_hash = d.hash FROM documents d WHERE d.id = ch.document_id;
@@ -3,18 +3,42 @@
 /// Batch embedder implementation.
 pub mod batch;
 
+/// Re-embedding collections into new collections.
+pub mod clone;
+
+/// Assembling search results into contexts for language models.
+pub mod context;
+
 /// Document storage implementations.
 pub mod document;
 
 /// Text embedder implementations.
 pub mod embedder;
 
+/// Retrieval evaluation runs.
+pub mod eval;
+
+/// Language model clients.
+pub mod generator;
+
+/// Background job tracking.
+pub mod job;
+
+/// Generating evaluation questions with chat models.
+pub mod questions;
+
+/// Reconciliation of embeddings with vector databases.
+pub mod reconcile;
+
 /// Repository implementations.
 pub mod repo;
 
 /// Application state configuration.
 pub mod state;
 
+/// Chunking configuration sweeps.
+pub mod sweep;
+
 /// Vector database implementations.
 pub mod vector;
 
@@ -171,19 +171,15 @@ impl BatchEmbedder {
 
             // Get the content and chunk it
 
-            let content = ok_or_continue!(services.document.get_content(document_id).await);
-            let chunks = ok_or_continue!(services.document.get_chunks(&document, &content).await);
-            let chunks = match chunks {
-                crate::core::chunk::ChunkedDocument::Ref(r) => r,
-                crate::core::chunk::ChunkedDocument::Owned(ref o) => {
-                    o.iter().map(|s| s.as_str()).collect()
-                }
-            };
+            let stored = ok_or_continue!(services.document.get_or_create_chunks(document_id).await);
+            let chunks: Vec<&str> = stored.iter().map(|chunk| chunk.content.as_str()).collect();
+            let chunk_ids: Vec<Uuid> = stored.iter().map(|chunk| chunk.id).collect();
 
             let create = CreateEmbeddings {
                 document_id: document.id,
                 collection_id: collection.id,
                 chunks: &chunks,
+                chunk_ids: Some(&chunk_ids),
             };
 
             let embeddings = ok_or_continue!(services.vector.create_embeddings(create).await);
@@ -191,6 +187,7 @@ impl BatchEmbedder {
             let report = report
                 .embeddings_id(embeddings.id)
                 .model_used(collection.model.clone())
+                .sparse_model_used(collection.sparse_model.clone())
                 .vector_db(collection.provider.clone())
                 .total_chunks(chunks.len())
                 .finished_at(Utc::now())
@@ -303,7 +300,8 @@ pub struct EmbeddingAddReport {
     pub document_id: Uuid,
     pub collection_id: Uuid,
     pub embeddings_id: Uuid,
-    pub model_used: String,
+    pub model_used: Option<String>,
+    pub sparse_model_used: Option<String>,
     pub vector_db: String,
     pub total_chunks: usize,
     pub started_at: chrono::DateTime<chrono::Utc>,
@@ -324,6 +322,7 @@ struct EmbeddingAddReportBuilder {
     collection_id: Uuid,
     embeddings_id: Option<Uuid>,
     model_used: Option<String>,
+    sparse_model_used: Option<String>,
     vector_db: Option<String>,
     total_chunks: Option<usize>,
     started_at: chrono::DateTime<chrono::Utc>,
@@ -338,6 +337,7 @@ impl EmbeddingAddReportBuilder {
             started_at: chrono::Utc::now(),
             embeddings_id: None,
             model_used: None,
+            sparse_model_used: None,
             vector_db: None,
             total_chunks: None,
             finished_at: None,
@@ -349,8 +349,13 @@ impl EmbeddingAddReportBuilder {
         self
     }
 
-    fn model_used(mut self, model_used: String) -> Self {
-        self.model_used = Some(model_used);
+    fn model_used(mut self, model_used: Option<String>) -> Self {
+        self.model_used = model_used;
+        self
+    }
+
+    fn sparse_model_used(mut self, sparse_model_used: Option<String>) -> Self {
+        self.sparse_model_used = sparse_model_used;
         self
     }
 
@@ -374,7 +379,8 @@ impl EmbeddingAddReportBuilder {
             document_id: self.document_id,
             collection_id: self.collection_id,
             embeddings_id: self.embeddings_id.unwrap(),
-            model_used: self.model_used.unwrap(),
+            model_used: self.model_used,
+            sparse_model_used: self.sparse_model_used,
             vector_db: self.vector_db.unwrap(),
             total_chunks: self.total_chunks.unwrap(),
             started_at: self.started_at,
@@ -0,0 +1,81 @@
+use super::{
+    job::{Job, JobKind, JobTracker},
+    state::ServiceState,
+};
+use crate::{core::service::vector::dto::CloneCollectionPayload, error::ChonkitError};
+use uuid::Uuid;
+
+/// Create a new collection from `source_id` and re-embed all of the source's documents
+/// into it in a background job, using each document's current parsing and chunking
+/// configuration.
+///
+/// The target collection is created before the job starts so invalid parameters are
+/// reported immediately. If an alias is given, it is pointed to the target collection
+/// once every document is embedded successfully.
+///
+/// * `services`: Chonkit services.
+/// * `jobs`: Where to track the job.
+/// * `source_id`: Source collection ID.
+/// * `payload`: Target collection parameters.
+pub async fn clone_collection(
+    services: &ServiceState,
+    jobs: &JobTracker,
+    source_id: Uuid,
+    payload: CloneCollectionPayload,
+) -> Result<Job, ChonkitError> {
+    let alias = payload.alias.clone();
+
+    let (target, documents) = services
+        .vector
+        .create_clone_target(source_id, payload)
+        .await?;
+
+    let kind = JobKind::Clone {
+        source_collection: source_id,
+        target_collection: target.id,
+        alias: alias.clone(),
+    };
+
+    let job = jobs.start(kind, documents.len());
+
+    tracing::info!(
+        "Starting job '{}' | Cloning collection '{source_id}' into '{}' | {} document(s)",
+        job.id,
+        target.name,
+        documents.len()
+    );
+
+    let services = services.clone();
+    let jobs = jobs.clone();
+    let job_id = job.id;
+
+    tokio::spawn(async move {
+        for document_id in documents {
+            match services.embed_document(document_id, target.id).await {
+                Ok(_) => jobs.item_ok(job_id),
+                Err(e) => {
+                    e.print();
+                    jobs.item_err(job_id, format!("Embedding document '{document_id}': {e}"));
+                }
+            }
+        }
+
+        if let Some(alias) = alias {
+            let failed = jobs.get(job_id).map(|job| job.failed).unwrap_or_default();
+            if failed > 0 {
+                jobs.error(
+                    job_id,
+                    format!("Alias '{alias}' not swapped; {failed} document(s) failed"),
+                );
+            } else if let Err(e) = services.vector.swap_alias(&alias, target.id).await {
+                jobs.error(job_id, format!("Swapping alias '{alias}': {e}"));
+            }
+        }
+
+        jobs.finish(job_id);
+
+        tracing::info!("Job '{job_id}' finished");
+    });
+
+    Ok(job)
+}
@@ -0,0 +1,585 @@
+use super::state::ServiceState;
+use crate::{
+    core::{
+        document::parser::ParsedContent, model::document::DocumentType, search::FederatedHit,
+        service::vector::dto::SearchPayload,
+    },
+    error::ChonkitError,
+    map_err,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, ops::Range};
+use tracing::warn;
+use uuid::Uuid;
+use validify::Validate;
+
+/// Used to estimate the amount of tokens in a text since
+/// the tokenizer of the consuming model is unknown.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Params for assembling a context.
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextPayload {
+    /// The search to obtain the passages with. Any amount of collections can be
+    /// searched, the results are merged as in federated searches.
+    #[serde(flatten)]
+    pub search: SearchPayload,
+
+    /// Maximum amount of tokens in the context block, including source labels.
+    /// Tokens are estimated as 4 characters each.
+    #[validate(range(min = 1.))]
+    pub max_tokens: usize,
+}
+
+/// A context block ready to be passed to a model, along with its sources.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Context {
+    /// Passages labeled with the numbers of their citations, most relevant first.
+    pub context: String,
+
+    /// Estimated amount of tokens in `context`.
+    pub tokens: usize,
+
+    pub citations: Vec<Citation>,
+}
+
+/// The source of a passage in a context block.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Citation {
+    /// Number the passage is labeled with in the context, e.g. `[1]`.
+    pub label: usize,
+
+    pub document_id: Option<Uuid>,
+
+    /// `None` if the document no longer exists.
+    pub document_name: Option<String>,
+
+    /// Collection the passage was found in.
+    pub collection_id: Uuid,
+
+    pub collection_name: String,
+
+    /// Byte offset of the passage in the parsed document content. `None` if the passage
+    /// could not be located, e.g. when the document's parsing configuration changed
+    /// after it was embedded.
+    pub start: Option<usize>,
+
+    /// Byte offset of the end of the passage in the parsed document content.
+    pub end: Option<usize>,
+
+    /// Pages the passage spans. Empty for documents without pages.
+    pub pages: Vec<usize>,
+
+    /// Best reciprocal rank fusion score of the hits merged into the passage.
+    pub score: f64,
+}
+
+/// A continuous piece of a document made from one or more search hits.
+#[derive(Debug)]
+struct Passage {
+    text: String,
+    document_id: Option<Uuid>,
+    collection_id: Uuid,
+    collection_name: String,
+    range: Option<Range<usize>>,
+    /// Rank of the best hit in the passage.
+    rank: usize,
+    score: f64,
+}
+
+impl Passage {
+    fn new(rank: usize, hit: FederatedHit, range: Option<Range<usize>>) -> Self {
+        Self {
+            text: hit.content,
+            document_id: hit.document_id,
+            collection_id: hit.collection_id,
+            collection_name: hit.collection_name,
+            range,
+            rank,
+            score: hit.rrf_score,
+        }
+    }
+}
+
+/// Search the collections and assemble the results into a context block that fits into
+/// the token budget.
+///
+/// Hits are located in their documents so that overlapping and adjacent hits of the same
+/// document can be merged into a single passage. Hits linked to stored chunks are located
+/// by the chunks' offsets. Documents are only parsed to locate the hits without stored
+/// offsets and to find the pages of paginated documents. Passages are ordered by their
+/// best hit and added to the context until the budget is exhausted, skipping those
+/// that do not fit.
+///
+/// * `services`: Chonkit services.
+/// * `payload`: Search params and token budget.
+pub async fn assemble_context(
+    services: &ServiceState,
+    payload: ContextPayload,
+) -> Result<Context, ChonkitError> {
+    map_err!(payload.validate());
+
+    let hits = services.vector.federated_search(payload.search).await?;
+
+    let offsets = chunk_offsets(services, &hits).await;
+
+    let mut names = HashMap::new();
+    let mut contents = HashMap::new();
+
+    for document_id in hits.iter().filter_map(|hit| hit.document_id) {
+        if names.contains_key(&document_id) {
+            continue;
+        }
+
+        // Passages of documents that cannot be read are cited without their names
+        let document = match services.document.get_document(document_id).await {
+            Ok(document) => document,
+            Err(e) => {
+                warn!("Cannot cite document '{document_id}': {e}");
+                names.insert(document_id, None);
+                continue;
+            }
+        };
+
+        let located = hits
+            .iter()
+            .filter(|hit| hit.document_id == Some(document_id))
+            .all(|hit| hit.chunk_id.is_some_and(|id| offsets.contains_key(&id)));
+
+        let paginated = matches!(
+            DocumentType::try_from(document.ext.as_str()),
+            Ok(DocumentType::Pdf)
+        );
+
+        if !located || paginated {
+            match services.document.get_parsed_content(document_id).await {
+                Ok(content) => {
+                    contents.insert(document_id, content);
+                }
+                Err(e) => warn!("Cannot locate passages in document '{document_id}': {e}"),
+            }
+        }
+
+        names.insert(document_id, Some(document.name));
+    }
+
+    let passages = merge_passages(hits, &contents, &offsets);
+
+    Ok(fit_budget(passages, payload.max_tokens, &names, &contents))
+}
+
+/// Load the stored chunks the hits are linked to in a single query and get their offsets
+/// in the parsed content of their documents. Chunks whose content differs from their hit's
+/// are skipped.
+///
+/// * `services`: Chonkit services.
+/// * `hits`: Search hits.
+async fn chunk_offsets(
+    services: &ServiceState,
+    hits: &[FederatedHit],
+) -> HashMap<Uuid, Range<usize>> {
+    let linked: HashMap<Uuid, &FederatedHit> = hits
+        .iter()
+        .filter_map(|hit| Some((hit.chunk_id?, hit)))
+        .collect();
+
+    if linked.is_empty() {
+        return HashMap::new();
+    }
+
+    let ids = linked.keys().copied().collect::<Vec<_>>();
+
+    let chunks = match services.document.get_chunks_by_id(&ids).await {
+        Ok(chunks) => chunks,
+        Err(e) => {
+            warn!("Cannot load the chunks of the search hits: {e}");
+            return HashMap::new();
+        }
+    };
+
+    chunks
+        .into_iter()
+        .filter_map(|chunk| {
+            let hit = linked.get(&chunk.id)?;
+            if hit.document_id != Some(chunk.document_id) || hit.content != chunk.content {
+                return None;
+            }
+            let range = chunk.start_offset? as usize..chunk.end_offset? as usize;
+            (range.len() == chunk.content.len()).then_some((chunk.id, range))
+        })
+        .collect()
+}
+
+/// Merge hits of the same document that overlap or are adjacent in the document's content
+/// into passages. Hits that cannot be located are kept as they are, without duplicates.
+/// Passages are ordered by their best hit.
+///
+/// * `hits`: Search hits, best first.
+/// * `contents`: Parsed contents of the documents the hits belong to.
+/// * `offsets`: Offsets of the stored chunks the hits are linked to.
+fn merge_passages(
+    hits: Vec<FederatedHit>,
+    contents: &HashMap<Uuid, ParsedContent>,
+    offsets: &HashMap<Uuid, Range<usize>>,
+) -> Vec<Passage> {
+    let mut located: HashMap<Uuid, Vec<Passage>> = HashMap::new();
+    let mut passages: Vec<Passage> = vec![];
+
+    for (rank, hit) in hits.into_iter().enumerate() {
+        let range = hit.document_id.and_then(|id| {
+            let text = contents.get(&id).map(|content| content.text.as_str());
+
+            // Offsets are stale if the document was reparsed differently since
+            let stored = hit
+                .chunk_id
+                .and_then(|chunk_id| offsets.get(&chunk_id))
+                .filter(|range| {
+                    text.is_none_or(|text| text.get((*range).clone()) == Some(&hit.content))
+                });
+
+            if let Some(range) = stored {
+                return Some(range.clone());
+            }
+
+            let start = text?.find(&hit.content)?;
+            Some(start..start + hit.content.len())
+        });
+
+        match (hit.document_id, range) {
+            (Some(document_id), Some(range)) => located
+                .entry(document_id)
+                .or_default()
+                .push(Passage::new(rank, hit, Some(range))),
+            _ => {
+                let duplicate = passages
+                    .iter()
+                    .any(|p| p.document_id == hit.document_id && p.text == hit.content);
+                if !duplicate {
+                    passages.push(Passage::new(rank, hit, None));
+                }
+            }
+        }
+    }
+
+    for (document_id, mut hits) in located {
+        // Documents whose hits were all located by stored offsets are not parsed
+        let text = contents
+            .get(&document_id)
+            .map(|content| content.text.as_str());
+
+        hits.sort_by_key(|p| p.range.as_ref().map(|r| r.start));
+
+        let mut merged: Vec<Passage> = vec![];
+
+        for hit in hits {
+            let range = hit.range.clone().expect("located");
+
+            let Some(last) = merged.last_mut() else {
+                merged.push(hit);
+                continue;
+            };
+
+            let last_range = last.range.as_mut().expect("located");
+
+            // Overlapping, or separated only by whitespace
+            let adjacent = range.start <= last_range.end
+                || text.is_some_and(|text| text[last_range.end..range.start].trim().is_empty());
+
+            if !adjacent {
+                merged.push(hit);
+                continue;
+            }
+
+            // Without the content, extend the passage by the part of the hit past its end
+            if text.is_none() && range.end > last_range.end {
+                if let Some(tail) = hit.text.get(last_range.end - range.start..) {
+                    last.text.push_str(tail);
+                }
+            }
+
+            last_range.end = last_range.end.max(range.end);
+
+            if hit.rank < last.rank {
+                last.rank = hit.rank;
+                last.collection_id = hit.collection_id;
+                last.collection_name = hit.collection_name;
+            }
+
+            last.score = last.score.max(hit.score);
+        }
+
+        if let Some(text) = text {
+            for passage in merged.iter_mut() {
+                let range = passage.range.clone().expect("located");
+                passage.text = text[range].to_string();
+            }
+        }
+
+        passages.extend(merged);
+    }
+
+    passages.sort_by_key(|p| p.rank);
+
+    passages
+}
+
+/// Add passages to the context until the token budget is exhausted.
+/// Passages that do not fit are skipped.
+///
+/// * `passages`: Passages, most relevant first.
+/// * `max_tokens`: Token budget.
+/// * `names`: Names of the documents the passages belong to.
+/// * `contents`: Parsed contents of the documents the passages belong to.
+fn fit_budget(
+    passages: Vec<Passage>,
+    max_tokens: usize,
+    names: &HashMap<Uuid, Option<String>>,
+    contents: &HashMap<Uuid, ParsedContent>,
+) -> Context {
+    let mut context = String::new();
+    let mut tokens = 0;
+    let mut citations: Vec<Citation> = vec![];
+
+    for passage in passages {
+        let label = citations.len() + 1;
+
+        let document_name = passage
+            .document_id
+            .and_then(|id| names.get(&id).cloned().flatten());
+
+        let pages = match (passage.document_id, &passage.range) {
+            (Some(id), Some(range)) => contents
+                .get(&id)
+                .map(|content| {
+                    let start = content.page_at(range.start);
+                    let end = content.page_at(range.end.saturating_sub(1));
+                    match (start, end) {
+                        (Some(start), Some(end)) => (start..=end).collect(),
+                        _ => vec![],
+                    }
+                })
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+
+        let source = document_name
+            .as_deref()
+            .unwrap_or(passage.collection_name.as_str());
+
+        let heading = match (pages.first(), pages.last()) {
+            (Some(first), Some(last)) if first == last => format!("[{label}] {source}, p. {first}"),
+            (Some(first), Some(last)) => format!("[{label}] {source}, pp. {first}-{last}"),
+            _ => format!("[{label}] {source}"),
+        };
+
+        let block = if context.is_empty() {
+            format!("{heading}\n{}", passage.text)
+        } else {
+            format!("\n\n{heading}\n{}", passage.text)
+        };
+
+        let block_tokens = estimate_tokens(&block);
+
+        if tokens + block_tokens > max_tokens {
+            continue;
+        }
+
+        tokens += block_tokens;
+        context.push_str(&block);
+
+        citations.push(Citation {
+            label,
+            document_id: passage.document_id,
+            document_name,
+            collection_id: passage.collection_id,
+            collection_name: passage.collection_name,
+            start: passage.range.as_ref().map(|range| range.start),
+            end: passage.range.as_ref().map(|range| range.end),
+            pages,
+            score: passage.score,
+        });
+    }
+
+    Context {
+        context,
+        tokens,
+        citations,
+    }
+}
+
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(content: &str, document_id: Uuid, rrf_score: f64) -> FederatedHit {
+        FederatedHit {
+            content: content.to_string(),
+            document_id: Some(document_id),
+            chunk_index: None,
+            chunk_id: None,
+            collection_id: Uuid::nil(),
+            collection_name: "collection".to_string(),
+            provider: "test".to_string(),
+            score: rrf_score,
+            normalized_score: 1.,
+            rrf_score,
+        }
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent_hits() {
+        let document_id = Uuid::new_v4();
+        let text = "Alpha beta gamma. Delta epsilon.\nZeta eta theta. Iota kappa.";
+
+        let contents = HashMap::from([(
+            document_id,
+            ParsedContent {
+                text: text.to_string(),
+                pages: vec![],
+            },
+        )]);
+
+        let hits = vec![
+            hit("Delta epsilon.", document_id, 0.5),
+            hit("Iota kappa.", document_id, 0.4),
+            hit("beta gamma. Delta", document_id, 0.3),
+            // Not part of the content, kept as is
+            hit("Lost chunk", document_id, 0.2),
+            hit("Lost chunk", document_id, 0.1),
+        ];
+
+        let passages = merge_passages(hits, &contents, &HashMap::new());
+
+        assert_eq!(3, passages.len());
+        assert_eq!("beta gamma. Delta epsilon.", passages[0].text);
+        assert_eq!(Some(6..32), passages[0].range);
+        assert_eq!(0, passages[0].rank);
+        assert_eq!(0.5, passages[0].score);
+        assert_eq!("Iota kappa.", passages[1].text);
+        assert_eq!("Lost chunk", passages[2].text);
+        assert_eq!(None, passages[2].range);
+    }
+
+    #[test]
+    fn locates_hits_by_chunk_offsets() {
+        let document_id = Uuid::new_v4();
+        let text = "Repeated. Something else entirely. Repeated.";
+
+        let contents = HashMap::from([(
+            document_id,
+            ParsedContent {
+                text: text.to_string(),
+                pages: vec![],
+            },
+        )]);
+
+        let chunk_id = Uuid::new_v4();
+        let stale_id = Uuid::new_v4();
+        let offsets = HashMap::from([(chunk_id, 35..44), (stale_id, 0..9)]);
+
+        let mut linked = hit("Repeated.", document_id, 0.5);
+        linked.chunk_id = Some(chunk_id);
+
+        let mut stale = hit("Something", document_id, 0.4);
+        stale.chunk_id = Some(stale_id);
+
+        let passages = merge_passages(vec![linked, stale], &contents, &offsets);
+
+        assert_eq!(Some(35..44), passages[0].range);
+        assert_eq!(Some(10..19), passages[1].range);
+    }
+
+    #[test]
+    fn merges_hits_by_chunk_offsets_without_content() {
+        let document_id = Uuid::new_v4();
+
+        let (first_id, second_id, far_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let offsets = HashMap::from([(first_id, 0..11), (second_id, 6..17), (far_id, 30..35)]);
+
+        let mut first = hit("Alpha beta.", document_id, 0.5);
+        first.chunk_id = Some(first_id);
+
+        let mut second = hit("beta. Gamma", document_id, 0.4);
+        second.chunk_id = Some(second_id);
+
+        let mut far = hit("Omega", document_id, 0.3);
+        far.chunk_id = Some(far_id);
+
+        let passages = merge_passages(vec![second, far, first], &HashMap::new(), &offsets);
+
+        assert_eq!(2, passages.len());
+        assert_eq!("Alpha beta. Gamma", passages[0].text);
+        assert_eq!(Some(0..17), passages[0].range);
+        assert_eq!(0, passages[0].rank);
+        assert_eq!("Omega", passages[1].text);
+        assert_eq!(Some(30..35), passages[1].range);
+    }
+
+    #[test]
+    fn fits_budget_with_citations() {
+        let document_id = Uuid::new_v4();
+        let text = "First page.\nSecond page.\nThird page.\n";
+
+        let contents = HashMap::from([(
+            document_id,
+            ParsedContent {
+                text: text.to_string(),
+                pages: vec![(0, 1), (12, 2), (25, 3)],
+            },
+        )]);
+        let names = HashMap::from([(document_id, Some("doc.pdf".to_string()))]);
+
+        let hits = vec![
+            hit("page.\nSecond", document_id, 0.5),
+            hit("Third page.", document_id, 0.4),
+        ];
+
+        let passages = merge_passages(hits, &contents, &HashMap::new());
+        let context = fit_budget(passages, 1000, &names, &contents);
+
+        assert_eq!(
+            "[1] doc.pdf, pp. 1-2\npage.\nSecond\n\n[2] doc.pdf, p. 3\nThird page.",
+            context.context
+        );
+        assert_eq!(2, context.citations.len());
+        assert_eq!(vec![1, 2], context.citations[0].pages);
+        assert_eq!(Some(6), context.citations[0].start);
+        assert_eq!(Some(18), context.citations[0].end);
+        assert_eq!(vec![3], context.citations[1].pages);
+
+        // Only the first passage fits
+        let passages = merge_passages(
+            vec![
+                hit("page.\nSecond", document_id, 0.5),
+                hit("Third page.", document_id, 0.4),
+            ],
+            &contents,
+            &HashMap::new(),
+        );
+        let budget = estimate_tokens("[1] doc.pdf, pp. 1-2\npage.\nSecond");
+        let context = fit_budget(passages, budget, &names, &contents);
+
+        assert_eq!(1, context.citations.len());
+        assert_eq!(budget, context.tokens);
+    }
+
+    #[test]
+    fn locates_pages() {
+        let content = ParsedContent {
+            text: "a".repeat(30),
+            pages: vec![(0, 3), (10, 4), (20, 5)],
+        };
+        assert_eq!(Some(3), content.page_at(0));
+        assert_eq!(Some(3), content.page_at(9));
+        assert_eq!(Some(4), content.page_at(10));
+        assert_eq!(Some(5), content.page_at(29));
+        assert_eq!(None, ParsedContent::default().page_at(0));
+    }
+}
@@ -1,5 +1,9 @@
 use crate::config::{DEFAULT_COLLECTION_EMBEDDING_MODEL, DEFAULT_COLLECTION_SIZE};
-use crate::{core::embedder::Embedder, error::ChonkitError, map_err};
+use crate::{
+    core::embedder::{Embedder, SparseEmbedding},
+    error::ChonkitError,
+    map_err,
+};
 
 pub use chonkit_embedders::fastembed::local::LocalFastEmbedder;
 
@@ -17,14 +21,59 @@ impl Embedder for LocalFastEmbedder {
     }
 
     async fn list_embedding_models(&self) -> Result<Vec<(String, usize)>, ChonkitError> {
-        Ok(self
-            .list_models()
-            .into_iter()
-            .map(|m| (m.model_code, m.dim))
-            .collect())
+        Ok(self.list_models())
     }
 
     async fn embed(&self, content: &[&str], model: &str) -> Result<Vec<Vec<f64>>, ChonkitError> {
-        Ok(map_err!(self.embed(content, model)))
+        let embedder = self.clone();
+        let (content, model) = to_owned(content, model);
+
+        // Loading models and running inference blocks
+        let embeddings = map_err!(
+            tokio::task::spawn_blocking(move || {
+                let content: Vec<&str> = content.iter().map(String::as_str).collect();
+                embedder.embed(&content, &model)
+            })
+            .await
+        );
+
+        Ok(map_err!(embeddings))
+    }
+
+    async fn list_sparse_models(&self) -> Result<Vec<String>, ChonkitError> {
+        Ok(self.list_sparse_models())
+    }
+
+    async fn embed_sparse(
+        &self,
+        content: &[&str],
+        model: &str,
+    ) -> Result<Vec<SparseEmbedding>, ChonkitError> {
+        let embedder = self.clone();
+        let (content, model) = to_owned(content, model);
+
+        let embeddings = map_err!(
+            tokio::task::spawn_blocking(move || {
+                let content: Vec<&str> = content.iter().map(String::as_str).collect();
+                embedder.embed_sparse(&content, &model)
+            })
+            .await
+        );
+
+        Ok(map_err!(embeddings)
+            .into_iter()
+            .map(|e| SparseEmbedding {
+                indices: e.indices,
+                values: e.values,
+            })
+            .collect())
     }
 }
+
+/// Copy the arguments so they can be moved to a blocking task.
+fn to_owned(content: &[&str], model: &str) -> (Vec<String>, String) {
+    (
+        content.iter().map(|c| c.to_string()).collect(),
+        model.to_string(),
+    )
+}
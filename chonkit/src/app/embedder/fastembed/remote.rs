@@ -1,5 +1,9 @@
 use crate::config::{DEFAULT_COLLECTION_EMBEDDING_MODEL, DEFAULT_COLLECTION_SIZE};
-use crate::{core::embedder::Embedder, error::ChonkitError, map_err};
+use crate::{
+    core::embedder::{Embedder, SparseEmbedding},
+    error::ChonkitError,
+    map_err,
+};
 
 pub use chonkit_embedders::fastembed::remote::RemoteFastEmbedder;
 
@@ -23,4 +27,22 @@ impl Embedder for RemoteFastEmbedder {
     async fn embed(&self, content: &[&str], model: &str) -> Result<Vec<Vec<f64>>, ChonkitError> {
         Ok(map_err!(self.embed(content, model).await))
     }
+
+    async fn list_sparse_models(&self) -> Result<Vec<String>, ChonkitError> {
+        Ok(map_err!(self.list_sparse_models().await))
+    }
+
+    async fn embed_sparse(
+        &self,
+        content: &[&str],
+        model: &str,
+    ) -> Result<Vec<SparseEmbedding>, ChonkitError> {
+        Ok(map_err!(self.embed_sparse(content, model).await)
+            .into_iter()
+            .map(|e| SparseEmbedding {
+                indices: e.indices,
+                values: e.values,
+            })
+            .collect())
+    }
 }
@@ -15,7 +15,7 @@ impl Embedder for OpenAiEmbeddings {
     }
 
     async fn list_embedding_models(&self) -> Result<Vec<(String, usize)>, ChonkitError> {
-        Ok(self.list_embedding_models())
+        Ok(map_err!(self.list_embedding_models().await))
     }
 
     async fn embed(&self, content: &[&str], model: &str) -> Result<Vec<Vec<f64>>, ChonkitError> {
@@ -0,0 +1,157 @@
+use super::state::ServiceState;
+use crate::{
+    core::{
+        chunk::ChunkConfig,
+        eval::{self, RetrievalMetrics, Retrieved},
+        model::{
+            collection::Collection,
+            eval::{EvalQuestionResult, EvalRun, EvalRunInsert},
+        },
+        service::{eval::dto::RunEvaluationPayload, vector::dto::SearchPayload},
+    },
+    error::ChonkitError,
+    map_err,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+use validify::Validify;
+
+/// Amount of chunks retrieved per question if not specified.
+pub(super) const DEFAULT_K: u32 = 10;
+
+/// Evaluate the retrieval quality of a collection with the questions of a dataset and
+/// store the results.
+///
+/// Every question is searched in the collection and the top `k` chunks are scored
+/// against what the question is expected to retrieve. The chunking configurations of the
+/// involved documents are stored with the results so runs can be compared.
+///
+/// * `services`: Chonkit services.
+/// * `dataset_id`: Dataset ID.
+/// * `payload`: Collection to evaluate and amount of chunks to retrieve.
+pub async fn run_evaluation(
+    services: &ServiceState,
+    dataset_id: Uuid,
+    mut payload: RunEvaluationPayload,
+) -> Result<EvalRun, ChonkitError> {
+    map_err!(payload.validify());
+
+    let collection = services
+        .vector
+        .get_collection(payload.collection_id)
+        .await?;
+
+    let k = payload.k.unwrap_or(DEFAULT_K);
+
+    evaluate_collection(services, dataset_id, &collection, k, None).await
+}
+
+/// Evaluate a collection with the questions of a dataset and store the results.
+///
+/// * `services`: Chonkit services.
+/// * `dataset_id`: Dataset ID.
+/// * `collection`: The collection to search.
+/// * `k`: Amount of chunks to retrieve per question.
+/// * `chunk_configs`: Chunking configurations the collection's documents were embedded with.
+///   If not given, the current configurations of the involved documents are stored.
+pub(super) async fn evaluate_collection(
+    services: &ServiceState,
+    dataset_id: Uuid,
+    collection: &Collection,
+    k: u32,
+    chunk_configs: Option<HashMap<Uuid, ChunkConfig>>,
+) -> Result<EvalRun, ChonkitError> {
+    let questions = services.eval.list_questions(dataset_id).await?;
+
+    let mut results = Vec::with_capacity(questions.len());
+    let mut document_ids = vec![];
+
+    for question in questions {
+        let search = SearchPayload {
+            query: question.question.clone(),
+            collection_id: Some(collection.id),
+            alias: None,
+            collection_name: None,
+            provider: None,
+            collections: vec![],
+            aliases: vec![],
+            limit: Some(k),
+            vector: None,
+            rescore: None,
+            oversampling: None,
+            mmr_lambda: None,
+            max_per_document: None,
+            candidates: None,
+        };
+
+        let hits = services.vector.federated_search(search).await?;
+
+        let retrieved: Vec<Retrieved> = hits
+            .iter()
+            .map(|hit| Retrieved {
+                document_id: hit.document_id,
+                content: &hit.content,
+            })
+            .collect();
+
+        let metrics = eval::score(&question.documents, &question.spans, &retrieved, k as usize);
+
+        document_ids.extend(question.documents.iter().copied());
+        document_ids.extend(hits.iter().filter_map(|hit| hit.document_id));
+
+        results.push(EvalQuestionResult {
+            question_id: question.id,
+            question: question.question,
+            metrics,
+        });
+    }
+
+    let chunk_configs = match chunk_configs {
+        Some(chunk_configs) => chunk_configs,
+        None => current_chunk_configs(services, document_ids).await,
+    };
+
+    let metrics: Vec<RetrievalMetrics> = results.iter().map(|result| result.metrics).collect();
+
+    let insert = EvalRunInsert {
+        id: Uuid::new_v4(),
+        dataset_id,
+        collection_id: collection.id,
+        collection_name: &collection.name,
+        embedder: &collection.embedder,
+        model: collection.model.as_deref(),
+        sparse_model: collection.sparse_model.as_deref(),
+        provider: &collection.provider,
+        k: k as i32,
+        metrics: RetrievalMetrics::mean(&metrics),
+        chunk_configs,
+        questions: results,
+    };
+
+    services.eval.insert_run(insert).await
+}
+
+/// Get the current chunking configurations of documents, skipping ones without any.
+async fn current_chunk_configs(
+    services: &ServiceState,
+    document_ids: Vec<Uuid>,
+) -> HashMap<Uuid, ChunkConfig> {
+    let mut chunk_configs = HashMap::new();
+
+    for document_id in document_ids {
+        if chunk_configs.contains_key(&document_id) {
+            continue;
+        }
+
+        // Documents deleted since they were added to the dataset have no configuration
+        let Ok(config) = services.document.get_config(document_id).await else {
+            continue;
+        };
+
+        if let Some(chunk_config) = config.chunk_config {
+            chunk_configs.insert(document_id, chunk_config);
+        }
+    }
+
+    chunk_configs
+}
@@ -0,0 +1,177 @@
+use crate::{core::generator::Generator, err, error::ChonkitError, map_err};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Timeout for chat requests. Local models can take a while to respond.
+const CHAT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Client for OpenAI compatible chat completion APIs, such as Ollama and vLLM.
+pub struct OpenAiChat {
+    endpoint: String,
+    key: Option<String>,
+    model: Option<String>,
+    client: reqwest::Client,
+}
+
+impl OpenAiChat {
+    /// The endpoint should not contain the `/v1` path.
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            key: None,
+            model: None,
+            client: reqwest::Client::builder()
+                .timeout(CHAT_TIMEOUT)
+                .build()
+                .expect("error while building http client"),
+        }
+    }
+
+    /// Send the key as a bearer token.
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    /// Use `model` when requests do not specify one.
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = Some(model.to_string());
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Generator for OpenAiChat {
+    fn id(&self) -> &'static str {
+        "openai-chat"
+    }
+
+    fn default_model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    async fn chat(&self, model: &str, system: &str, prompt: &str) -> Result<String, ChonkitError> {
+        let request = ChatRequest {
+            model,
+            messages: vec![
+                ChatMessage {
+                    role: "system",
+                    content: system,
+                },
+                ChatMessage {
+                    role: "user",
+                    content: prompt,
+                },
+            ],
+        };
+
+        let mut builder = self
+            .client
+            .post(format!("{}/v1/chat/completions", self.endpoint))
+            .json(&request);
+
+        if let Some(ref key) = self.key {
+            builder = builder.bearer_auth(key);
+        }
+
+        let response = map_err!(builder.send().await);
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return err!(
+                Generation,
+                "chat request failed with status {status}: {body}"
+            );
+        }
+
+        let response: ChatResponse = map_err!(response.json().await);
+
+        match response.choices.into_iter().next() {
+            Some(choice) => Ok(choice.message.content),
+            None => err!(Generation, "chat response contains no choices"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponseMessage {
+    content: String,
+}
+
+/// A chat endpoint responding to every request with the same content.
+#[cfg(test)]
+pub(crate) mod stub {
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+
+    /// Spawn the stub and return its address.
+    ///
+    /// * `content`: Content of every response.
+    pub async fn spawn(content: &'static str) -> String {
+        let router = Router::new().route(
+            "/v1/chat/completions",
+            post(move |Json(request): Json<Value>| async move {
+                Json(json!({
+                    "model": request["model"],
+                    "choices": [{ "index": 0, "message": { "role": "assistant", "content": content } }]
+                }))
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        format!("http://{address}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn chats_with_compatible_endpoint() {
+        let endpoint = stub::spawn("1. Where do penguins live?").await;
+
+        let chat = OpenAiChat::new(&endpoint).with_model("stub");
+        assert_eq!(Some("stub"), chat.default_model());
+
+        let response = chat
+            .chat("stub", "system", "Penguins live in Antarctica.")
+            .await
+            .unwrap();
+
+        assert_eq!("1. Where do penguins live?", response);
+
+        let missing = OpenAiChat::new(&format!("{endpoint}/missing"));
+        let Err(e) = missing.chat("stub", "system", "prompt").await else {
+            panic!("expected an error for a missing endpoint");
+        };
+        assert!(matches!(e.error, crate::error::ChonkitErr::Generation(_)));
+    }
+}
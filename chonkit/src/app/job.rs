@@ -0,0 +1,278 @@
+//! Tracking of long running background jobs.
+
+use super::sweep::SweepReport;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use uuid::Uuid;
+
+/// Finished jobs are evicted this many hours after finishing.
+const FINISHED_JOB_TTL_HOURS: i64 = 24;
+
+/// Maximum amount of finished jobs kept. The jobs that finished first are evicted first.
+const MAX_FINISHED_JOBS: usize = 100;
+
+/// Keeps the state of background jobs in memory so their progress can be queried.
+/// Jobs are lost on restart. Finished jobs are evicted once they expire or when too
+/// many of them are kept, running jobs are never evicted.
+#[derive(Debug, Clone, Default)]
+pub struct JobTracker {
+    jobs: Arc<RwLock<HashMap<Uuid, Job>>>,
+}
+
+impl JobTracker {
+    /// Start tracking a new job and return its initial state.
+    ///
+    /// * `kind`: What the job does.
+    /// * `total`: Total amount of items the job processes.
+    pub fn start(&self, kind: JobKind, total: usize) -> Job {
+        let job = Job {
+            id: Uuid::new_v4(),
+            kind,
+            status: JobStatus::Running,
+            total,
+            processed: 0,
+            failed: 0,
+            errors: vec![],
+            result: None,
+            started_at: Utc::now(),
+            finished_at: None,
+        };
+
+        let mut jobs = self.write();
+        evict_finished(&mut jobs, Utc::now());
+        jobs.insert(job.id, job.clone());
+
+        job
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<Job> {
+        self.read().get(&id).cloned()
+    }
+
+    /// List all tracked jobs, most recent first.
+    pub fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.read().values().cloned().collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.started_at));
+        jobs
+    }
+
+    /// Record a successfully processed item.
+    pub fn item_ok(&self, id: Uuid) {
+        self.update(id, |job| job.processed += 1);
+    }
+
+    /// Record a failed item.
+    pub fn item_err(&self, id: Uuid, error: String) {
+        self.update(id, |job| {
+            job.processed += 1;
+            job.failed += 1;
+            job.errors.push(error);
+        });
+    }
+
+    /// Record an error not related to a single item, e.g. a failed follow-up step.
+    pub fn error(&self, id: Uuid, error: String) {
+        self.update(id, |job| job.errors.push(error));
+    }
+
+    /// Set the output of the job. Can be set multiple times to report partial results.
+    pub fn set_result(&self, id: Uuid, result: JobResult) {
+        self.update(id, |job| job.result = Some(result));
+    }
+
+    /// Mark the job as finished. The job is marked as failed if any errors were recorded.
+    pub fn finish(&self, id: Uuid) {
+        self.update(id, |job| {
+            job.status = if job.errors.is_empty() {
+                JobStatus::Finished
+            } else {
+                JobStatus::Failed
+            };
+            job.finished_at = Some(Utc::now());
+        });
+
+        evict_finished(&mut self.write(), Utc::now());
+    }
+
+    fn update(&self, id: Uuid, f: impl FnOnce(&mut Job)) {
+        if let Some(job) = self.write().get_mut(&id) {
+            f(job);
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<Uuid, Job>> {
+        self.jobs.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<Uuid, Job>> {
+        self.jobs.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Remove finished jobs older than the TTL, then the earliest finished jobs
+/// exceeding the maximum amount of finished jobs.
+fn evict_finished(jobs: &mut HashMap<Uuid, Job>, now: DateTime<Utc>) {
+    let cutoff = now - chrono::Duration::hours(FINISHED_JOB_TTL_HOURS);
+
+    jobs.retain(|_, job| {
+        job.finished_at
+            .is_none_or(|finished_at| finished_at > cutoff)
+    });
+
+    let mut finished: Vec<(DateTime<Utc>, Uuid)> = jobs
+        .values()
+        .filter_map(|job| Some((job.finished_at?, job.id)))
+        .collect();
+
+    if finished.len() <= MAX_FINISHED_JOBS {
+        return;
+    }
+
+    finished.sort_unstable();
+
+    for (_, id) in &finished[..finished.len() - MAX_FINISHED_JOBS] {
+        jobs.remove(id);
+    }
+}
+
+/// The state of a background job.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    pub id: Uuid,
+
+    pub kind: JobKind,
+
+    pub status: JobStatus,
+
+    /// Total amount of items to process.
+    pub total: usize,
+
+    /// Amount of processed items, including failed ones.
+    pub processed: usize,
+
+    /// Amount of items that failed processing.
+    pub failed: usize,
+
+    /// Errors encountered during the job.
+    pub errors: Vec<String>,
+
+    /// The output of the job, for jobs that produce one.
+    pub result: Option<JobResult>,
+
+    pub started_at: DateTime<Utc>,
+
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    Running,
+    /// Finished without errors.
+    Finished,
+    /// Finished with errors.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum JobKind {
+    /// Re-embedding the documents of a collection into another collection.
+    #[serde(rename_all = "camelCase")]
+    Clone {
+        source_collection: Uuid,
+        target_collection: Uuid,
+        /// Alias to point to the target collection once the job succeeds.
+        alias: Option<String>,
+    },
+
+    /// Evaluating chunking configurations of documents against an evaluation dataset.
+    #[serde(rename_all = "camelCase")]
+    Sweep {
+        dataset_id: Uuid,
+        /// Collection whose models and providers are used for the evaluated collections.
+        collection_id: Uuid,
+        documents: Vec<Uuid>,
+        /// Whether the best configuration gets applied to the documents.
+        apply: bool,
+    },
+
+    /// Generating evaluation questions from document chunks with a chat model.
+    #[serde(rename_all = "camelCase")]
+    Questions {
+        /// The dataset the questions are added to.
+        dataset_id: Uuid,
+        documents: Vec<Uuid>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum JobResult {
+    Sweep(SweepReport),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn questions() -> JobKind {
+        JobKind::Questions {
+            dataset_id: Uuid::new_v4(),
+            documents: vec![],
+        }
+    }
+
+    #[test]
+    fn evicts_expired_finished_jobs() {
+        let tracker = JobTracker::default();
+
+        let running = tracker.start(questions(), 1);
+        let expired = tracker.start(questions(), 1);
+        let recent = tracker.start(questions(), 1);
+
+        tracker.finish(expired.id);
+        tracker.finish(recent.id);
+
+        tracker.update(expired.id, |job| {
+            job.finished_at = Some(Utc::now() - chrono::Duration::hours(FINISHED_JOB_TTL_HOURS + 1))
+        });
+
+        tracker.start(questions(), 1);
+
+        assert!(tracker.get(running.id).is_some());
+        assert!(tracker.get(expired.id).is_none());
+        assert!(tracker.get(recent.id).is_some());
+    }
+
+    #[test]
+    fn evicts_earliest_finished_jobs_over_limit() {
+        let tracker = JobTracker::default();
+
+        let running = tracker.start(questions(), 1);
+        let base = Utc::now() - chrono::Duration::hours(1);
+
+        // Distinct finish times keep the eviction order deterministic
+        let finished: Vec<Uuid> = (0..MAX_FINISHED_JOBS + 2)
+            .map(|i| {
+                let job = tracker.start(questions(), 1);
+                tracker.finish(job.id);
+                tracker.update(job.id, |job| {
+                    job.finished_at = Some(base + chrono::Duration::seconds(i as i64))
+                });
+                job.id
+            })
+            .collect();
+
+        assert_eq!(MAX_FINISHED_JOBS + 1, tracker.list().len());
+        assert!(tracker.get(running.id).is_some());
+        assert!(tracker.get(finished[0]).is_none());
+        assert!(tracker.get(finished[1]).is_none());
+        assert!(tracker.get(finished[2]).is_some());
+    }
+}
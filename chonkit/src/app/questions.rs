@@ -0,0 +1,229 @@
+use super::{
+    job::{Job, JobKind, JobTracker},
+    state::ServiceState,
+};
+use crate::{
+    core::{
+        chunk::ChunkedDocument,
+        generator::{
+            parse_questions, render_prompt, sample_indices, Generator, DEFAULT_QUESTION_PROMPT,
+            DEFAULT_QUESTION_SYSTEM_PROMPT,
+        },
+        model::document::Document,
+        service::eval::dto::{
+            CreateEvalDatasetPayload, EvalQuestionPayload, GenerateQuestionsPayload,
+        },
+    },
+    err,
+    error::ChonkitError,
+    map_err,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+use validify::Validify;
+
+/// Amount of chunks sampled per document if not specified.
+const DEFAULT_CHUNKS_PER_DOCUMENT: usize = 5;
+
+/// Create an evaluation dataset and fill it with questions generated from document
+/// chunks in a background job.
+///
+/// Chunks are sampled evenly across each document and the chat model is asked to write
+/// questions answerable by each of them. Every question expects its source document and
+/// the source chunk as a span.
+///
+/// The dataset is created before the job starts so invalid parameters are reported
+/// immediately. Questions are added to it as every document is processed.
+///
+/// * `services`: Chonkit services.
+/// * `jobs`: Where to track the job.
+/// * `generator`: Chat model client.
+/// * `payload`: Dataset name, documents and prompts.
+pub async fn generate_questions(
+    services: &ServiceState,
+    jobs: &JobTracker,
+    generator: Option<Arc<dyn Generator + Send + Sync>>,
+    mut payload: GenerateQuestionsPayload,
+) -> Result<Job, ChonkitError> {
+    map_err!(payload.validify());
+
+    let Some(generator) = generator else {
+        return err!(
+            InvalidProvider,
+            "Question generation requires a chat endpoint; pass --llm-endpoint or set LLM_ENDPOINT"
+        );
+    };
+
+    let Some(model) = payload
+        .model
+        .take()
+        .or_else(|| generator.default_model().map(String::from))
+    else {
+        return err!(
+            InvalidProvider,
+            "No chat model given and no default model configured; pass --llm-model or set LLM_MODEL"
+        );
+    };
+
+    let mut document_ids = std::mem::take(&mut payload.documents);
+    document_ids.sort();
+    document_ids.dedup();
+
+    let mut documents = Vec::with_capacity(document_ids.len());
+    for document_id in document_ids.iter() {
+        documents.push(services.document.get_document(*document_id).await?);
+    }
+
+    let dataset = services
+        .eval
+        .create_dataset(CreateEvalDatasetPayload {
+            name: payload.name,
+            questions: vec![],
+        })
+        .await?
+        .dataset;
+
+    let kind = JobKind::Questions {
+        dataset_id: dataset.id,
+        documents: document_ids,
+    };
+
+    let job = jobs.start(kind, documents.len());
+
+    tracing::info!(
+        "Starting job '{}' | Generating questions for {} document(s) into dataset '{}' with '{model}'",
+        job.id,
+        documents.len(),
+        dataset.name
+    );
+
+    let services = services.clone();
+    let jobs = jobs.clone();
+    let job_id = job.id;
+
+    tokio::spawn(async move {
+        let generation = Generation {
+            services: &services,
+            jobs: &jobs,
+            job_id,
+            dataset_id: dataset.id,
+            generator: generator.as_ref(),
+            model,
+            system_prompt: payload
+                .system_prompt
+                .unwrap_or_else(|| DEFAULT_QUESTION_SYSTEM_PROMPT.to_string()),
+            prompt: payload
+                .prompt
+                .unwrap_or_else(|| DEFAULT_QUESTION_PROMPT.to_string()),
+            chunks_per_document: payload
+                .chunks_per_document
+                .unwrap_or(DEFAULT_CHUNKS_PER_DOCUMENT),
+            questions_per_chunk: payload.questions_per_chunk.unwrap_or(1),
+        };
+
+        for document in documents {
+            match generation.document(&document).await {
+                Ok(_) => jobs.item_ok(job_id),
+                Err(e) => {
+                    e.print();
+                    jobs.item_err(
+                        job_id,
+                        format!("Generating questions for document '{}': {e}", document.id),
+                    );
+                }
+            }
+        }
+
+        jobs.finish(job_id);
+
+        tracing::info!("Job '{job_id}' finished");
+    });
+
+    Ok(job)
+}
+
+struct Generation<'a> {
+    services: &'a ServiceState,
+    jobs: &'a JobTracker,
+    job_id: Uuid,
+    dataset_id: Uuid,
+    generator: &'a (dyn Generator + Send + Sync),
+    model: String,
+    system_prompt: String,
+    prompt: String,
+    chunks_per_document: usize,
+    questions_per_chunk: usize,
+}
+
+impl Generation<'_> {
+    /// Generate questions for the sampled chunks of a document and add them to the dataset.
+    /// Failed chunks are recorded as job errors and skipped.
+    async fn document(&self, document: &Document) -> Result<usize, ChonkitError> {
+        let content = self.services.document.get_content(document.id).await?;
+        let chunks = self
+            .services
+            .document
+            .get_chunks(document, &content)
+            .await?;
+
+        let chunks = match chunks {
+            ChunkedDocument::Ref(r) => r,
+            ChunkedDocument::Owned(ref o) => o.iter().map(|s| s.as_str()).collect(),
+        };
+
+        let mut questions = vec![];
+
+        for i in sample_indices(chunks.len(), self.chunks_per_document) {
+            let chunk = chunks[i].trim();
+
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let prompt = render_prompt(
+                &self.prompt,
+                chunk,
+                &document.name,
+                self.questions_per_chunk,
+            );
+
+            let response = match self
+                .generator
+                .chat(&self.model, &self.system_prompt, &prompt)
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    e.print();
+                    self.jobs.error(
+                        self.job_id,
+                        format!(
+                            "Generating questions for chunk {i} of '{}': {e}",
+                            document.id
+                        ),
+                    );
+                    continue;
+                }
+            };
+
+            for question in parse_questions(&response, self.questions_per_chunk) {
+                questions.push(EvalQuestionPayload {
+                    question,
+                    documents: vec![document.id],
+                    spans: vec![chunk.to_string()],
+                });
+            }
+        }
+
+        let amount = questions.len();
+
+        if amount > 0 {
+            self.services
+                .eval
+                .add_questions(self.dataset_id, questions)
+                .await?;
+        }
+
+        Ok(amount)
+    }
+}
@@ -0,0 +1,120 @@
+use super::state::ServiceState;
+use crate::{core::model::collection::ReconciliationReport, error::ChonkitError};
+use std::{collections::HashSet, str::FromStr};
+use uuid::Uuid;
+
+/// What to do when reconciling on application startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcileMode {
+    /// Only log the differences.
+    Report,
+
+    /// Log and repair the differences.
+    Repair,
+}
+
+impl FromStr for ReconcileMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "report" => Ok(Self::Report),
+            "repair" => Ok(Self::Repair),
+            _ => Err(format!(
+                "invalid reconcile mode '{s}', expected 'report' or 'repair'"
+            )),
+        }
+    }
+}
+
+/// Find the differences between the embeddings in the repository and the vector databases.
+///
+/// When repairing, orphaned vectors of deleted documents are deleted and documents whose
+/// vectors are missing are embedded again. Orphaned vector collections are only reported. Embeddings in collections missing from their
+/// vector database cannot be repaired and are only reported.
+///
+/// * `services`: Chonkit services.
+/// * `repair`: Whether to repair the differences.
+pub async fn reconcile(
+    services: &ServiceState,
+    repair: bool,
+) -> Result<ReconciliationReport, ChonkitError> {
+    let mut report = services.vector.reconcile().await?;
+
+    if !repair || report.is_clean() {
+        return Ok(report);
+    }
+
+    services.vector.delete_orphans(&mut report).await;
+
+    let missing_collections: HashSet<Uuid> = report
+        .missing_collections
+        .iter()
+        .map(|collection| collection.id)
+        .collect();
+
+    for embedding in report.missing_vectors.iter() {
+        if missing_collections.contains(&embedding.collection_id) {
+            continue;
+        }
+
+        let (document_id, collection_id) = (embedding.document_id, embedding.collection_id);
+
+        match reembed(services, document_id, collection_id).await {
+            Ok(_) => tracing::info!("Re-embedded '{document_id}' in collection '{collection_id}'"),
+            Err(e) => report.repair_errors.push(format!(
+                "Re-embedding '{document_id}' in collection '{collection_id}': {e}"
+            )),
+        }
+    }
+
+    report.repaired = true;
+
+    Ok(report)
+}
+
+/// Run the reconciliation on application startup and log the results.
+pub async fn reconcile_on_startup(services: &ServiceState, mode: ReconcileMode) {
+    tracing::info!("Reconciling embeddings ({mode:?})");
+
+    let report = match reconcile(services, mode == ReconcileMode::Repair).await {
+        Ok(report) => report,
+        Err(e) => {
+            tracing::error!("Error reconciling embeddings: {e}");
+            return;
+        }
+    };
+
+    if report.is_clean() {
+        tracing::info!("Embeddings are in sync with the vector databases");
+        return;
+    }
+
+    tracing::warn!(
+        "Reconciliation found {} orphaned collection(s), {} document(s) with orphaned vectors, {} missing collection(s) and {} embedding(s) with missing vectors",
+        report.orphaned_collections.len(),
+        report.orphaned_vectors.len(),
+        report.missing_collections.len(),
+        report.missing_vectors.len(),
+    );
+
+    for error in report.repair_errors.iter() {
+        tracing::error!("Reconciliation repair error: {error}");
+    }
+}
+
+/// Delete the embedding entry of a document and embed it again.
+async fn reembed(
+    services: &ServiceState,
+    document_id: Uuid,
+    collection_id: Uuid,
+) -> Result<(), ChonkitError> {
+    services
+        .vector
+        .delete_embeddings(collection_id, document_id)
+        .await?;
+
+    services.embed_document(document_id, collection_id).await?;
+
+    Ok(())
+}
@@ -2,7 +2,11 @@ use crate::{core::repo::Atomic, error::ChonkitError, map_err};
 use sqlx::{PgPool, Transaction};
 use tracing::info;
 
+pub mod chunk;
 pub mod document;
+pub mod eval;
+pub mod preset;
+pub mod rule;
 pub mod vector;
 
 pub async fn init(url: &str) -> PgPool {
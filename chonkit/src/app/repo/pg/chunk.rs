@@ -0,0 +1,160 @@
+use crate::{
+    core::{
+        model::chunk::{Chunk, ChunkInsert},
+        repo::{chunk::ChunkRepo, Atomic},
+    },
+    error::ChonkitError,
+    map_err,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+impl ChunkRepo for PgPool {
+    async fn get_chunk(&self, id: Uuid) -> Result<Option<Chunk>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                Chunk,
+                r#"
+                    SELECT id, document_id, config_hash, document_hash, chunk_index, start_offset, end_offset, content, content_hash, created_at, updated_at
+                    FROM chunks
+                    WHERE id = $1
+                "#,
+                id
+            )
+            .fetch_optional(self)
+            .await
+        ))
+    }
+
+    async fn get_chunks_by_id(&self, ids: &[Uuid]) -> Result<Vec<Chunk>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                Chunk,
+                r#"
+                    SELECT id, document_id, config_hash, document_hash, chunk_index, start_offset, end_offset, content, content_hash, created_at, updated_at
+                    FROM chunks
+                    WHERE id = ANY($1)
+                "#,
+                ids
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn list_chunks(
+        &self,
+        document_id: Uuid,
+        config_hash: Option<&str>,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                Chunk,
+                r#"
+                    SELECT id, document_id, config_hash, document_hash, chunk_index, start_offset, end_offset, content, content_hash, created_at, updated_at
+                    FROM chunks
+                    WHERE document_id = $1 AND ($2::TEXT IS NULL OR config_hash = $2)
+                    ORDER BY created_at, config_hash, chunk_index
+                "#,
+                document_id,
+                config_hash
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn insert_chunks(
+        &self,
+        document_id: Uuid,
+        config_hash: &str,
+        document_hash: &str,
+        chunks: Vec<ChunkInsert<'_>>,
+        mut tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        let mut inserted = Vec::with_capacity(chunks.len());
+
+        for ChunkInsert {
+            id,
+            chunk_index,
+            start_offset,
+            end_offset,
+            content,
+            content_hash,
+        } in chunks
+        {
+            let query = sqlx::query_as!(
+                Chunk,
+                r#"
+                    INSERT INTO chunks(id, document_id, config_hash, document_hash, chunk_index, start_offset, end_offset, content, content_hash)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    RETURNING id, document_id, config_hash, document_hash, chunk_index, start_offset, end_offset, content, content_hash, created_at, updated_at
+                "#,
+                id,
+                document_id,
+                config_hash,
+                document_hash,
+                chunk_index,
+                start_offset,
+                end_offset,
+                content,
+                content_hash
+            );
+
+            let chunk = match tx {
+                Some(ref mut tx) => query.fetch_one(&mut ***tx).await,
+                None => query.fetch_one(self).await,
+            };
+
+            inserted.push(map_err!(chunk));
+        }
+
+        Ok(inserted)
+    }
+
+    async fn delete_chunks(
+        &self,
+        document_id: Uuid,
+        config_hash: &str,
+        tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<u64, ChonkitError> {
+        let query = sqlx::query!(
+            "DELETE FROM chunks WHERE document_id = $1 AND config_hash = $2",
+            document_id,
+            config_hash
+        );
+
+        let result = match tx {
+            Some(tx) => query.execute(&mut **tx).await,
+            None => query.execute(self).await,
+        };
+
+        Ok(map_err!(result).rows_affected())
+    }
+
+    async fn search_chunks(
+        &self,
+        query: &str,
+        document_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                Chunk,
+                r#"
+                    SELECT id, document_id, config_hash, document_hash, chunk_index, start_offset, end_offset, content, content_hash, created_at, updated_at
+                    FROM chunks
+                    WHERE to_tsvector('simple', content) @@ websearch_to_tsquery('simple', $1)
+                    AND ($2::UUID IS NULL OR document_id = $2)
+                    ORDER BY ts_rank(to_tsvector('simple', content), websearch_to_tsquery('simple', $1)) DESC, id
+                    LIMIT $3
+                "#,
+                query,
+                document_id,
+                limit
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+}
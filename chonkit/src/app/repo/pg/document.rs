@@ -4,10 +4,12 @@ use crate::{
         chunk::ChunkConfig,
         document::parser::ParseConfig,
         model::{
+            chunk::{ChunkSource, MANUAL_CHUNKS},
             collection::CollectionShort,
             document::{
                 config::{DocumentChunkConfig, DocumentParseConfig},
-                Document, DocumentConfig, DocumentDisplay, DocumentInsert, DocumentUpdate,
+                Document, DocumentConfig, DocumentDisplay, DocumentFilter, DocumentInsert,
+                DocumentUpdate,
             },
             List, PaginationSort,
         },
@@ -52,14 +54,24 @@ impl DocumentRepo for PgPool {
                         d.ext,
                         d.hash,
                         d.src,
+                        d.preset_id,
                         c.config AS "chunk_config: Option<Json<ChunkConfig>>",
-                        p.config AS "parse_config: _"
+                        p.config AS "parse_config: _",
+                        EXISTS(
+                            SELECT 1 FROM chunks ch WHERE ch.document_id = d.id AND ch.config_hash = $2
+                        ) AS "manual_chunks!",
+                        EXISTS(
+                            SELECT 1 FROM chunks ch
+                            WHERE ch.document_id = d.id AND ch.config_hash = $2
+                            AND ch.document_hash IS DISTINCT FROM d.hash
+                        ) AS "stale_chunks!"
                     FROM documents d 
                     LEFT JOIN chunkers c ON c.document_id = d.id
                     LEFT JOIN parsers p ON p.document_id = d.id
                     WHERE d.id = $1
                 "#,
-                id
+                id,
+                MANUAL_CHUNKS
             )
             .fetch_optional(self)
             .await
@@ -120,15 +132,12 @@ impl DocumentRepo for PgPool {
     async fn list(
         &self,
         params: PaginationSort,
-        src: Option<&str>,
-        ready: Option<bool>,
+        filter: &DocumentFilter,
     ) -> Result<List<Document>, ChonkitError> {
         let mut query =
             sqlx::query_builder::QueryBuilder::<Postgres>::new("SELECT COUNT(id) FROM documents");
 
-        if let Some(src) = src {
-            query.push(" WHERE src = ").push_bind(src);
-        }
+        push_filter(&mut query, filter);
 
         let total = map_err!(query
             .build()
@@ -155,55 +164,7 @@ impl DocumentRepo for PgPool {
             FROM documents"#,
         );
 
-        match (ready, src) {
-            (Some(ready), None) => {
-                if ready {
-                    query.push(
-                        r#"
-                        INNER JOIN chunkers ON chunkers.document_id = documents.id
-                        INNER JOIN parsers ON parsers.document_id = documents.id
-                        "#,
-                    );
-                } else {
-                    query.push(
-                        r#"
-                        WHERE NOT EXISTS (
-                            SELECT 1 FROM chunkers WHERE chunkers.document_id = documents.id
-                        )
-                        AND NOT EXISTS (
-                            SELECT 1 FROM parsers WHERE parsers.document_id = documents.id
-                        )
-                    "#,
-                    );
-                }
-            }
-            (Some(ready), Some(src)) => {
-                if ready {
-                    query.push(
-                        r#"
-                        INNER JOIN chunkers ON chunkers.document_id = documents.id
-                        INNER JOIN parsers ON parsers.document_id = documents.id
-                        "#,
-                    );
-                    query.push(" WHERE src = ").push_bind(src);
-                } else {
-                    query.push(" WHERE src = ").push_bind(src).push(
-                        r#"
-                        AND NOT EXISTS (
-                            SELECT 1 FROM chunkers WHERE chunkers.document_id = documents.id
-                        )
-                        AND NOT EXISTS (
-                            SELECT 1 FROM parsers WHERE parsers.document_id = documents.id
-                        )
-                        "#,
-                    );
-                }
-            }
-            (None, Some(src)) => {
-                query.push(" WHERE src = ").push_bind(src);
-            }
-            (None, None) => {}
-        }
+        push_filter(&mut query, filter);
 
         query
             .push(format!(" ORDER BY {sort_by} {sort_dir} "))
@@ -217,6 +178,19 @@ impl DocumentRepo for PgPool {
         Ok(List::new(Some(total as usize), documents))
     }
 
+    async fn list_ids(&self, filter: &DocumentFilter) -> Result<Vec<Uuid>, ChonkitError> {
+        let mut query =
+            sqlx::query_builder::QueryBuilder::<Postgres>::new("SELECT id FROM documents");
+
+        push_filter(&mut query, filter);
+
+        query.push(" ORDER BY created_at");
+
+        Ok(map_err!(query.build().fetch_all(self).await.map(|rows| {
+            rows.iter().map(|row| row.get::<Uuid, usize>(0)).collect()
+        })))
+    }
+
     async fn list_with_collections(
         &self,
         params: PaginationSort,
@@ -256,6 +230,7 @@ impl DocumentRepo for PgPool {
                         collections.id AS collection_id,
                         collections.name AS collection_name,
                         collections.model AS collection_model,
+                        collections.sparse_model AS collection_sparse_model,
                         collections.embedder AS collection_embedder,
                         collections.provider AS collection_provider
                 FROM documents
@@ -305,7 +280,8 @@ impl DocumentRepo for PgPool {
             let collection = CollectionShort::new(
                 row.collection_id.unwrap(),
                 row.collection_name.unwrap(),
-                row.collection_model.unwrap(),
+                row.collection_model,
+                row.collection_sparse_model,
                 row.collection_embedder.unwrap(),
                 row.collection_provider.unwrap(),
             );
@@ -359,30 +335,56 @@ impl DocumentRepo for PgPool {
 
     async fn update(
         &self,
-        id: uuid::Uuid,
+        ids: &[Uuid],
         update: DocumentUpdate<'_>,
-    ) -> Result<u64, ChonkitError> {
-        let DocumentUpdate { name, label, tags } = update;
+    ) -> Result<Vec<Uuid>, ChonkitError> {
+        let DocumentUpdate {
+            name,
+            label,
+            tags,
+            add_tags,
+            remove_tags,
+        } = update;
 
-        let result = map_err!(
-            sqlx::query!(
+        let update_tags = tags.is_some() || !add_tags.is_empty() || !remove_tags.is_empty();
+
+        // Tags keep their order and duplicates are dropped
+        Ok(map_err!(
+            sqlx::query_scalar!(
                 r#"
-            UPDATE documents SET 
-            name = $1,
-            label = $2,
-            tags = $3
-            WHERE id = $4 
-        "#,
-                name.as_ref(),
-                label.as_ref(),
-                tags.as_deref(),
-                id
+                UPDATE documents SET
+                    name = COALESCE($1, name),
+                    label = CASE WHEN $2::TEXT IS NULL THEN label ELSE NULLIF($2, '') END,
+                    tags = CASE WHEN $3 THEN ARRAY(
+                        SELECT t.tag
+                        FROM UNNEST(COALESCE($4, tags, '{}') || $5::TEXT[]) WITH ORDINALITY AS t(tag, i)
+                        WHERE NOT t.tag = ANY($6::TEXT[])
+                        GROUP BY t.tag
+                        ORDER BY MIN(t.i)
+                    ) ELSE tags END
+                WHERE id = ANY($7)
+                RETURNING id
+                "#,
+                name,
+                label,
+                update_tags,
+                tags,
+                add_tags,
+                remove_tags,
+                ids
             )
-            .execute(self)
+            .fetch_all(self)
             .await
-        );
+        ))
+    }
 
-        Ok(result.rows_affected())
+    async fn update_hash(&self, id: Uuid, hash: &str) -> Result<(), ChonkitError> {
+        map_err!(
+            sqlx::query!("UPDATE documents SET hash = $1 WHERE id = $2", hash, id)
+                .execute(self)
+                .await
+        );
+        Ok(())
     }
 
     async fn remove_by_id(
@@ -645,6 +647,7 @@ struct DocumentCollectionJoin {
     collection_name: Option<String>,
     collection_embedder: Option<String>,
     collection_model: Option<String>,
+    collection_sparse_model: Option<String>,
     collection_provider: Option<String>,
 }
 
@@ -667,6 +670,80 @@ where
     }
 }
 
+/// Append the filter's conditions to a query selecting from `documents`.
+fn push_filter<'a>(
+    query: &mut sqlx::query_builder::QueryBuilder<'a, Postgres>,
+    filter: &'a DocumentFilter,
+) {
+    let DocumentFilter {
+        src,
+        label,
+        tags,
+        all_tags,
+        ext,
+        created_after,
+        created_before,
+        ready,
+    } = filter;
+
+    query.push(" WHERE TRUE");
+
+    if let Some(src) = src {
+        query.push(" AND documents.src = ").push_bind(src);
+    }
+
+    if let Some(label) = label {
+        query.push(" AND documents.label = ").push_bind(label);
+    }
+
+    if !tags.is_empty() {
+        query.push(" AND documents.tags && ").push_bind(tags);
+    }
+
+    if !all_tags.is_empty() {
+        query.push(" AND documents.tags @> ").push_bind(all_tags);
+    }
+
+    if !ext.is_empty() {
+        query
+            .push(" AND documents.ext = ANY(")
+            .push_bind(ext)
+            .push(")");
+    }
+
+    if let Some(created_after) = created_after {
+        query
+            .push(" AND documents.created_at >= ")
+            .push_bind(created_after);
+    }
+
+    if let Some(created_before) = created_before {
+        query
+            .push(" AND documents.created_at < ")
+            .push_bind(created_before);
+    }
+
+    match ready {
+        Some(true) => {
+            query.push(
+                r#"
+                AND EXISTS (SELECT 1 FROM chunkers WHERE chunkers.document_id = documents.id)
+                AND EXISTS (SELECT 1 FROM parsers WHERE parsers.document_id = documents.id)
+                "#,
+            );
+        }
+        Some(false) => {
+            query.push(
+                r#"
+                AND NOT EXISTS (SELECT 1 FROM chunkers WHERE chunkers.document_id = documents.id)
+                AND NOT EXISTS (SELECT 1 FROM parsers WHERE parsers.document_id = documents.id)
+                "#,
+            );
+        }
+        None => {}
+    }
+}
+
 struct SelectDocumentConfig {
     id: uuid::Uuid,
     name: String,
@@ -676,6 +753,9 @@ struct SelectDocumentConfig {
     src: String,
     chunk_config: Option<Json<ChunkConfig>>,
     parse_config: Option<Json<ParseConfig>>,
+    manual_chunks: bool,
+    stale_chunks: bool,
+    preset_id: Option<uuid::Uuid>,
 }
 
 impl From<SelectDocumentConfig> for DocumentConfig {
@@ -689,6 +769,9 @@ impl From<SelectDocumentConfig> for DocumentConfig {
             src,
             chunk_config,
             parse_config,
+            manual_chunks,
+            stale_chunks,
+            preset_id,
         }: SelectDocumentConfig,
     ) -> Self {
         Self {
@@ -700,6 +783,13 @@ impl From<SelectDocumentConfig> for DocumentConfig {
             src,
             chunk_config: chunk_config.map(|c| c.0),
             parse_config: parse_config.map(|c| c.0),
+            chunk_source: if manual_chunks {
+                ChunkSource::Manual
+            } else {
+                ChunkSource::Automatic
+            },
+            stale_chunks,
+            preset_id,
         }
     }
 }
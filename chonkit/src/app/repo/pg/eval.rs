@@ -0,0 +1,291 @@
+use crate::{
+    core::{
+        chunk::ChunkConfig,
+        eval::RetrievalMetrics,
+        model::eval::{
+            EvalDataset, EvalQuestion, EvalQuestionInsert, EvalQuestionResult, EvalRun,
+            EvalRunInsert,
+        },
+        repo::{eval::EvalRepo, Atomic},
+    },
+    err,
+    error::ChonkitError,
+    map_err,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgPool};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl EvalRepo for PgPool {
+    async fn list_datasets(&self) -> Result<Vec<EvalDataset>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                EvalDataset,
+                "SELECT id, name, created_at, updated_at FROM eval_datasets ORDER BY name"
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn get_dataset(&self, id: Uuid) -> Result<Option<EvalDataset>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                EvalDataset,
+                "SELECT id, name, created_at, updated_at FROM eval_datasets WHERE id = $1",
+                id
+            )
+            .fetch_optional(self)
+            .await
+        ))
+    }
+
+    async fn insert_dataset(
+        &self,
+        name: &str,
+        tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<EvalDataset, ChonkitError> {
+        let query = sqlx::query_as!(
+            EvalDataset,
+            "INSERT INTO eval_datasets(id, name) VALUES ($1, $2) RETURNING id, name, created_at, updated_at",
+            Uuid::new_v4(),
+            name
+        );
+
+        let dataset = match tx {
+            Some(tx) => query.fetch_one(&mut **tx).await,
+            None => query.fetch_one(self).await,
+        };
+
+        match dataset {
+            Ok(dataset) => Ok(dataset),
+            Err(sqlx::Error::Database(e)) if e.code().is_some_and(|code| code == "23505") => {
+                err!(AlreadyExists, "Evaluation dataset '{name}' already exists")
+            }
+            Err(e) => map_err!(Err(e)),
+        }
+    }
+
+    async fn delete_dataset(&self, id: Uuid) -> Result<u64, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query!("DELETE FROM eval_datasets WHERE id = $1", id)
+                .execute(self)
+                .await
+        )
+        .rows_affected())
+    }
+
+    async fn list_questions(&self, dataset_id: Uuid) -> Result<Vec<EvalQuestion>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                EvalQuestion,
+                r#"
+                    SELECT id, dataset_id, question, documents, spans, created_at, updated_at
+                    FROM eval_questions
+                    WHERE dataset_id = $1
+                    ORDER BY created_at, id
+                "#,
+                dataset_id
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn insert_questions(
+        &self,
+        dataset_id: Uuid,
+        questions: Vec<EvalQuestionInsert<'_>>,
+        mut tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<Vec<EvalQuestion>, ChonkitError> {
+        let mut inserted = Vec::with_capacity(questions.len());
+
+        for EvalQuestionInsert {
+            id,
+            question,
+            documents,
+            spans,
+        } in questions
+        {
+            let query = sqlx::query_as!(
+                EvalQuestion,
+                r#"
+                    INSERT INTO eval_questions(id, dataset_id, question, documents, spans)
+                    VALUES ($1, $2, $3, $4, $5)
+                    RETURNING id, dataset_id, question, documents, spans, created_at, updated_at
+                "#,
+                id,
+                dataset_id,
+                question,
+                documents,
+                spans
+            );
+
+            let question = match tx {
+                Some(ref mut tx) => query.fetch_one(&mut ***tx).await,
+                None => query.fetch_one(self).await,
+            };
+
+            inserted.push(map_err!(question));
+        }
+
+        Ok(inserted)
+    }
+
+    async fn delete_question(&self, id: Uuid) -> Result<u64, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query!("DELETE FROM eval_questions WHERE id = $1", id)
+                .execute(self)
+                .await
+        )
+        .rows_affected())
+    }
+
+    async fn list_runs(&self, dataset_id: Uuid) -> Result<Vec<EvalRun>, ChonkitError> {
+        let runs = map_err!(
+            sqlx::query_as!(
+                EvalRunRow,
+                r#"
+                    SELECT
+                        id, dataset_id, collection_id, collection_name, embedder, model, sparse_model, provider, k,
+                        recall, precision, mrr, ndcg,
+                        chunk_configs AS "chunk_configs: _",
+                        questions AS "questions: _",
+                        created_at
+                    FROM eval_runs
+                    WHERE dataset_id = $1
+                    ORDER BY created_at DESC
+                "#,
+                dataset_id
+            )
+            .fetch_all(self)
+            .await
+        );
+
+        Ok(runs.into_iter().map(EvalRun::from).collect())
+    }
+
+    async fn get_run(&self, id: Uuid) -> Result<Option<EvalRun>, ChonkitError> {
+        let run = map_err!(
+            sqlx::query_as!(
+                EvalRunRow,
+                r#"
+                    SELECT
+                        id, dataset_id, collection_id, collection_name, embedder, model, sparse_model, provider, k,
+                        recall, precision, mrr, ndcg,
+                        chunk_configs AS "chunk_configs: _",
+                        questions AS "questions: _",
+                        created_at
+                    FROM eval_runs
+                    WHERE id = $1
+                "#,
+                id
+            )
+            .fetch_optional(self)
+            .await
+        );
+
+        Ok(run.map(EvalRun::from))
+    }
+
+    async fn insert_run(&self, insert: EvalRunInsert<'_>) -> Result<EvalRun, ChonkitError> {
+        let EvalRunInsert {
+            id,
+            dataset_id,
+            collection_id,
+            collection_name,
+            embedder,
+            model,
+            sparse_model,
+            provider,
+            k,
+            metrics,
+            chunk_configs,
+            questions,
+        } = insert;
+
+        let run = map_err!(
+            sqlx::query_as!(
+                EvalRunRow,
+                r#"
+                    INSERT INTO eval_runs(
+                        id, dataset_id, collection_id, collection_name, embedder, model, sparse_model, provider, k,
+                        recall, precision, mrr, ndcg, chunk_configs, questions
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                    RETURNING
+                        id, dataset_id, collection_id, collection_name, embedder, model, sparse_model, provider, k,
+                        recall, precision, mrr, ndcg,
+                        chunk_configs AS "chunk_configs: _",
+                        questions AS "questions: _",
+                        created_at
+                "#,
+                id,
+                dataset_id,
+                collection_id,
+                collection_name,
+                embedder,
+                model,
+                sparse_model,
+                provider,
+                k,
+                metrics.recall,
+                metrics.precision,
+                metrics.mrr,
+                metrics.ndcg,
+                Json(chunk_configs) as Json<HashMap<Uuid, ChunkConfig>>,
+                Json(questions) as Json<Vec<EvalQuestionResult>>,
+            )
+            .fetch_one(self)
+            .await
+        );
+
+        Ok(run.into())
+    }
+}
+
+/// Private DTO for evaluation runs since their metrics are stored in separate columns.
+struct EvalRunRow {
+    id: Uuid,
+    dataset_id: Uuid,
+    collection_id: Option<Uuid>,
+    collection_name: String,
+    embedder: String,
+    model: Option<String>,
+    sparse_model: Option<String>,
+    provider: String,
+    k: i32,
+    recall: f64,
+    precision: f64,
+    mrr: f64,
+    ndcg: f64,
+    chunk_configs: Json<HashMap<Uuid, ChunkConfig>>,
+    questions: Json<Vec<EvalQuestionResult>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<EvalRunRow> for EvalRun {
+    fn from(row: EvalRunRow) -> Self {
+        Self {
+            id: row.id,
+            dataset_id: row.dataset_id,
+            collection_id: row.collection_id,
+            collection_name: row.collection_name,
+            embedder: row.embedder,
+            model: row.model,
+            sparse_model: row.sparse_model,
+            provider: row.provider,
+            k: row.k,
+            metrics: RetrievalMetrics {
+                recall: row.recall,
+                precision: row.precision,
+                mrr: row.mrr,
+                ndcg: row.ndcg,
+            },
+            chunk_configs: row.chunk_configs,
+            questions: row.questions,
+            created_at: row.created_at,
+        }
+    }
+}
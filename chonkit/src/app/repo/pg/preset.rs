@@ -0,0 +1,224 @@
+use crate::{
+    core::{
+        chunk::ChunkConfig,
+        document::parser::ParseConfig,
+        model::preset::{DocumentSelection, Preset, PresetInsert},
+        repo::{preset::PresetRepo, Atomic},
+    },
+    err,
+    error::ChonkitError,
+    map_err,
+};
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+impl PresetRepo for PgPool {
+    async fn list_presets(&self) -> Result<Vec<Preset>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                Preset,
+                r#"
+                    SELECT id, name, parse_config AS "parse_config: _", chunk_config AS "chunk_config: _", created_at, updated_at
+                    FROM presets
+                    ORDER BY name
+                "#
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn get_preset(&self, id: Uuid) -> Result<Option<Preset>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                Preset,
+                r#"
+                    SELECT id, name, parse_config AS "parse_config: _", chunk_config AS "chunk_config: _", created_at, updated_at
+                    FROM presets
+                    WHERE id = $1
+                "#,
+                id
+            )
+            .fetch_optional(self)
+            .await
+        ))
+    }
+
+    async fn insert_preset(&self, insert: PresetInsert<'_>) -> Result<Preset, ChonkitError> {
+        let PresetInsert {
+            id,
+            name,
+            parse_config,
+            chunk_config,
+        } = insert;
+
+        let preset = sqlx::query_as!(
+            Preset,
+            r#"
+                INSERT INTO presets(id, name, parse_config, chunk_config)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id, name, parse_config AS "parse_config: _", chunk_config AS "chunk_config: _", created_at, updated_at
+            "#,
+            id,
+            name,
+            Json(parse_config) as Json<ParseConfig>,
+            Json(chunk_config) as Json<ChunkConfig>,
+        )
+        .fetch_one(self)
+        .await;
+
+        match preset {
+            Ok(preset) => Ok(preset),
+            Err(sqlx::Error::Database(e)) if e.code().is_some_and(|code| code == "23505") => {
+                err!(AlreadyExists, "Preset '{name}' already exists")
+            }
+            Err(e) => map_err!(Err(e)),
+        }
+    }
+
+    async fn update_preset(
+        &self,
+        id: Uuid,
+        name: &str,
+        parse_config: ParseConfig,
+        chunk_config: ChunkConfig,
+        tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<Option<Preset>, ChonkitError> {
+        let query = sqlx::query_as!(
+            Preset,
+            r#"
+                UPDATE presets SET name = $2, parse_config = $3, chunk_config = $4
+                WHERE id = $1
+                RETURNING id, name, parse_config AS "parse_config: _", chunk_config AS "chunk_config: _", created_at, updated_at
+            "#,
+            id,
+            name,
+            Json(parse_config) as Json<ParseConfig>,
+            Json(chunk_config) as Json<ChunkConfig>,
+        );
+
+        let preset = match tx {
+            Some(tx) => query.fetch_optional(&mut **tx).await,
+            None => query.fetch_optional(self).await,
+        };
+
+        match preset {
+            Ok(preset) => Ok(preset),
+            Err(sqlx::Error::Database(e)) if e.code().is_some_and(|code| code == "23505") => {
+                err!(AlreadyExists, "Preset '{name}' already exists")
+            }
+            Err(e) => map_err!(Err(e)),
+        }
+    }
+
+    async fn delete_preset(&self, id: Uuid) -> Result<u64, ChonkitError> {
+        let result = sqlx::query!("DELETE FROM presets WHERE id = $1", id)
+            .execute(self)
+            .await;
+
+        match result {
+            Ok(result) => Ok(result.rows_affected()),
+            Err(sqlx::Error::Database(e)) if e.code().is_some_and(|code| code == "23503") => {
+                err!(
+                    AlreadyExists,
+                    "Preset '{id}' is used by configuration rules; delete or update them first"
+                )
+            }
+            Err(e) => map_err!(Err(e)),
+        }
+    }
+
+    async fn list_preset_documents(&self, preset_id: Uuid) -> Result<Vec<Uuid>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_scalar!(
+                "SELECT id FROM documents WHERE preset_id = $1 ORDER BY name",
+                preset_id
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn link_documents(
+        &self,
+        preset_id: Uuid,
+        selection: DocumentSelection<'_>,
+        tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<Vec<Uuid>, ChonkitError> {
+        let DocumentSelection {
+            documents,
+            label,
+            tags,
+        } = selection;
+
+        let query = sqlx::query_scalar!(
+            r#"
+                UPDATE documents SET preset_id = $1
+                WHERE id = ANY($2) OR label = $3 OR tags && $4
+                RETURNING id
+            "#,
+            preset_id,
+            documents,
+            label,
+            tags
+        );
+
+        let documents = match tx {
+            Some(tx) => query.fetch_all(&mut **tx).await,
+            None => query.fetch_all(self).await,
+        };
+
+        Ok(map_err!(documents))
+    }
+
+    async fn unlink_document(&self, document_id: Uuid) -> Result<u64, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query!(
+                "UPDATE documents SET preset_id = NULL WHERE id = $1 AND preset_id IS NOT NULL",
+                document_id
+            )
+            .execute(self)
+            .await
+        )
+        .rows_affected())
+    }
+
+    async fn sync_preset_configs(
+        &self,
+        preset_id: Uuid,
+        mut tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<u64, ChonkitError> {
+        let chunkers = sqlx::query!(
+            r#"
+                INSERT INTO chunkers(document_id, config)
+                SELECT d.id, p.chunk_config FROM documents d JOIN presets p ON p.id = d.preset_id
+                WHERE p.id = $1
+                ON CONFLICT(document_id) DO UPDATE SET config = EXCLUDED.config
+            "#,
+            preset_id
+        );
+
+        let parsers = sqlx::query!(
+            r#"
+                INSERT INTO parsers(document_id, config)
+                SELECT d.id, p.parse_config FROM documents d JOIN presets p ON p.id = d.preset_id
+                WHERE p.id = $1
+                ON CONFLICT(document_id) DO UPDATE SET config = EXCLUDED.config
+            "#,
+            preset_id
+        );
+
+        let result = match tx {
+            Some(ref mut tx) => {
+                map_err!(parsers.execute(&mut ***tx).await);
+                chunkers.execute(&mut ***tx).await
+            }
+            None => {
+                map_err!(parsers.execute(self).await);
+                chunkers.execute(self).await
+            }
+        };
+
+        Ok(map_err!(result).rows_affected())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    core::{
+        chunk::ChunkConfig,
+        document::parser::ParseConfig,
+        model::rule::{ConfigRule, ConfigRuleInsert},
+        repo::rule::RuleRepo,
+        rule::RuleCriteria,
+    },
+    err,
+    error::ChonkitError,
+    map_err,
+};
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+impl RuleRepo for PgPool {
+    async fn list_rules(&self) -> Result<Vec<ConfigRule>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                ConfigRule,
+                r#"
+                    SELECT
+                        id,
+                        name,
+                        priority,
+                        criteria AS "criteria: _",
+                        preset_id,
+                        parse_config AS "parse_config: _",
+                        chunk_config AS "chunk_config: _",
+                        created_at,
+                        updated_at
+                    FROM config_rules
+                    ORDER BY priority DESC, created_at, name
+                "#
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn get_rule(&self, id: Uuid) -> Result<Option<ConfigRule>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                ConfigRule,
+                r#"
+                    SELECT
+                        id,
+                        name,
+                        priority,
+                        criteria AS "criteria: _",
+                        preset_id,
+                        parse_config AS "parse_config: _",
+                        chunk_config AS "chunk_config: _",
+                        created_at,
+                        updated_at
+                    FROM config_rules
+                    WHERE id = $1
+                "#,
+                id
+            )
+            .fetch_optional(self)
+            .await
+        ))
+    }
+
+    async fn insert_rule(&self, insert: ConfigRuleInsert<'_>) -> Result<ConfigRule, ChonkitError> {
+        let ConfigRuleInsert {
+            id,
+            name,
+            priority,
+            criteria,
+            preset_id,
+            parse_config,
+            chunk_config,
+        } = insert;
+
+        let rule = sqlx::query_as!(
+            ConfigRule,
+            r#"
+                INSERT INTO config_rules(id, name, priority, criteria, preset_id, parse_config, chunk_config)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING
+                    id,
+                    name,
+                    priority,
+                    criteria AS "criteria: _",
+                    preset_id,
+                    parse_config AS "parse_config: _",
+                    chunk_config AS "chunk_config: _",
+                    created_at,
+                    updated_at
+            "#,
+            id,
+            name,
+            priority,
+            Json(criteria) as Json<&RuleCriteria>,
+            preset_id,
+            parse_config.map(Json) as Option<Json<&ParseConfig>>,
+            chunk_config.map(Json) as Option<Json<&ChunkConfig>>,
+        )
+        .fetch_one(self)
+        .await;
+
+        match rule {
+            Ok(rule) => Ok(rule),
+            Err(sqlx::Error::Database(e)) if e.code().is_some_and(|code| code == "23505") => {
+                err!(AlreadyExists, "Rule '{name}' already exists")
+            }
+            Err(e) => map_err!(Err(e)),
+        }
+    }
+
+    async fn update_rule(
+        &self,
+        id: Uuid,
+        update: ConfigRuleInsert<'_>,
+    ) -> Result<Option<ConfigRule>, ChonkitError> {
+        let ConfigRuleInsert {
+            name,
+            priority,
+            criteria,
+            preset_id,
+            parse_config,
+            chunk_config,
+            ..
+        } = update;
+
+        let rule = sqlx::query_as!(
+            ConfigRule,
+            r#"
+                UPDATE config_rules
+                SET name = $2, priority = $3, criteria = $4, preset_id = $5, parse_config = $6, chunk_config = $7
+                WHERE id = $1
+                RETURNING
+                    id,
+                    name,
+                    priority,
+                    criteria AS "criteria: _",
+                    preset_id,
+                    parse_config AS "parse_config: _",
+                    chunk_config AS "chunk_config: _",
+                    created_at,
+                    updated_at
+            "#,
+            id,
+            name,
+            priority,
+            Json(criteria) as Json<&RuleCriteria>,
+            preset_id,
+            parse_config.map(Json) as Option<Json<&ParseConfig>>,
+            chunk_config.map(Json) as Option<Json<&ChunkConfig>>,
+        )
+        .fetch_optional(self)
+        .await;
+
+        match rule {
+            Ok(rule) => Ok(rule),
+            Err(sqlx::Error::Database(e)) if e.code().is_some_and(|code| code == "23505") => {
+                err!(AlreadyExists, "Rule '{name}' already exists")
+            }
+            Err(e) => map_err!(Err(e)),
+        }
+    }
+
+    async fn delete_rule(&self, id: Uuid) -> Result<u64, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query!("DELETE FROM config_rules WHERE id = $1", id)
+                .execute(self)
+                .await
+        )
+        .rows_affected())
+    }
+}
@@ -2,19 +2,21 @@ use crate::{
     core::{
         model::{
             collection::{
-                Collection, CollectionDisplay, CollectionInsert, Embedding, EmbeddingInsert,
+                Collection, CollectionAlias, CollectionDisplay, CollectionInsert, Embedding,
+                EmbeddingInsert,
             },
             document::DocumentShort,
             List, Pagination, PaginationSort,
         },
         repo::{vector::VectorRepo, Atomic},
+        vector::VectorIndexConfig,
     },
     err,
     error::ChonkitError,
     map_err,
 };
 use chrono::{DateTime, Utc};
-use sqlx::{prelude::FromRow, PgPool, Postgres};
+use sqlx::{prelude::FromRow, types::Json, PgPool, Postgres};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -32,7 +34,7 @@ impl VectorRepo for PgPool {
         let (sort_by, sort_dir) = params.to_sort();
 
         let mut query = sqlx::query_builder::QueryBuilder::<Postgres>::new(
-            "SELECT id, name, model, embedder, provider, created_at, updated_at FROM collections",
+            "SELECT id, name, model, sparse_model, embedder, provider, index_config, created_at, updated_at FROM collections",
         );
 
         query
@@ -83,7 +85,9 @@ impl VectorRepo for PgPool {
                                 collections.id,
                                 collections.name,
                                 collections.model,
+                                collections.sparse_model,
                                 collections.embedder,
+                                collections.index_config,
                                 collections.provider,
                                 collections.created_at,
                                 collections.updated_at
@@ -102,7 +106,9 @@ impl VectorRepo for PgPool {
                         cols.id,
                         cols.name,
                         cols.model,
+                        cols.sparse_model,
                         cols.embedder,
+                        cols.index_config,
                         cols.provider,
                         cols.created_at,
                         cols.updated_at,
@@ -127,8 +133,10 @@ impl VectorRepo for PgPool {
                 id: collection_row.id,
                 name: collection_row.name,
                 model: collection_row.model,
+                sparse_model: collection_row.sparse_model,
                 embedder: collection_row.embedder,
                 provider: collection_row.provider,
+                index_config: collection_row.index_config,
                 created_at: collection_row.created_at,
                 updated_at: collection_row.updated_at,
             };
@@ -164,6 +172,15 @@ impl VectorRepo for PgPool {
         ))
     }
 
+    async fn list_all_collections(&self) -> Result<Vec<Collection>, ChonkitError> {
+        Ok(map_err!(sqlx::query_as!(
+            Collection,
+            r#"SELECT id, name, model, sparse_model, embedder, provider, index_config AS "index_config: _", created_at, updated_at FROM collections"#,
+        )
+        .fetch_all(self)
+        .await))
+    }
+
     async fn insert_collection(
         &self,
         insert: CollectionInsert<'_>,
@@ -173,24 +190,28 @@ impl VectorRepo for PgPool {
             id,
             name,
             model,
+            sparse_model,
             embedder,
             provider,
+            index_config,
         } = insert;
 
         let query = sqlx::query_as!(
             Collection,
-            "INSERT INTO collections
-                (id, name, model, embedder, provider)
+            r#"INSERT INTO collections
+                (id, name, model, sparse_model, embedder, provider, index_config)
              VALUES
-                ($1, $2, $3, $4, $5)
+                ($1, $2, $3, $4, $5, $6, $7)
              RETURNING 
-                id, name, model, embedder, provider, created_at, updated_at
-             ",
+                id, name, model, sparse_model, embedder, provider, index_config AS "index_config: _", created_at, updated_at
+             "#,
             id,
             name,
             model,
+            sparse_model,
             embedder,
-            provider
+            provider,
+            Json(index_config) as Json<VectorIndexConfig>,
         );
 
         let collection = if let Some(tx) = tx {
@@ -220,7 +241,7 @@ impl VectorRepo for PgPool {
     async fn get_collection(&self, id: Uuid) -> Result<Option<Collection>, ChonkitError> {
         Ok(map_err!(sqlx::query_as!(
             Collection,
-            "SELECT id, name, model, embedder, provider, created_at, updated_at FROM collections WHERE id = $1",
+            r#"SELECT id, name, model, sparse_model, embedder, provider, index_config AS "index_config: _", created_at, updated_at FROM collections WHERE id = $1"#,
             id
         )
         .fetch_optional(self)
@@ -233,7 +254,7 @@ impl VectorRepo for PgPool {
     ) -> Result<Option<CollectionDisplay>, ChonkitError> {
         let collection = map_err!(sqlx::query_as!(
             Collection,
-            "SELECT id, name, model, embedder, provider, created_at, updated_at FROM collections WHERE id = $1",
+            r#"SELECT id, name, model, sparse_model, embedder, provider, index_config AS "index_config: _", created_at, updated_at FROM collections WHERE id = $1"#,
             collection_id
         )
         .fetch_optional(self)
@@ -268,7 +289,7 @@ impl VectorRepo for PgPool {
     ) -> Result<Option<Collection>, ChonkitError> {
         Ok(map_err!(sqlx::query_as!(
             Collection,
-            "SELECT id, name, model, embedder, provider, created_at, updated_at FROM collections WHERE name = $1 AND provider = $2",
+            r#"SELECT id, name, model, sparse_model, embedder, provider, index_config AS "index_config: _", created_at, updated_at FROM collections WHERE name = $1 AND provider = $2"#,
             name,
             provider
         )
@@ -320,6 +341,23 @@ impl VectorRepo for PgPool {
         ))
     }
 
+    async fn get_collection_embeddings(
+        &self,
+        collection_id: Uuid,
+    ) -> Result<Vec<Embedding>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                Embedding,
+                "SELECT id, document_id, collection_id, created_at, updated_at 
+             FROM embeddings
+             WHERE collection_id = $1",
+                collection_id
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
     async fn get_embeddings(
         &self,
         document_id: Uuid,
@@ -410,6 +448,17 @@ impl VectorRepo for PgPool {
         .rows_affected())
     }
 
+    async fn get_existing_documents(&self, ids: &[Uuid]) -> Result<Vec<Uuid>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query!("SELECT id FROM documents WHERE id = ANY($1)", ids)
+                .fetch_all(self)
+                .await
+        )
+        .into_iter()
+        .map(|row| row.id)
+        .collect())
+    }
+
     async fn delete_all_embeddings(&self, collection_id: Uuid) -> Result<u64, ChonkitError> {
         Ok(map_err!(
             sqlx::query!(
@@ -421,6 +470,65 @@ impl VectorRepo for PgPool {
         )
         .rows_affected())
     }
+
+    async fn get_alias(&self, name: &str) -> Result<Option<CollectionAlias>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                CollectionAlias,
+                "SELECT name, collection_id, created_at, updated_at FROM collection_aliases WHERE name = $1",
+                name
+            )
+            .fetch_optional(self)
+            .await
+        ))
+    }
+
+    async fn list_aliases(&self) -> Result<Vec<CollectionAlias>, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query_as!(
+                CollectionAlias,
+                "SELECT name, collection_id, created_at, updated_at FROM collection_aliases ORDER BY name"
+            )
+            .fetch_all(self)
+            .await
+        ))
+    }
+
+    async fn delete_alias(&self, name: &str) -> Result<u64, ChonkitError> {
+        Ok(map_err!(
+            sqlx::query!("DELETE FROM collection_aliases WHERE name = $1", name)
+                .execute(self)
+                .await
+        )
+        .rows_affected())
+    }
+
+    async fn upsert_alias(
+        &self,
+        name: &str,
+        collection_id: Uuid,
+        tx: Option<&mut <Self as Atomic>::Tx>,
+    ) -> Result<CollectionAlias, ChonkitError> {
+        let query = sqlx::query_as!(
+            CollectionAlias,
+            r#"
+                INSERT INTO collection_aliases(name, collection_id)
+                VALUES ($1, $2)
+                ON CONFLICT(name) DO UPDATE
+                SET collection_id = $2
+                RETURNING name, collection_id, created_at, updated_at
+            "#,
+            name,
+            collection_id
+        );
+
+        let alias = match tx {
+            Some(tx) => query.fetch_one(&mut **tx).await,
+            None => query.fetch_one(self).await,
+        };
+
+        Ok(map_err!(alias))
+    }
 }
 
 /// Private DTO for joining collections and the documents they contain.
@@ -428,9 +536,11 @@ impl VectorRepo for PgPool {
 struct CollectionDocumentJoin {
     id: Uuid,
     name: String,
-    model: String,
+    model: Option<String>,
+    sparse_model: Option<String>,
     embedder: String,
     provider: String,
+    index_config: Json<VectorIndexConfig>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     document_id: Option<Uuid>,
@@ -12,7 +12,14 @@ use super::router::{
         __path_upload_documents,
         __path_chunk_preview,
         __path_parse_preview,
+        __path_list_document_chunks,
+        __path_search_chunks,
+        __path_save_manual_chunks,
+        __path_edit_manual_chunks,
+        __path_delete_manual_chunks,
         __path_update_document_config,
+        __path_update_document,
+        __path_update_documents,
         __path_sync,
     },
 
@@ -20,38 +27,120 @@ use super::router::{
     vector::{
         __path_list_collections,
         __path_list_embedding_models,
+        __path_list_sparse_models,
         __path_get_collection,
         __path_create_collection,
         __path_delete_collection,
+        __path_clone_collection,
+        __path_export_collection,
+        __path_import_collection,
+        __path_list_aliases,
+        __path_swap_alias,
+        __path_delete_alias,
         __path_list_embedded_documents,
         __path_embed,
         __path_batch_embed,
+        __path_reconcile_embeddings,
+        __path_delete_orphaned_collection,
         __path_search, 
+        __path_context,
         __path_count_embeddings,
+        __path_list_chunks,
         __path_delete_embeddings,
         __path_list_collections_display,
         __path_collection_display
+    },
+
+    // Jobs
+    job::{
+        __path_list_jobs,
+        __path_get_job,
+    },
+
+    // Evaluations
+    eval::{
+        __path_list_datasets,
+        __path_create_dataset,
+        __path_get_dataset,
+        __path_delete_dataset,
+        __path_add_questions,
+        __path_delete_question,
+        __path_run,
+        __path_list_runs,
+        __path_get_run,
+        __path_sweep,
+        __path_generate,
+    },
+
+    // Presets
+    preset::{
+        __path_list_presets,
+        __path_create_preset,
+        __path_get_preset,
+        __path_update_preset,
+        __path_delete_preset,
+        __path_assign_preset,
+    },
+
+    // Configuration rules
+    rule::{
+        __path_list_rules,
+        __path_create_rule,
+        __path_get_rule,
+        __path_update_rule,
+        __path_delete_rule,
     }
 };
 use super::dto::{
-    EmbeddingBatchPayload, EmbeddingSinglePayload, ListDocumentsPayload, ListEmbeddingsPayload,
-    UploadResult,
+    EmbeddingBatchPayload, EmbeddingSinglePayload, ExportCollectionParams, ImportCollectionParams,
+    ListChunksParams, ListDocumentsPayload, ListEmbeddingsPayload, ReconcilePayload,
+    SearchResponse, SwapAliasPayload, UploadParams, UploadResult,
 };
 use crate::{
-    app::state::AppConfig,
+    app::{
+        context::{Citation, Context, ContextPayload},
+        job::{Job, JobKind, JobResult, JobStatus},
+        state::AppConfig,
+        sweep::{SweepCandidate, SweepReport},
+    },
     core::{
-        chunk::{ChunkConfig, SemanticWindowConfig, SlidingWindowConfig, SnappingWindowConfig},
+        chunk::{
+            ChunkConfig, ChunkEdit, MarkdownWindowConfig, SemanticWindowConfig,
+            SlidingWindowConfig, SnappingWindowConfig,
+        },
         document::parser::ParseConfig,
+        eval::{EvalMetric, RetrievalMetrics, SweepChunker, SweepGrid},
         model::{
-            collection::{Collection, CollectionDisplay, CollectionShort, Embedding},
-            document::{Document, DocumentConfig, DocumentDisplay, DocumentShort},
+            chunk::{Chunk, ChunkSource},
+            collection::{
+                Collection, CollectionAlias, CollectionDisplay, CollectionImport, CollectionShort,
+                Embedding, OrphanedCollection, OrphanedVectors, ReconciliationReport,
+            },
+            document::{Document, DocumentConfig, DocumentDisplay, DocumentFilter, DocumentShort},
+            eval::{EvalDataset, EvalDatasetDisplay, EvalQuestion, EvalQuestionResult, EvalRun},
+            preset::{Preset, PresetDisplay},
+            rule::ConfigRule,
             List, Pagination, PaginationSort, SortDirection,
         },
+        rule::RuleCriteria,
+        search::FederatedHit,
         service::{
-            document::dto::ChunkPreviewPayload,
-            vector::dto::{CreateCollectionPayload, SearchPayload},
+            document::dto::{
+                ChunkPreviewPayload, ChunkSearch, DocumentBulkUpdatePayload, DocumentUpdatePayload,
+                SaveChunksPayload,
+            },
+            eval::dto::{
+                CreateEvalDatasetPayload, EvalQuestionPayload, GenerateQuestionsPayload,
+                RunEvaluationPayload, SweepPayload,
+            },
+            preset::dto::{AssignPresetPayload, PresetPayload},
+            rule::dto::RulePayload,
+            vector::dto::{CloneCollectionPayload, CreateCollectionPayload, SearchPayload},
+        },
+        vector::{
+            snapshot::SnapshotFormat, Distance, ProductCompression, Quantization, StoredChunk,
+            VectorCollection, VectorIndexConfig, VectorKind,
         },
-        vector::VectorCollection,
     },
 };
 use utoipa::OpenApi;
@@ -69,27 +158,74 @@ use utoipa::OpenApi;
         upload_documents,
         chunk_preview,
         parse_preview,
+        list_document_chunks,
+        search_chunks,
+        save_manual_chunks,
+        edit_manual_chunks,
+        delete_manual_chunks,
         update_document_config,
+        update_document,
+        update_documents,
         sync,
         // Vectors
         list_collections,
         get_collection,
         create_collection,
         delete_collection,
+        clone_collection,
+        export_collection,
+        import_collection,
+        list_aliases,
+        swap_alias,
+        delete_alias,
         list_embedding_models,
+        list_sparse_models,
         list_embedded_documents,
         embed,
         batch_embed,
+        reconcile_embeddings,
+        delete_orphaned_collection,
         search,
+        context,
         delete_embeddings,
         count_embeddings,
+        list_chunks,
         list_collections_display,
         collection_display,
+        // Jobs
+        list_jobs,
+        get_job,
+        // Evaluations
+        list_datasets,
+        create_dataset,
+        get_dataset,
+        delete_dataset,
+        add_questions,
+        delete_question,
+        run,
+        list_runs,
+        get_run,
+        sweep,
+        generate,
+        // Presets
+        list_presets,
+        create_preset,
+        get_preset,
+        update_preset,
+        delete_preset,
+        assign_preset,
+        // Configuration rules
+        list_rules,
+        create_rule,
+        get_rule,
+        update_rule,
+        delete_rule,
     ),
     components(schemas(
         List<Collection>,
         List<Document>,
         List<DocumentDisplay>,
+        List<StoredChunk>,
         Pagination,
         PaginationSort,
         Document,
@@ -100,18 +236,78 @@ use utoipa::OpenApi;
         SnappingWindowConfig,
         SemanticWindowConfig,
         SemanticWindowConfig,
+        MarkdownWindowConfig,
         ChunkPreviewPayload,
+        Chunk,
+        ChunkSource,
+        ChunkEdit,
+        SaveChunksPayload,
+        ChunkSearch,
+        ListChunksParams,
+        DocumentFilter,
+        DocumentUpdatePayload,
+        DocumentBulkUpdatePayload,
         ParseConfig,
         CreateCollectionPayload,
         SearchPayload,
         Embedding,
         Collection,
         VectorCollection,
+        StoredChunk,
+        VectorKind,
+        VectorIndexConfig,
+        Distance,
+        Quantization,
+        ProductCompression,
         AppConfig,
         EmbeddingBatchPayload,
         EmbeddingSinglePayload,
         ListEmbeddingsPayload,
         ListDocumentsPayload,
+        ReconcilePayload,
+        ReconciliationReport,
+        SnapshotFormat,
+        CollectionImport,
+        CloneCollectionPayload,
+        CollectionAlias,
+        SwapAliasPayload,
+        SearchResponse,
+        FederatedHit,
+        ContextPayload,
+        Context,
+        Citation,
+        EvalDataset,
+        EvalDatasetDisplay,
+        EvalQuestion,
+        EvalQuestionResult,
+        EvalRun,
+        RetrievalMetrics,
+        CreateEvalDatasetPayload,
+        EvalQuestionPayload,
+        RunEvaluationPayload,
+        SweepPayload,
+        GenerateQuestionsPayload,
+        SweepGrid,
+        SweepChunker,
+        EvalMetric,
+        SweepReport,
+        SweepCandidate,
+        Job,
+        JobKind,
+        JobResult,
+        JobStatus,
+        ExportCollectionParams,
+        ImportCollectionParams,
+        OrphanedCollection,
+        OrphanedVectors,
+        Preset,
+        PresetDisplay,
+        PresetPayload,
+        AssignPresetPayload,
+        ConfigRule,
+        RulePayload,
+        RuleCriteria,
+        UploadParams,
 
         // Display
         DocumentDisplay,
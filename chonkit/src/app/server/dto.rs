@@ -3,9 +3,16 @@
 use crate::core::{
     chunk::ChunkConfig,
     document::parser::ParseConfig,
-    model::{document::DocumentConfig, Pagination, PaginationSort},
+    model::{
+        document::{DocumentConfig, DocumentFilter},
+        Pagination, PaginationSort,
+    },
+    search::FederatedHit,
+    vector::snapshot::SnapshotFormat,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_with::{formats::CommaSeparator, serde_as, DisplayFromStr, StringWithSeparator};
 use std::collections::HashMap;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
@@ -20,6 +27,15 @@ pub(super) struct UploadResult {
     pub errors: HashMap<String, Vec<String>>,
 }
 
+/// Search results. Federated searches return hits attributed to their collections,
+/// searches of a single collection return the matching contents.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub(super) enum SearchResponse {
+    Contents(Vec<String>),
+    Hits(Vec<FederatedHit>),
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct ConfigUpdatePayload {
@@ -47,9 +63,15 @@ pub(super) struct EmbeddingSinglePayload {
 #[validate(Self::validate_schema)]
 pub(super) struct EmbeddingBatchPayload {
     /// The documents to embed and add to the collection.
+    #[serde(default)]
     pub add: Vec<Uuid>,
 
+    /// Embed and add all documents matching the filter to the collection.
+    #[validate]
+    pub add_filter: Option<DocumentFilter>,
+
     /// The documents to remove from the collection.
+    #[serde(default)]
     pub remove: Vec<Uuid>,
 
     /// The ID of the collection in which to store the embeddings to.
@@ -59,10 +81,10 @@ pub(super) struct EmbeddingBatchPayload {
 impl EmbeddingBatchPayload {
     #[schema_validation]
     fn validate_schema(&self) -> Result<(), ValidationErrors> {
-        if self.add.is_empty() && self.remove.is_empty() {
+        if self.add.is_empty() && self.add_filter.is_none() && self.remove.is_empty() {
             schema_err! {
                 "no_documents",
-                "either `add` or `remove` must contain document IDs"
+                "either `add` or `remove` must contain document IDs, or `addFilter` must be given"
             }
         }
     }
@@ -81,6 +103,7 @@ pub(super) struct ListEmbeddingsPayload {
     pub collection: Option<Uuid>,
 }
 
+#[serde_as]
 #[derive(Debug, Default, Deserialize, Validate, ToSchema, IntoParams)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct ListDocumentsPayload {
@@ -98,5 +121,107 @@ pub(super) struct ListDocumentsPayload {
 
     /// If given and `true`, only return documents that are ready for processing, i.e. that have
     /// their parser and chunker configured.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
     pub ready: Option<bool>,
+
+    /// Filter by label.
+    pub label: Option<String>,
+
+    /// Comma separated tags. Only return documents with any of them.
+    #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, String>>")]
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    #[param(value_type = Option<String>)]
+    pub tags: Option<Vec<String>>,
+
+    /// Comma separated tags. Only return documents with all of them.
+    #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, String>>")]
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    #[param(value_type = Option<String>)]
+    pub all_tags: Option<Vec<String>>,
+
+    /// Comma separated file extensions, e.g. `md,pdf`.
+    #[serde_as(as = "Option<StringWithSeparator<CommaSeparator, String>>")]
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    #[param(value_type = Option<String>)]
+    pub ext: Option<Vec<String>>,
+
+    /// Only return documents created at or after the date (RFC 3339).
+    pub created_after: Option<DateTime<Utc>>,
+
+    /// Only return documents created before the date (RFC 3339).
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl ListDocumentsPayload {
+    pub fn to_filter(&self) -> DocumentFilter {
+        DocumentFilter {
+            src: self.src.clone(),
+            label: self.label.clone(),
+            tags: self.tags.clone().unwrap_or_default(),
+            all_tags: self.all_tags.clone().unwrap_or_default(),
+            ext: self.ext.clone().unwrap_or_default(),
+            created_after: self.created_after,
+            created_before: self.created_before,
+            ready: self.ready,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct UploadParams {
+    /// Label of the uploaded documents. Used when choosing their configurations.
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ListChunksParams {
+    /// If `true`, only list the chunks of the document's current parsing and
+    /// chunking configuration. Otherwise list the chunks of all configurations.
+    #[serde(default)]
+    pub current: bool,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ReconcilePayload {
+    /// If `true`, delete orphaned vectors of deleted documents and re-embed
+    /// documents whose vectors are missing.
+    #[serde(default)]
+    pub repair: bool,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ExportCollectionParams {
+    /// Snapshot file format. Defaults to `jsonl`.
+    #[serde(default)]
+    pub format: SnapshotFormat,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ImportCollectionParams {
+    /// Snapshot file format. Defaults to `jsonl`.
+    #[serde(default)]
+    pub format: SnapshotFormat,
+
+    /// Name of the created collection. Defaults to the name in the snapshot.
+    pub name: Option<String>,
+
+    /// Vector database provider of the created collection.
+    /// Defaults to the provider in the snapshot.
+    pub provider: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SwapAliasPayload {
+    /// The collection to point the alias to.
+    pub collection_id: Uuid,
 }
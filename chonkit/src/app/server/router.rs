@@ -4,7 +4,7 @@ use axum::{
     extract::{DefaultBodyLimit, State},
     http::{HeaderName, HeaderValue, Method},
     response::IntoResponse,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Json, Router,
 };
 use std::{str::FromStr, time::Duration};
@@ -18,6 +18,10 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 pub(super) mod document;
+pub(super) mod eval;
+pub(super) mod job;
+pub(super) mod preset;
+pub(super) mod rule;
 pub(super) mod vector;
 
 pub fn router(state: AppState, cors_origins: Vec<String>, cors_headers: Vec<String>) -> Router {
@@ -57,23 +61,43 @@ pub fn router(state: AppState, cors_origins: Vec<String>, cors_headers: Vec<Stri
         .route("/info", get(app_config))
         .with_state(state.clone());
 
+    let jobs = Router::new()
+        .route("/collections/:id/clone", post(clone_collection))
+        .route("/evaluations/datasets/:id/sweeps", post(eval::sweep))
+        .route("/evaluations/datasets/generate", post(eval::generate))
+        .route("/jobs", get(job::list_jobs))
+        .route("/jobs/:id", get(job::get_job))
+        .with_state(state.clone());
+
     let batch_router = Router::new()
         .route("/embeddings/batch", post(batch_embed))
-        .with_state(state.batch_embedder.clone());
+        .with_state(state.clone());
 
     let router = Router::new()
         .route("/documents", get(list_documents))
         .route("/documents", post(upload_documents))
+        .route("/collections/import", post(import_collection))
         .route_layer(DefaultBodyLimit::max(100_000_000))
         .route("/documents/:id", get(get_document))
         .route("/documents/:id", delete(delete_document))
+        .route("/documents/:id", patch(update_document))
+        .route("/documents", patch(update_documents))
         .route("/documents/:id/config", put(update_document_config))
         .route("/documents/:id/chunk/preview", post(chunk_preview))
         .route("/documents/:id/parse/preview", post(parse_preview))
+        .route("/documents/:id/chunks", get(list_document_chunks))
+        .route("/documents/:id/chunks/manual", put(save_manual_chunks))
+        .route("/documents/:id/chunks/manual", patch(edit_manual_chunks))
+        .route("/documents/:id/chunks/manual", delete(delete_manual_chunks))
+        .route("/chunks/search", get(search_chunks))
         .route("/collections", get(list_collections))
         .route("/collections", post(create_collection))
         .route("/collections/:id", get(get_collection))
         .route("/collections/:id", delete(delete_collection))
+        .route("/collections/:id/export", get(export_collection))
+        .route("/aliases", get(list_aliases))
+        .route("/aliases/:name", put(swap_alias))
+        .route("/aliases/:name", delete(delete_alias))
         .route(
             "/collections/:collection_id/documents/:document_id",
             delete(delete_embeddings),
@@ -82,15 +106,53 @@ pub fn router(state: AppState, cors_origins: Vec<String>, cors_headers: Vec<Stri
             "/collections/:collection_id/documents/:document_id/count",
             get(count_embeddings),
         )
+        .route(
+            "/collections/:collection_id/documents/:document_id/chunks",
+            get(list_chunks),
+        )
         .route("/embeddings", get(list_embedded_documents))
         .route("/embeddings", post(embed))
+        .route("/embeddings/reconcile", post(reconcile_embeddings))
+        .route(
+            "/embeddings/orphans/:provider/:name",
+            delete(delete_orphaned_collection),
+        )
         .route("/embeddings/:provider/models", get(list_embedding_models))
+        .route(
+            "/embeddings/:provider/sparse-models",
+            get(list_sparse_models),
+        )
         .route("/search", post(search))
+        .route("/context", post(context))
         .route("/display/documents", get(list_documents_display))
         .route("/display/collections", get(list_collections_display))
         .route("/display/collections/:id", get(collection_display))
+        .route("/evaluations/datasets", get(eval::list_datasets))
+        .route("/evaluations/datasets", post(eval::create_dataset))
+        .route("/evaluations/datasets/:id", get(eval::get_dataset))
+        .route("/evaluations/datasets/:id", delete(eval::delete_dataset))
+        .route(
+            "/evaluations/datasets/:id/questions",
+            post(eval::add_questions),
+        )
+        .route("/evaluations/datasets/:id/runs", get(eval::list_runs))
+        .route("/evaluations/datasets/:id/runs", post(eval::run))
+        .route("/evaluations/questions/:id", delete(eval::delete_question))
+        .route("/evaluations/runs/:id", get(eval::get_run))
+        .route("/presets", get(preset::list_presets))
+        .route("/presets", post(preset::create_preset))
+        .route("/presets/:id", get(preset::get_preset))
+        .route("/presets/:id", put(preset::update_preset))
+        .route("/presets/:id", delete(preset::delete_preset))
+        .route("/presets/:id/documents", post(preset::assign_preset))
+        .route("/rules", get(rule::list_rules))
+        .route("/rules", post(rule::create_rule))
+        .route("/rules/:id", get(rule::get_rule))
+        .route("/rules/:id", put(rule::update_rule))
+        .route("/rules/:id", delete(rule::delete_rule))
         .with_state(state.services.clone())
         .merge(batch_router)
+        .merge(jobs)
         .merge(sync);
 
     #[cfg(feature = "auth-vault")]
@@ -1,15 +1,22 @@
 use crate::{
     app::{
-        server::dto::{ConfigUpdatePayload, ListDocumentsPayload, UploadResult},
+        server::dto::{
+            ConfigUpdatePayload, ListChunksParams, ListDocumentsPayload, UploadParams, UploadResult,
+        },
         state::{AppState, ServiceState},
     },
     core::{
+        chunk::ChunkEdit,
         document::parser::ParseConfig,
         model::{
+            chunk::Chunk,
             document::{Document, DocumentConfig, DocumentDisplay, DocumentType},
             List,
         },
-        service::document::dto::{ChunkPreviewPayload, DocumentUpload},
+        service::document::dto::{
+            ChunkPreviewPayload, ChunkSearch, DocumentBulkUpdatePayload, DocumentUpdatePayload,
+            DocumentUpload, SaveChunksPayload,
+        },
     },
     error::ChonkitError,
 };
@@ -38,10 +45,11 @@ pub(super) async fn list_documents(
     params: Option<Query<ListDocumentsPayload>>,
 ) -> Result<Json<List<Document>>, ChonkitError> {
     let Query(params) = params.unwrap_or_default();
+    let filter = params.to_filter();
 
     let documents = services
         .document
-        .list_documents(params.pagination, params.src.as_deref(), params.ready)
+        .list_documents(params.pagination, filter)
         .await?;
 
     Ok(Json(documents))
@@ -97,6 +105,113 @@ pub(super) async fn get_document(
     Ok(Json(document))
 }
 
+#[utoipa::path(
+    get,
+    path = "/documents/{id}/chunks",
+    responses(
+        (status = 200, description = "List the stored chunks of a document", body = Vec<Chunk>),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Document ID"),
+        ListChunksParams,
+    )
+)]
+pub(super) async fn list_document_chunks(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    params: Option<Query<ListChunksParams>>,
+) -> Result<Json<Vec<Chunk>>, ChonkitError> {
+    let Query(params) = params.unwrap_or_default();
+    let chunks = services.document.list_chunks(id, params.current).await?;
+    Ok(Json(chunks))
+}
+
+#[utoipa::path(
+    put,
+    path = "/documents/{id}/chunks/manual",
+    responses(
+        (status = 200, description = "Save a manually curated chunk list for the document", body = Vec<Chunk>),
+        (status = 404, description = "Document not found"),
+        (status = 422, description = "Invalid chunks"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Document ID"),
+    ),
+    request_body = SaveChunksPayload
+)]
+pub(super) async fn save_manual_chunks(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SaveChunksPayload>,
+) -> Result<Json<Vec<Chunk>>, ChonkitError> {
+    let chunks = services.document.save_manual_chunks(id, payload).await?;
+    Ok(Json(chunks))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/documents/{id}/chunks/manual",
+    responses(
+        (status = 200, description = "Edit the manually curated chunks of the document", body = Vec<Chunk>),
+        (status = 404, description = "Document or manual chunks not found"),
+        (status = 422, description = "Invalid edits"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Document ID"),
+    ),
+    request_body = Vec<ChunkEdit>
+)]
+pub(super) async fn edit_manual_chunks(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(edits): Json<Vec<ChunkEdit>>,
+) -> Result<Json<Vec<Chunk>>, ChonkitError> {
+    let chunks = services.document.edit_manual_chunks(id, edits).await?;
+    Ok(Json(chunks))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/documents/{id}/chunks/manual",
+    responses(
+        (status = 204, description = "Delete the manually curated chunks of the document"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Document ID"),
+    )
+)]
+pub(super) async fn delete_manual_chunks(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ChonkitError> {
+    services.document.delete_manual_chunks(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/chunks/search",
+    responses(
+        (status = 200, description = "Full text search over stored chunks", body = Vec<Chunk>),
+        (status = 422, description = "Invalid search parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(ChunkSearch)
+)]
+pub(super) async fn search_chunks(
+    services: State<ServiceState>,
+    Query(params): Query<ChunkSearch>,
+) -> Result<Json<Vec<Chunk>>, ChonkitError> {
+    let chunks = services.document.search_chunks(params).await?;
+    Ok(Json(chunks))
+}
+
 #[utoipa::path(
     delete,
     path = "/documents/{id}",
@@ -126,11 +241,16 @@ pub(super) async fn delete_document(
         (status = 400, description = "Bad request"),
         (status = 500, description = "Internal server error")
     ),
+    params(
+        ("params" = UploadParams, Query, description = "Upload parameters"),
+    ),
 )]
 pub(super) async fn upload_documents(
     services: axum::extract::State<ServiceState>,
+    params: Option<Query<UploadParams>>,
     mut form: axum::extract::Multipart,
 ) -> Result<Json<UploadResult>, ChonkitError> {
+    let Query(params) = params.unwrap_or_default();
     let mut documents = vec![];
     let mut errors = HashMap::<String, Vec<String>>::new();
 
@@ -165,7 +285,8 @@ pub(super) async fn upload_documents(
             }
         };
 
-        let upload = DocumentUpload::new(name.to_string(), typ, &file);
+        let upload =
+            DocumentUpload::new(name.to_string(), typ, &file).with_label(params.label.clone());
 
         // Only store provider that supports upload currently
         let document = match services.document.upload("fs", upload).await {
@@ -186,6 +307,45 @@ pub(super) async fn upload_documents(
     Ok(Json(UploadResult { documents, errors }))
 }
 
+#[utoipa::path(
+    patch,
+    path = "/documents/{id}",
+    responses(
+        (status = 200, description = "Update the name, label and tags of a document", body = Document),
+        (status = 404, description = "Document not found"),
+        (status = 422, description = "Invalid update"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Document ID"),
+    ),
+    request_body = DocumentUpdatePayload
+)]
+pub(super) async fn update_document(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<DocumentUpdatePayload>,
+) -> Result<Json<Document>, ChonkitError> {
+    Ok(Json(services.document.update_document(id, payload).await?))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/documents",
+    responses(
+        (status = 200, description = "IDs of the updated documents", body = Vec<Uuid>),
+        (status = 422, description = "Invalid update"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = DocumentBulkUpdatePayload
+)]
+pub(super) async fn update_documents(
+    services: State<ServiceState>,
+    Json(payload): Json<DocumentBulkUpdatePayload>,
+) -> Result<Json<Vec<Uuid>>, ChonkitError> {
+    Ok(Json(services.document.update_documents(payload).await?))
+}
+
 #[utoipa::path(
     put,
     path = "/documents/{id}/config",
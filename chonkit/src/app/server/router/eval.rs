@@ -0,0 +1,243 @@
+use crate::{
+    app::{
+        eval::run_evaluation,
+        job::Job,
+        questions::generate_questions,
+        state::{AppState, ServiceState},
+        sweep::sweep_chunk_configs,
+    },
+    core::{
+        model::eval::{EvalDataset, EvalDatasetDisplay, EvalQuestion, EvalRun},
+        service::eval::dto::{
+            CreateEvalDatasetPayload, EvalQuestionPayload, GenerateQuestionsPayload,
+            RunEvaluationPayload, SweepPayload,
+        },
+    },
+    error::ChonkitError,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/evaluations/datasets",
+    responses(
+        (status = 200, description = "List evaluation datasets", body = Vec<EvalDataset>),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+pub(super) async fn list_datasets(
+    services: State<ServiceState>,
+) -> Result<Json<Vec<EvalDataset>>, ChonkitError> {
+    Ok(Json(services.eval.list_datasets().await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/evaluations/datasets",
+    responses(
+        (status = 200, description = "Dataset created", body = EvalDatasetDisplay),
+        (status = 409, description = "Dataset already exists"),
+        (status = 422, description = "Invalid questions"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = CreateEvalDatasetPayload
+)]
+pub(super) async fn create_dataset(
+    services: State<ServiceState>,
+    Json(payload): Json<CreateEvalDatasetPayload>,
+) -> Result<Json<EvalDatasetDisplay>, ChonkitError> {
+    Ok(Json(services.eval.create_dataset(payload).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/evaluations/datasets/{id}",
+    responses(
+        (status = 200, description = "Dataset and its questions", body = EvalDatasetDisplay),
+        (status = 404, description = "Dataset not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Dataset ID")
+    ),
+)]
+pub(super) async fn get_dataset(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EvalDatasetDisplay>, ChonkitError> {
+    Ok(Json(services.eval.get_dataset(id).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/evaluations/datasets/{id}",
+    responses(
+        (status = 204, description = "Dataset, its questions and runs deleted"),
+        (status = 404, description = "Dataset not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Dataset ID")
+    ),
+)]
+pub(super) async fn delete_dataset(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ChonkitError> {
+    services.eval.delete_dataset(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/evaluations/datasets/{id}/questions",
+    responses(
+        (status = 200, description = "Questions added to the dataset", body = Vec<EvalQuestion>),
+        (status = 404, description = "Dataset not found"),
+        (status = 422, description = "Invalid questions"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Dataset ID")
+    ),
+    request_body = Vec<EvalQuestionPayload>
+)]
+pub(super) async fn add_questions(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(questions): Json<Vec<EvalQuestionPayload>>,
+) -> Result<Json<Vec<EvalQuestion>>, ChonkitError> {
+    Ok(Json(services.eval.add_questions(id, questions).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/evaluations/questions/{id}",
+    responses(
+        (status = 204, description = "Question deleted"),
+        (status = 404, description = "Question not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Question ID")
+    ),
+)]
+pub(super) async fn delete_question(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ChonkitError> {
+    services.eval.delete_question(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/evaluations/datasets/{id}/runs",
+    responses(
+        (status = 200, description = "Dataset evaluated against the collection", body = EvalRun),
+        (status = 404, description = "Dataset or collection not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Dataset ID")
+    ),
+    request_body = RunEvaluationPayload
+)]
+pub(super) async fn run(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RunEvaluationPayload>,
+) -> Result<Json<EvalRun>, ChonkitError> {
+    Ok(Json(run_evaluation(&services, id, payload).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/evaluations/datasets/{id}/runs",
+    responses(
+        (status = 200, description = "Runs of the dataset, most recent first", body = Vec<EvalRun>),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Dataset ID")
+    ),
+)]
+pub(super) async fn list_runs(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<EvalRun>>, ChonkitError> {
+    Ok(Json(services.eval.list_runs(id).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/evaluations/runs/{id}",
+    responses(
+        (status = 200, description = "Evaluation run with the metrics of every question", body = EvalRun),
+        (status = 404, description = "Run not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Run ID")
+    ),
+)]
+pub(super) async fn get_run(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<EvalRun>, ChonkitError> {
+    Ok(Json(services.eval.get_run(id).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/evaluations/datasets/{id}/sweeps",
+    responses(
+        (status = 202, description = "Chunking configurations are being evaluated in the returned job", body = Job),
+        (status = 404, description = "Dataset, collection or documents not found"),
+        (status = 422, description = "Invalid sweep grid"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Dataset ID")
+    ),
+    request_body = SweepPayload
+)]
+pub(super) async fn sweep(
+    state: State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SweepPayload>,
+) -> Result<(StatusCode, Json<Job>), ChonkitError> {
+    let job = sweep_chunk_configs(&state.services, &state.jobs, id, payload).await?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/evaluations/datasets/generate",
+    responses(
+        (status = 202, description = "Dataset created, questions are being generated in the returned job", body = Job),
+        (status = 404, description = "Documents not found"),
+        (status = 409, description = "Dataset already exists"),
+        (status = 422, description = "Invalid parameters or no chat endpoint configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = GenerateQuestionsPayload
+)]
+pub(super) async fn generate(
+    state: State<AppState>,
+    Json(payload): Json<GenerateQuestionsPayload>,
+) -> Result<(StatusCode, Json<Job>), ChonkitError> {
+    let job = generate_questions(
+        &state.services,
+        &state.jobs,
+        state.generator.clone(),
+        payload,
+    )
+    .await?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
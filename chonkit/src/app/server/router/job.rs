@@ -0,0 +1,42 @@
+use crate::{
+    app::{job::Job, state::AppState},
+    err,
+    error::ChonkitError,
+};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/jobs",
+    responses(
+        (status = 200, description = "List background jobs, most recent first", body = Vec<Job>),
+    ),
+)]
+pub(super) async fn list_jobs(state: State<AppState>) -> Json<Vec<Job>> {
+    Json(state.jobs.list())
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    responses(
+        (status = 200, description = "Background job and its progress", body = Job),
+        (status = 404, description = "Job not found"),
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Job ID")
+    ),
+)]
+pub(super) async fn get_job(
+    state: State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Job>, ChonkitError> {
+    match state.jobs.get(id) {
+        Some(job) => Ok(Json(job)),
+        None => err!(DoesNotExist, "Job with ID '{id}'"),
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    app::state::ServiceState,
+    core::{
+        model::preset::{Preset, PresetDisplay},
+        service::preset::dto::{AssignPresetPayload, PresetPayload},
+    },
+    error::ChonkitError,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/presets",
+    responses(
+        (status = 200, description = "List parsing and chunking presets", body = Vec<Preset>),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+pub(super) async fn list_presets(
+    services: State<ServiceState>,
+) -> Result<Json<Vec<Preset>>, ChonkitError> {
+    Ok(Json(services.preset.list_presets().await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/presets",
+    responses(
+        (status = 200, description = "Preset created", body = Preset),
+        (status = 409, description = "Preset already exists"),
+        (status = 422, description = "Invalid preset"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = PresetPayload
+)]
+pub(super) async fn create_preset(
+    services: State<ServiceState>,
+    Json(payload): Json<PresetPayload>,
+) -> Result<Json<Preset>, ChonkitError> {
+    Ok(Json(services.preset.create_preset(payload).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/presets/{id}",
+    responses(
+        (status = 200, description = "Preset and its linked documents", body = PresetDisplay),
+        (status = 404, description = "Preset not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Preset ID")
+    ),
+)]
+pub(super) async fn get_preset(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PresetDisplay>, ChonkitError> {
+    Ok(Json(services.preset.get_preset(id).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/presets/{id}",
+    responses(
+        (status = 200, description = "Preset and its linked documents updated", body = PresetDisplay),
+        (status = 404, description = "Preset not found"),
+        (status = 409, description = "Preset name already taken"),
+        (status = 422, description = "Invalid preset"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Preset ID")
+    ),
+    request_body = PresetPayload
+)]
+pub(super) async fn update_preset(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<PresetPayload>,
+) -> Result<Json<PresetDisplay>, ChonkitError> {
+    Ok(Json(services.preset.update_preset(id, payload).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/presets/{id}",
+    responses(
+        (status = 204, description = "Preset deleted, linked documents keep their configurations"),
+        (status = 404, description = "Preset not found"),
+        (status = 409, description = "Preset is used by configuration rules"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Preset ID")
+    ),
+)]
+pub(super) async fn delete_preset(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ChonkitError> {
+    services.preset.delete_preset(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/presets/{id}/documents",
+    responses(
+        (status = 200, description = "IDs of the documents linked to the preset", body = Vec<Uuid>),
+        (status = 404, description = "Preset not found"),
+        (status = 422, description = "No documents selected"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Preset ID")
+    ),
+    request_body = AssignPresetPayload
+)]
+pub(super) async fn assign_preset(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AssignPresetPayload>,
+) -> Result<Json<Vec<Uuid>>, ChonkitError> {
+    Ok(Json(services.preset.assign_preset(id, payload).await?))
+}
@@ -0,0 +1,106 @@
+use crate::{
+    app::state::ServiceState,
+    core::{model::rule::ConfigRule, service::rule::dto::RulePayload},
+    error::ChonkitError,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/rules",
+    responses(
+        (status = 200, description = "List configuration rules in the order they are checked in", body = Vec<ConfigRule>),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+pub(super) async fn list_rules(
+    services: State<ServiceState>,
+) -> Result<Json<Vec<ConfigRule>>, ChonkitError> {
+    Ok(Json(services.rule.list_rules().await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/rules",
+    responses(
+        (status = 200, description = "Rule created", body = ConfigRule),
+        (status = 404, description = "Preset not found"),
+        (status = 409, description = "Rule already exists"),
+        (status = 422, description = "Invalid rule"),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = RulePayload
+)]
+pub(super) async fn create_rule(
+    services: State<ServiceState>,
+    Json(payload): Json<RulePayload>,
+) -> Result<Json<ConfigRule>, ChonkitError> {
+    Ok(Json(services.rule.create_rule(payload).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/rules/{id}",
+    responses(
+        (status = 200, description = "Rule", body = ConfigRule),
+        (status = 404, description = "Rule not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Rule ID")
+    ),
+)]
+pub(super) async fn get_rule(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ConfigRule>, ChonkitError> {
+    Ok(Json(services.rule.get_rule(id).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/rules/{id}",
+    responses(
+        (status = 200, description = "Rule updated", body = ConfigRule),
+        (status = 404, description = "Rule or preset not found"),
+        (status = 409, description = "Rule name already taken"),
+        (status = 422, description = "Invalid rule"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Rule ID")
+    ),
+    request_body = RulePayload
+)]
+pub(super) async fn update_rule(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RulePayload>,
+) -> Result<Json<ConfigRule>, ChonkitError> {
+    Ok(Json(services.rule.update_rule(id, payload).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/rules/{id}",
+    responses(
+        (status = 204, description = "Rule deleted"),
+        (status = 404, description = "Rule not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Rule ID")
+    ),
+)]
+pub(super) async fn delete_rule(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ChonkitError> {
+    services.rule.delete_rule(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,18 +1,44 @@
 use crate::{
-    app::{batch::{BatchEmbedderHandle, BatchJob, JobResult}, server::dto::{ EmbeddingBatchPayload, EmbeddingSinglePayload, ListEmbeddingsPayload, }, state::ServiceState}, core::{
-        chunk::ChunkedDocument, model::{
-            collection::{Collection, CollectionDisplay, Embedding},  List, PaginationSort
-        }, service::vector::dto::{CreateCollectionPayload, CreateEmbeddings, SearchPayload }
-    }, err, error::ChonkitError, map_err
+    app::{
+        batch::{BatchJob, JobResult},
+        clone,
+        context::{assemble_context, Context, ContextPayload},
+        job::Job,
+        reconcile::reconcile,
+        server::dto::{
+            EmbeddingBatchPayload, EmbeddingSinglePayload, ExportCollectionParams,
+            ImportCollectionParams, ListEmbeddingsPayload, ReconcilePayload, SearchResponse,
+            SwapAliasPayload,
+        },
+        state::{AppState, ServiceState},
+    },
+    core::{
+        model::{
+            collection::{
+                Collection, CollectionAlias, CollectionDisplay, CollectionImport, Embedding,
+                ReconciliationReport,
+            },
+            List, Pagination, PaginationSort,
+        },
+        service::vector::dto::{CloneCollectionPayload, CreateCollectionPayload, SearchPayload},
+        vector::StoredChunk,
+    },
+    err,
+    error::ChonkitError,
+    map_err,
 };
 use axum::{
-    extract::{Path, Query, State}, http::StatusCode, response::{sse::Event, Sse}, Json
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{sse::Event, IntoResponse, Sse},
+    Json,
 };
-use futures_util::Stream;
-use tokio_stream::StreamExt;
-use validify::Validate;
+use futures_util::{Stream, TryStreamExt};
 use std::{collections::HashMap, time::Duration};
+use tokio_stream::StreamExt;
 use uuid::Uuid;
+use validify::Validate;
 
 #[utoipa::path(
     get,
@@ -34,6 +60,136 @@ pub(super) async fn list_collections(
     Ok(Json(collections))
 }
 
+#[utoipa::path(
+    get,
+    path = "/collections/{id}/export",
+    responses(
+        (status = 200, description = "Collection snapshot containing the collection metadata and all of its points", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 404, description = "Collection not found"),
+        (status = 422, description = "Unsupported snapshot format"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Collection ID"),
+        ("params" = ExportCollectionParams, Query, description = "Export parameters"),
+    )
+)]
+pub(super) async fn export_collection(
+    services: State<ServiceState>,
+    Path(id): Path<Uuid>,
+    params: Option<Query<ExportCollectionParams>>,
+) -> Result<impl IntoResponse, ChonkitError> {
+    let Query(ExportCollectionParams { format }) = params.unwrap_or_default();
+    let collection = services.vector.get_collection(id).await?;
+    let snapshot = services.vector.export_collection(id, format).await?;
+    // Errors after the response started can only abort it
+    let snapshot = snapshot.inspect_err(ChonkitError::print);
+    let disposition = format!(
+        "attachment; filename=\"{}.{}\"",
+        collection.name,
+        format.extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(snapshot),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/collections/import",
+    request_body(content = Vec<u8>, description = "Collection snapshot", content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "Collection created from the snapshot along with the skipped documents", body = CollectionImport),
+        (status = 409, description = "Collection already exists"),
+        (status = 422, description = "Invalid snapshot or unsupported snapshot format"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("params" = ImportCollectionParams, Query, description = "Import parameters"),
+    )
+)]
+pub(super) async fn import_collection(
+    services: State<ServiceState>,
+    params: Option<Query<ImportCollectionParams>>,
+    body: Bytes,
+) -> Result<Json<CollectionImport>, ChonkitError> {
+    let Query(ImportCollectionParams {
+        format,
+        name,
+        provider,
+    }) = params.unwrap_or_default();
+    let import = services
+        .vector
+        .import_collection(body.to_vec(), format, name, provider)
+        .await?;
+    Ok(Json(import))
+}
+
+#[utoipa::path(
+    get,
+    path = "/aliases",
+    responses(
+        (status = 200, description = "List collection aliases", body = Vec<CollectionAlias>),
+        (status = 500, description = "Internal server error")
+    ),
+)]
+pub(super) async fn list_aliases(
+    services: State<ServiceState>,
+) -> Result<Json<Vec<CollectionAlias>>, ChonkitError> {
+    let aliases = services.vector.list_aliases().await?;
+    Ok(Json(aliases))
+}
+
+#[utoipa::path(
+    put,
+    path = "/aliases/{name}",
+    responses(
+        (status = 200, description = "Alias created or atomically swapped to the collection", body = CollectionAlias),
+        (status = 404, description = "Collection not found"),
+        (status = 422, description = "Invalid alias name"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("name" = String, Path, description = "Alias name")
+    ),
+    request_body = SwapAliasPayload
+)]
+pub(super) async fn swap_alias(
+    services: State<ServiceState>,
+    Path(name): Path<String>,
+    Json(payload): Json<SwapAliasPayload>,
+) -> Result<Json<CollectionAlias>, ChonkitError> {
+    let alias = services
+        .vector
+        .swap_alias(&name, payload.collection_id)
+        .await?;
+    Ok(Json(alias))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/aliases/{name}",
+    responses(
+        (status = 204, description = "Alias deleted"),
+        (status = 404, description = "Alias not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("name" = String, Path, description = "Alias name")
+    ),
+)]
+pub(super) async fn delete_alias(
+    services: State<ServiceState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ChonkitError> {
+    services.vector.delete_alias(&name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[utoipa::path(
     get,
     path = "/display/collections",
@@ -88,12 +244,33 @@ pub(super) async fn create_collection(
     services: State<ServiceState>,
     Json(payload): Json<CreateCollectionPayload>,
 ) -> Result<Json<Collection>, ChonkitError> {
-    let collection = services.vector
-        .create_collection( payload)
-        .await?;
+    let collection = services.vector.create_collection(payload).await?;
     Ok(Json(collection))
 }
 
+#[utoipa::path(
+    post,
+    path = "/collections/{id}/clone",
+    responses(
+        (status = 202, description = "Target collection created, documents are being embedded in the returned job", body = Job),
+        (status = 404, description = "Source collection not found"),
+        (status = 422, description = "Invalid target collection parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Source collection ID")
+    ),
+    request_body = CloneCollectionPayload
+)]
+pub(super) async fn clone_collection(
+    state: State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CloneCollectionPayload>,
+) -> Result<(StatusCode, Json<Job>), ChonkitError> {
+    let job = clone::clone_collection(&state.services, &state.jobs, id, payload).await?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
 #[utoipa::path(
     get,
     path = "/collections/{id}", 
@@ -130,9 +307,7 @@ pub(super) async fn delete_collection(
     services: State<ServiceState>,
     Path(collection_id): Path<Uuid>,
 ) -> Result<StatusCode, ChonkitError> {
-    services.vector
-        .delete_collection(collection_id)
-        .await?;
+    services.vector.delete_collection(collection_id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -151,7 +326,8 @@ pub(super) async fn list_embedding_models(
     services: State<ServiceState>,
     Path(provider): Path<String>,
 ) -> Result<Json<HashMap<String, usize>>, ChonkitError> {
-    let models = services.vector
+    let models = services
+        .vector
         .list_embedding_models(&provider)
         .await?
         .into_iter()
@@ -159,6 +335,25 @@ pub(super) async fn list_embedding_models(
     Ok(Json(models))
 }
 
+#[utoipa::path(
+    get,
+    path = "/embeddings/{provider}/sparse-models", 
+    responses(
+        (status = 200, description = "List available sparse embedding models", body = Vec<String>),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("provider" = String, Path, description = "Embedding provider"),
+    ),
+)]
+pub(super) async fn list_sparse_models(
+    services: State<ServiceState>,
+    Path(provider): Path<String>,
+) -> Result<Json<Vec<String>>, ChonkitError> {
+    let models = services.vector.list_sparse_models(&provider).await?;
+    Ok(Json(models))
+}
+
 #[utoipa::path(
     post,
     path = "/embeddings", 
@@ -178,31 +373,10 @@ pub(super) async fn embed(
         collection,
     } = payload;
 
-
     let document = services.document.get_document(document_id).await?;
     let collection = services.vector.get_collection(collection).await?;
-    let content = services.document.get_content(document_id).await?;
-
-    let chunks = services.document
-        .get_chunks(&document, &content)
-        .await?;
-
-    let chunks = match chunks {
-        ChunkedDocument::Ref(r) => r,
-        ChunkedDocument::Owned(ref o) => {
-            o.iter().map(|s| s.as_str()).collect()
-        }
-    };
 
-    let create = CreateEmbeddings {
-        document_id: document.id,
-        collection_id: collection.id,
-        chunks: &chunks,
-    };
-
-    services.vector
-        .create_embeddings(create)
-        .await?;
+    services.embed_document(document.id, collection.id).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -217,17 +391,30 @@ pub(super) async fn embed(
     request_body = EmbeddingBatchPayload
 )]
 pub(super) async fn batch_embed(
-    State(batch_embedder): axum::extract::State<BatchEmbedderHandle>,
+    state: axum::extract::State<AppState>,
     Json(job): Json<EmbeddingBatchPayload>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, ChonkitError>>>, ChonkitError> {
     map_err!(job.validate());
 
     let EmbeddingBatchPayload {
         collection,
-        add,
-        remove
+        mut add,
+        add_filter,
+        remove,
     } = job;
 
+    if let Some(filter) = add_filter {
+        add.extend(state.services.document.list_document_ids(filter).await?);
+        add.sort();
+        add.dedup();
+    }
+
+    if add.is_empty() && remove.is_empty() {
+        return err!(DoesNotExist, "Documents matching the filter");
+    }
+
+    let batch_embedder = &state.batch_embedder;
+
     let (tx, rx) = tokio::sync::mpsc::channel::<JobResult>(add.len() + remove.len());
 
     let job = BatchJob::new(collection, add, remove, tx);
@@ -283,15 +470,63 @@ pub(super) async fn list_embedded_documents(
         pagination,
     } = payload;
 
-    let embeddings = services.vector.list_embeddings(pagination, collection_id).await?;
+    let embeddings = services
+        .vector
+        .list_embeddings(pagination, collection_id)
+        .await?;
     Ok(Json(embeddings))
 }
 
+#[utoipa::path(
+    post,
+    path = "/embeddings/reconcile",
+    responses(
+        (status = 200, description = "Differences between the stored embeddings and the vector databases", body = ReconciliationReport),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("payload" = ReconcilePayload, Query, description = "Reconciliation parameters"),
+    ),
+)]
+pub(super) async fn reconcile_embeddings(
+    services: State<ServiceState>,
+    payload: Option<Query<ReconcilePayload>>,
+) -> Result<Json<ReconciliationReport>, ChonkitError> {
+    let Query(payload) = payload.unwrap_or_default();
+    let report = reconcile(&services, payload.repair).await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/embeddings/orphans/{provider}/{name}",
+    responses(
+        (status = 204, description = "Orphaned vector collection deleted successfully"),
+        (status = 404, description = "Orphaned vector collection not found"),
+        (status = 409, description = "Vector collection is tracked in the repository"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("provider" = String, Path, description = "Vector database provider"),
+        ("name" = String, Path, description = "Vector collection name"),
+    ),
+)]
+pub(super) async fn delete_orphaned_collection(
+    services: State<ServiceState>,
+    Path((provider, name)): Path<(String, String)>,
+) -> Result<StatusCode, ChonkitError> {
+    services
+        .vector
+        .delete_orphaned_collection(&provider, &name)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[utoipa::path(
     post,
     path = "/search", 
     responses(
-        (status = 200, description = "Search results returned", body = inline(Vec<String>)),
+        (status = 200, description = "Search results returned. Federated searches return hits merged with reciprocal rank fusion.", body = SearchResponse),
         (status = 500, description = "Internal server error")
     ),
     request_body = SearchPayload
@@ -299,9 +534,31 @@ pub(super) async fn list_embedded_documents(
 pub(super) async fn search(
     services: State<ServiceState>,
     Json(search): Json<SearchPayload>,
-) -> Result<Json<Vec<String>>, ChonkitError> {
+) -> Result<Json<SearchResponse>, ChonkitError> {
+    if search.is_federated() {
+        let hits = services.vector.federated_search(search).await?;
+        return Ok(Json(SearchResponse::Hits(hits)));
+    }
+
     let chunks = services.vector.search(search).await?;
-    Ok(Json(chunks))
+    Ok(Json(SearchResponse::Contents(chunks)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/context",
+    responses(
+        (status = 200, description = "Context assembled from the search results", body = Context),
+        (status = 500, description = "Internal server error")
+    ),
+    request_body = ContextPayload
+)]
+pub(super) async fn context(
+    services: State<ServiceState>,
+    Json(payload): Json<ContextPayload>,
+) -> Result<Json<Context>, ChonkitError> {
+    let context = assemble_context(&services, payload).await?;
+    Ok(Json(context))
 }
 
 #[utoipa::path(
@@ -320,12 +577,41 @@ pub(super) async fn count_embeddings(
     services: State<ServiceState>,
     Path((collection_id, document_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<usize>, ChonkitError> {
-    let amount = services.vector
+    let amount = services
+        .vector
         .count_embeddings(collection_id, document_id)
         .await?;
     Ok(Json(amount))
 }
 
+#[utoipa::path(
+    get,
+    path = "/collections/{collection_id}/documents/{document_id}/chunks",
+    responses(
+        (status = 200, description = "Stored chunks of a given document in a given collection, ordered by their position in the document.", body = inline(List<StoredChunk>)),
+        (status = 400, description = "Invalid pagination parameters"),
+        (status = 404, description = "Collection not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("collection_id" = Uuid, Path, description = "Collection ID"),
+        ("document_id" = Uuid, Path, description = "Document ID"),
+        ("pagination" = Pagination, Query, description = "Pagination parameters"),
+    ),
+)]
+pub(super) async fn list_chunks(
+    services: State<ServiceState>,
+    Path((collection_id, document_id)): Path<(Uuid, Uuid)>,
+    payload: Option<Query<Pagination>>,
+) -> Result<Json<List<StoredChunk>>, ChonkitError> {
+    let Query(pagination) = payload.unwrap_or_default();
+    let chunks = services
+        .vector
+        .list_chunks(collection_id, document_id, pagination)
+        .await?;
+    Ok(Json(chunks))
+}
+
 #[utoipa::path(
     delete,
     path = "/collections/{collection_id}/documents/{document_id}",
@@ -342,7 +628,8 @@ pub(super) async fn delete_embeddings(
     services: State<ServiceState>,
     Path((collection_id, document_id)): Path<(Uuid, Uuid)>,
 ) -> Result<StatusCode, ChonkitError> {
-    services.vector
+    services
+        .vector
         .delete_embeddings(collection_id, document_id)
         .await?;
     Ok(StatusCode::NO_CONTENT)
@@ -1,14 +1,24 @@
 use super::{
     batch::{BatchEmbedder, BatchEmbedderHandle},
     document::store::FsDocumentStore,
+    job::JobTracker,
+    reconcile::reconcile_on_startup,
 };
 use crate::{
     core::{
         chunk::ChunkConfig,
         document::store::DocumentStore,
         embedder::Embedder,
+        generator::Generator,
+        model::{collection::Embedding, preset::Preset},
         provider::{ProviderFactory, ProviderState},
-        service::{document::DocumentService, vector::VectorService},
+        service::{
+            document::DocumentService,
+            eval::EvalService,
+            preset::PresetService,
+            rule::RuleService,
+            vector::{dto::CreateEmbeddings, VectorService},
+        },
         vector::VectorDb,
     },
     err,
@@ -18,6 +28,7 @@ use serde::Serialize;
 use sqlx::PgPool;
 use std::{collections::HashMap, sync::Arc};
 use tracing_subscriber::EnvFilter;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -27,6 +38,12 @@ pub struct AppState {
     /// Handle for batch embedding documents.
     pub batch_embedder: BatchEmbedderHandle,
 
+    /// Background jobs and their progress.
+    pub jobs: JobTracker,
+
+    /// Chat model client for generating evaluation questions, if configured.
+    pub generator: Option<Arc<dyn Generator + Send + Sync>>,
+
     /// Downstream service providers for chonkit services.
     /// Used for displaying some metadata and in tests.
     pub providers: AppProviderState,
@@ -47,7 +64,7 @@ impl AppState {
 
         let postgres = crate::app::repo::pg::init(&args.db_url()).await;
 
-        let vector_provider = Self::init_vector_providers(args);
+        let vector_provider = Self::init_vector_providers(args, &postgres).await;
         let embedding_provider = Self::init_embedding_providers(args);
         let document_provider = Self::init_document_providers(args);
 
@@ -59,6 +76,9 @@ impl AppState {
         };
 
         let document = DocumentService::new(postgres.clone(), providers.clone().into());
+        let eval = EvalService::new(postgres.clone());
+        let preset = PresetService::new(postgres.clone());
+        let rule = RuleService::new(postgres.clone());
         let vector = VectorService::new(postgres, providers.clone().into());
 
         document.create_default_document().await;
@@ -68,7 +88,17 @@ impl AppState {
             }
         }
 
-        let service_state = ServiceState { document, vector };
+        let service_state = ServiceState {
+            document,
+            vector,
+            eval,
+            preset,
+            rule,
+        };
+
+        if let Some(mode) = args.reconcile() {
+            reconcile_on_startup(&service_state, mode).await;
+        }
 
         let batch_embedder = Self::spawn_batch_embedder(service_state.clone());
 
@@ -78,12 +108,30 @@ impl AppState {
         Self {
             services: service_state,
             batch_embedder,
+            jobs: JobTracker::default(),
+            generator: Self::init_generator(args),
             providers,
             #[cfg(feature = "auth-vault")]
             vault,
         }
     }
 
+    fn init_generator(args: &crate::config::StartArgs) -> Option<Arc<dyn Generator + Send + Sync>> {
+        let endpoint = args.llm_endpoint()?;
+
+        let mut generator = crate::app::generator::OpenAiChat::new(&endpoint);
+        if let Some(key) = args.llm_key() {
+            generator = generator.with_key(&key);
+        }
+        if let Some(model) = args.llm_model() {
+            generator = generator.with_model(&model);
+        }
+
+        tracing::info!("Using chat endpoint '{endpoint}' for question generation");
+
+        Some(Arc::new(generator))
+    }
+
     #[cfg(feature = "auth-vault")]
     async fn init_vault(args: &crate::config::StartArgs) -> crate::app::auth::VaultAuthenticator {
         crate::app::auth::VaultAuthenticator::new(
@@ -95,21 +143,39 @@ impl AppState {
         .await
     }
 
-    fn init_vector_providers(args: &crate::config::StartArgs) -> Arc<VectorDbProvider> {
+    async fn init_vector_providers(
+        args: &crate::config::StartArgs,
+        _postgres: &PgPool,
+    ) -> Arc<VectorDbProvider> {
         let mut provider = VectorDbProvider::default();
 
         #[cfg(feature = "qdrant")]
         {
-            let qdrant = crate::app::vector::qdrant::init(&args.qdrant_url());
+            let qdrant = crate::app::vector::qdrant::init(&args.qdrant_url(), _postgres.clone());
+            if let Err(e) = crate::app::vector::qdrant::remove_id_vectors(&qdrant).await {
+                tracing::error!("Error removing qdrant id vectors: {e}");
+            }
+            if let Err(e) = crate::app::vector::qdrant::create_chunk_indexes(&qdrant).await {
+                tracing::error!("Error creating qdrant chunk indexes: {e}");
+            }
             provider.register(qdrant.id(), qdrant);
         }
 
         #[cfg(feature = "weaviate")]
         {
             let weaviate = crate::app::vector::weaviate::init(&args.weaviate_url());
+            crate::app::vector::weaviate::add_object_properties(&weaviate)
+                .await
+                .expect("error adding weaviate object properties");
             provider.register(weaviate.id(), weaviate);
         }
 
+        #[cfg(feature = "pg-vectors")]
+        {
+            let postgres = crate::app::vector::postgres::init(_postgres.clone());
+            provider.register(postgres.id(), postgres);
+        }
+
         Arc::new(provider)
     }
 
@@ -121,18 +187,27 @@ impl AppState {
 
         #[cfg(feature = "fe-local")]
         {
-            let fastembed =
-                Arc::new(crate::app::embedder::fastembed::local::LocalFastEmbedder::new());
+            let fastembed = Arc::new(
+                crate::app::embedder::fastembed::local::LocalFastEmbedder::with_config(
+                    _args.fembed_config(),
+                ),
+            );
             provider.register(fastembed.id(), fastembed);
         }
 
+        // Remote embedders are registered as single instances shared by all services
+        // and batch jobs, which in turn share their rate limiters.
+        #[cfg(any(feature = "fe-remote", feature = "openai"))]
+        let request_config = _args.embed_request_config();
+
         // Remote implementations take precedence. This will override the local implementation
         // in the provider state.
         #[cfg(feature = "fe-remote")]
         {
             let fastembed = Arc::new(
-                crate::app::embedder::fastembed::remote::RemoteFastEmbedder::new(
+                crate::app::embedder::fastembed::remote::RemoteFastEmbedder::with_config(
                     _args.fembed_url(),
+                    request_config.clone(),
                 ),
             );
             provider.register(fastembed.id(), fastembed);
@@ -140,9 +215,14 @@ impl AppState {
 
         #[cfg(feature = "openai")]
         {
-            let openai = Arc::new(crate::app::embedder::openai::OpenAiEmbeddings::new(
+            let mut openai = crate::app::embedder::openai::OpenAiEmbeddings::with_config(
                 &_args.open_ai_key(),
-            ));
+                request_config,
+            );
+            if let Some(endpoint) = _args.openai_endpoint() {
+                openai = openai.with_endpoint(&endpoint);
+            }
+            let openai = Arc::new(openai);
             provider.register(openai.id(), openai);
         }
 
@@ -170,6 +250,7 @@ impl AppState {
         let mut default_chunkers = vec![
             ChunkConfig::sliding_default(),
             ChunkConfig::snapping_default(),
+            ChunkConfig::markdown_default(),
         ];
 
         for provider in self.providers.embedding.list_provider_ids() {
@@ -209,6 +290,7 @@ impl AppState {
             embedding_providers,
             default_chunkers,
             document_providers,
+            presets: self.services.preset.list_presets().await?,
         })
     }
 
@@ -222,6 +304,8 @@ impl AppState {
             services: services.clone(),
             providers,
             batch_embedder: Self::spawn_batch_embedder(services),
+            jobs: JobTracker::default(),
+            generator: None,
             #[cfg(feature = "auth-vault")]
             vault,
         }
@@ -251,6 +335,35 @@ impl From<AppProviderState> for ProviderState {
 pub struct ServiceState {
     pub document: DocumentService<PgPool>,
     pub vector: VectorService<PgPool>,
+    pub eval: EvalService<PgPool>,
+    pub preset: PresetService<PgPool>,
+    pub rule: RuleService<PgPool>,
+}
+
+impl ServiceState {
+    /// Chunk a document using its current configuration and embed it into the collection.
+    ///
+    /// * `document_id`: Document ID.
+    /// * `collection_id`: Collection ID.
+    pub async fn embed_document(
+        &self,
+        document_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<Embedding, ChonkitError> {
+        let stored = self.document.get_or_create_chunks(document_id).await?;
+
+        let chunks: Vec<&str> = stored.iter().map(|chunk| chunk.content.as_str()).collect();
+        let chunk_ids: Vec<Uuid> = stored.iter().map(|chunk| chunk.id).collect();
+
+        let create = CreateEmbeddings {
+            document_id,
+            collection_id,
+            chunks: &chunks,
+            chunk_ids: Some(&chunk_ids),
+        };
+
+        self.vector.create_embeddings(create).await
+    }
 }
 
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
@@ -267,6 +380,9 @@ pub struct AppConfig {
 
     /// A list of default chunking configurations.
     pub default_chunkers: Vec<ChunkConfig>,
+
+    /// A list of user defined parsing and chunking presets.
+    pub presets: Vec<Preset>,
 }
 
 /// Creates and implements functions for `$target` to easily get an instance of whatever
@@ -0,0 +1,483 @@
+use super::{
+    eval::{evaluate_collection, DEFAULT_K},
+    job::{Job, JobKind, JobResult, JobTracker},
+    state::ServiceState,
+};
+use crate::{
+    core::{
+        chunk::{ChunkConfig, ChunkedDocument},
+        eval::{EvalMetric, RetrievalMetrics, SweepChunker},
+        model::{collection::Collection, eval::EvalRun},
+        service::{
+            eval::dto::SweepPayload,
+            vector::dto::{CreateCollectionPayload, CreateEmbeddings},
+        },
+    },
+    err,
+    error::ChonkitError,
+    map_err,
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+use validify::Validify;
+
+/// The results of a sweep, updated as candidates get evaluated.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepReport {
+    /// Metric the candidates are ranked by.
+    pub metric: EvalMetric,
+
+    pub candidates: Vec<SweepCandidate>,
+
+    /// Index of the best evaluated candidate in `candidates`.
+    pub best: Option<usize>,
+
+    /// Whether the best configuration was applied to the documents.
+    pub applied: bool,
+
+    /// Total amount of chunks embedded during the sweep.
+    pub chunks: usize,
+}
+
+/// A chunking configuration evaluated in a sweep.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepCandidate {
+    pub config: ChunkConfig,
+
+    /// Amount of chunks the configuration produced for all documents.
+    pub chunks: Option<usize>,
+
+    /// Evaluation run of the candidate. The run's collection is deleted
+    /// once the candidate is evaluated.
+    pub run_id: Option<Uuid>,
+
+    pub metrics: Option<RetrievalMetrics>,
+
+    /// Why the candidate was not evaluated, if it was skipped due to the sweep's limits.
+    pub skipped: Option<String>,
+}
+
+impl SweepReport {
+    /// Index of the evaluated candidate with the highest metric. Ties are won by
+    /// the candidate with less chunks.
+    fn best(&self) -> Option<usize> {
+        let mut best: Option<(usize, f64, usize)> = None;
+
+        for (i, candidate) in self.candidates.iter().enumerate() {
+            let (Some(metrics), Some(chunks)) = (candidate.metrics, candidate.chunks) else {
+                continue;
+            };
+
+            let score = metrics.get(self.metric);
+
+            match best {
+                Some((_, best_score, best_chunks))
+                    if score < best_score || (score == best_score && chunks >= best_chunks) => {}
+                _ => best = Some((i, score, chunks)),
+            }
+        }
+
+        best.map(|(i, _, _)| i)
+    }
+}
+
+/// Evaluate a grid of chunking configurations for documents against a dataset in a
+/// background job.
+///
+/// For every candidate, the documents are chunked and embedded into a temporary
+/// collection using the models and providers of `payload.collection_id`. The collection
+/// is evaluated with the dataset's questions and deleted afterwards. The runs are stored
+/// like any other evaluation run. The report of the job is updated after every candidate.
+///
+/// Invalid parameters are reported before the job starts.
+///
+/// * `services`: Chonkit services.
+/// * `jobs`: Where to track the job.
+/// * `dataset_id`: Dataset ID.
+/// * `payload`: Sweep parameters.
+pub async fn sweep_chunk_configs(
+    services: &ServiceState,
+    jobs: &JobTracker,
+    dataset_id: Uuid,
+    mut payload: SweepPayload,
+) -> Result<Job, ChonkitError> {
+    map_err!(payload.validify());
+
+    let questions = services.eval.list_questions(dataset_id).await?;
+
+    if questions.is_empty() {
+        return err!(
+            DoesNotExist,
+            "Questions in evaluation dataset '{dataset_id}'"
+        );
+    }
+
+    let template = services
+        .vector
+        .get_collection(payload.collection_id)
+        .await?;
+
+    let mut documents = std::mem::take(&mut payload.documents);
+
+    if documents.is_empty() {
+        documents = questions
+            .iter()
+            .flat_map(|question| question.documents.iter().copied())
+            .collect();
+    }
+
+    documents.sort();
+    documents.dedup();
+
+    if documents.is_empty() {
+        return err!(
+            DoesNotExist,
+            "Documents to sweep; the questions of dataset '{dataset_id}' expect no documents"
+        );
+    }
+
+    for document_id in documents.iter() {
+        services.document.get_document(*document_id).await?;
+    }
+
+    let semantic = template
+        .model
+        .as_deref()
+        .map(|model| (template.embedder.as_str(), model));
+
+    if semantic.is_none() && payload.grid.chunkers.contains(&SweepChunker::Semantic) {
+        return err!(
+            InvalidEmbeddingModel,
+            "Semantic chunkers require collection '{}' to have a dense model",
+            template.name
+        );
+    }
+
+    let candidates = payload.grid.candidates(semantic);
+
+    if candidates.is_empty() {
+        return err!(Chunks, "sweep grid contains no valid candidates");
+    }
+
+    let kind = JobKind::Sweep {
+        dataset_id,
+        collection_id: template.id,
+        documents: documents.clone(),
+        apply: payload.apply,
+    };
+
+    let job = jobs.start(kind, candidates.len());
+
+    tracing::info!(
+        "Starting job '{}' | Sweeping {} chunking configuration(s) for {} document(s) against dataset '{dataset_id}'",
+        job.id,
+        candidates.len(),
+        documents.len()
+    );
+
+    let services = services.clone();
+    let jobs = jobs.clone();
+    let job_id = job.id;
+
+    tokio::spawn(async move {
+        let sweep = Sweep {
+            services: &services,
+            jobs: &jobs,
+            job_id,
+            dataset_id,
+            template,
+            documents,
+            k: payload.k.unwrap_or(DEFAULT_K),
+            max_chunks: payload.max_chunks,
+            max_duration: payload.max_duration.map(Duration::from_secs),
+        };
+
+        let mut report = SweepReport {
+            metric: payload.metric,
+            candidates: candidates
+                .into_iter()
+                .map(|config| SweepCandidate {
+                    config,
+                    chunks: None,
+                    run_id: None,
+                    metrics: None,
+                    skipped: None,
+                })
+                .collect(),
+            best: None,
+            applied: false,
+            chunks: 0,
+        };
+
+        sweep.run(&mut report).await;
+
+        if payload.apply {
+            sweep.apply(&mut report).await;
+        }
+
+        jobs.set_result(job_id, JobResult::Sweep(report));
+        jobs.finish(job_id);
+
+        tracing::info!("Job '{job_id}' finished");
+    });
+
+    Ok(job)
+}
+
+struct Sweep<'a> {
+    services: &'a ServiceState,
+    jobs: &'a JobTracker,
+    job_id: Uuid,
+    dataset_id: Uuid,
+    template: Collection,
+    documents: Vec<Uuid>,
+    k: u32,
+    max_chunks: Option<usize>,
+    max_duration: Option<Duration>,
+}
+
+impl Sweep<'_> {
+    async fn run(&self, report: &mut SweepReport) {
+        let started = Instant::now();
+
+        let mut contents = Vec::with_capacity(self.documents.len());
+
+        for document_id in self.documents.iter() {
+            match self.services.document.get_content(*document_id).await {
+                Ok(content) => contents.push((*document_id, content)),
+                Err(e) => {
+                    e.print();
+                    self.jobs.error(
+                        self.job_id,
+                        format!("Reading document '{document_id}': {e}"),
+                    );
+                    return;
+                }
+            }
+        }
+
+        for i in 0..report.candidates.len() {
+            if self
+                .max_duration
+                .is_some_and(|max_duration| started.elapsed() > max_duration)
+            {
+                report.candidates[i].skipped = Some("Time limit exceeded".to_string());
+                self.jobs.item_ok(self.job_id);
+                continue;
+            }
+
+            let config = report.candidates[i].config.clone();
+
+            match self.candidate(i, config, &contents, report).await {
+                Ok(()) => self.jobs.item_ok(self.job_id),
+                Err(e) => {
+                    e.print();
+                    let config = &report.candidates[i].config;
+                    self.jobs
+                        .item_err(self.job_id, format!("Evaluating {config} ({i}): {e}"));
+                }
+            }
+
+            report.best = report.best();
+            self.jobs
+                .set_result(self.job_id, JobResult::Sweep(report.clone()));
+        }
+    }
+
+    /// Chunk, embed and evaluate a single candidate.
+    async fn candidate(
+        &self,
+        i: usize,
+        config: ChunkConfig,
+        contents: &[(Uuid, String)],
+        report: &mut SweepReport,
+    ) -> Result<(), ChonkitError> {
+        let mut chunked = Vec::with_capacity(contents.len());
+
+        for (document_id, content) in contents.iter() {
+            let chunks = self
+                .services
+                .document
+                .chunk(config.clone(), content)
+                .await?;
+            chunked.push((*document_id, chunks));
+        }
+
+        let chunks = chunked
+            .iter()
+            .map(|(_, chunks)| match chunks {
+                ChunkedDocument::Ref(chunks) => chunks.len(),
+                ChunkedDocument::Owned(chunks) => chunks.len(),
+            })
+            .sum();
+
+        report.candidates[i].chunks = Some(chunks);
+
+        if let Some(max_chunks) = self.max_chunks {
+            if report.chunks + chunks > max_chunks {
+                report.candidates[i].skipped = Some(format!(
+                    "Chunk limit exceeded; {chunks} chunk(s) with {} already embedded",
+                    report.chunks
+                ));
+                return Ok(());
+            }
+        }
+
+        let name = format!(
+            "Sweep_{}_{i}",
+            self.job_id.simple().to_string().split_at(8).0
+        );
+
+        let create = CreateCollectionPayload {
+            name,
+            model: self.template.model.clone(),
+            sparse_model: self.template.sparse_model.clone(),
+            vector_provider: self.template.provider.clone(),
+            embedding_provider: self.template.embedder.clone(),
+            index_config: self.template.index_config.0,
+        };
+
+        let collection = self.services.vector.create_collection(create).await?;
+
+        report.chunks += chunks;
+
+        let run = self.evaluate(&collection, &config, &chunked).await;
+
+        // The collection is only needed for the evaluation
+        if let Err(e) = self.services.vector.delete_collection(collection.id).await {
+            e.print();
+            self.jobs.error(
+                self.job_id,
+                format!("Deleting collection '{}': {e}", collection.name),
+            );
+        }
+
+        let run = run?;
+
+        report.candidates[i].run_id = Some(run.id);
+        report.candidates[i].metrics = Some(run.metrics);
+
+        Ok(())
+    }
+
+    async fn evaluate(
+        &self,
+        collection: &Collection,
+        config: &ChunkConfig,
+        chunked: &[(Uuid, ChunkedDocument<'_>)],
+    ) -> Result<EvalRun, ChonkitError> {
+        for (document_id, chunks) in chunked.iter() {
+            let chunks = match chunks {
+                ChunkedDocument::Ref(chunks) => chunks.clone(),
+                ChunkedDocument::Owned(chunks) => chunks.iter().map(|s| s.as_str()).collect(),
+            };
+
+            let create = CreateEmbeddings {
+                document_id: *document_id,
+                collection_id: collection.id,
+                chunks: &chunks,
+                chunk_ids: None,
+            };
+
+            self.services.vector.create_embeddings(create).await?;
+        }
+
+        let chunk_configs: HashMap<Uuid, ChunkConfig> = self
+            .documents
+            .iter()
+            .map(|document_id| (*document_id, config.clone()))
+            .collect();
+
+        evaluate_collection(
+            self.services,
+            self.dataset_id,
+            collection,
+            self.k,
+            Some(chunk_configs),
+        )
+        .await
+    }
+
+    /// Set the best configuration as the chunking configuration of the documents.
+    async fn apply(&self, report: &mut SweepReport) {
+        let Some(best) = report.best else {
+            self.jobs.error(
+                self.job_id,
+                "No candidate was evaluated; nothing to apply".to_string(),
+            );
+            return;
+        };
+
+        let config = &report.candidates[best].config;
+        let mut applied = true;
+
+        for document_id in self.documents.iter() {
+            if let Err(e) = self
+                .services
+                .document
+                .update_chunker(*document_id, config.clone())
+                .await
+            {
+                e.print();
+                applied = false;
+                self.jobs.error(
+                    self.job_id,
+                    format!("Applying {config} to document '{document_id}': {e}"),
+                );
+            }
+        }
+
+        report.applied = applied;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(recall: f64, chunks: Option<usize>) -> SweepCandidate {
+        SweepCandidate {
+            config: ChunkConfig::sliding_default(),
+            chunks,
+            run_id: None,
+            metrics: chunks.map(|_| RetrievalMetrics {
+                recall,
+                ..Default::default()
+            }),
+            skipped: None,
+        }
+    }
+
+    #[test]
+    fn picks_best_candidate() {
+        let mut report = SweepReport {
+            metric: EvalMetric::Recall,
+            candidates: vec![
+                candidate(0.5, Some(10)),
+                candidate(0.8, Some(20)),
+                // Same recall with less chunks
+                candidate(0.8, Some(15)),
+                // Not evaluated
+                candidate(1., None),
+            ],
+            best: None,
+            applied: false,
+            chunks: 0,
+        };
+
+        assert_eq!(Some(2), report.best());
+
+        report.metric = EvalMetric::Mrr;
+        // All zero, fewest chunks wins
+        assert_eq!(Some(0), report.best());
+
+        report.candidates.clear();
+        assert_eq!(None, report.best());
+    }
+}
@@ -10,7 +10,10 @@ use super::{
         VectorDbProvider,
     },
 };
-use crate::core::service::{document::DocumentService, vector::VectorService};
+use crate::core::service::{
+    document::DocumentService, eval::EvalService, preset::PresetService, rule::RuleService,
+    vector::VectorService,
+};
 use crate::{
     config::DEFAULT_COLLECTION_EMBEDDING_MODEL,
     core::{
@@ -46,7 +49,7 @@ impl TestState {
         let (postgres, postgres_img) = init_postgres().await;
 
         #[cfg(feature = "qdrant")]
-        let (qdrant, qdrant_img) = init_qdrant().await;
+        let (qdrant, qdrant_img) = init_qdrant(postgres.clone()).await;
 
         #[cfg(feature = "weaviate")]
         let (weaviate, weaviate_img) = init_weaviate().await;
@@ -122,6 +125,9 @@ impl TestState {
 
         let services = ServiceState {
             vector: VectorService::new(postgres.clone(), providers.clone().into()),
+            eval: EvalService::new(postgres.clone()),
+            preset: PresetService::new(postgres.clone()),
+            rule: RuleService::new(postgres.clone()),
             document: DocumentService::new(postgres, providers.clone().into()),
         };
 
@@ -173,10 +179,13 @@ pub async fn init_postgres() -> (sqlx::PgPool, PostgresContainer) {
 }
 
 /// Setup a qdrant test container and connect to it using QdrantDb.
+/// Collection information is read from the given Postgres instance.
 /// When using suitest's [before_all][suitest::before_all], make sure you return this, othwerise the
 /// container will get dropped and cleaned up.
 #[cfg(feature = "qdrant")]
-pub async fn init_qdrant() -> (
+pub async fn init_qdrant(
+    postgres: sqlx::PgPool,
+) -> (
     super::vector::qdrant::QdrantDb,
     ContainerAsync<GenericImage>,
 ) {
@@ -192,7 +201,10 @@ pub async fn init_qdrant() -> (
     let qd_host = qd_image.get_host().await.unwrap();
     let qd_port = qd_image.get_host_port_ipv4(6334).await.unwrap();
     let qd_url = format!("http://{qd_host}:{qd_port}");
-    (crate::app::vector::qdrant::init(&qd_url), qd_image)
+    (
+        crate::app::vector::qdrant::init(&qd_url, postgres),
+        qd_image,
+    )
 }
 
 /// Setup a weaviate test container and connect to it using WeaviateDb.
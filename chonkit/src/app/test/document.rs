@@ -3,20 +3,40 @@
 #[suitest::suite_cfg(sequential = true)]
 mod document_service_integration_tests {
     use crate::{
-        app::test::{TestState, TestStateConfig},
+        app::{
+            generator::{stub, OpenAiChat},
+            job::{JobKind, JobStatus},
+            questions::generate_questions,
+            test::{TestState, TestStateConfig},
+        },
         core::{
+            chunk::{ChunkConfig, ChunkEdit},
             document::parser::{docx::DocxParser, pdf::PdfParser, text::TextParser, ParseConfig},
-            model::document::{DocumentType, TextDocumentType},
+            model::{
+                chunk::ChunkSource,
+                document::{DocumentFilter, DocumentType, TextDocumentType},
+                Pagination, PaginationSort,
+            },
             provider::ProviderFactory,
+            rule::RuleCriteria,
             service::{
-                document::dto::DocumentUpload,
+                document::dto::{
+                    ChunkSearch, DocumentBulkUpdatePayload, DocumentUpdatePayload, DocumentUpload,
+                    SaveChunksPayload,
+                },
+                eval::dto::GenerateQuestionsPayload,
+                preset::dto::{AssignPresetPayload, PresetPayload},
+                rule::dto::RulePayload,
                 vector::dto::{CreateCollectionPayload, CreateEmbeddings},
             },
+            vector::VectorIndexConfig,
         },
+        error::ChonkitErr,
     };
 
     const TEST_UPLOAD_PATH: &str = "__document_service_test_upload__";
     const TEST_DOCS_PATH: &str = "test/docs";
+    use std::sync::Arc;
     use suitest::{after_all, before_all, cleanup};
 
     #[before_all]
@@ -51,6 +71,7 @@ mod document_service_integration_tests {
             name: "UPLOAD_TEST_TXT".to_string(),
             ty: DocumentType::Text(TextDocumentType::Txt),
             file: content,
+            label: None,
         };
 
         let document = service.upload("fs", upload).await.unwrap();
@@ -76,6 +97,7 @@ mod document_service_integration_tests {
             name: "UPLOAD_TEST_PDF".to_string(),
             ty: DocumentType::Pdf,
             file: content,
+            label: None,
         };
 
         let document = service.upload("fs", upload).await.unwrap();
@@ -101,6 +123,7 @@ mod document_service_integration_tests {
             name: "UPLOAD_TEST_DOCX".to_string(),
             ty: DocumentType::Docx,
             file: content,
+            label: None,
         };
 
         let document = service.upload("fs", upload).await.unwrap();
@@ -127,6 +150,7 @@ mod document_service_integration_tests {
             name: "UPLOAD_TEST_PARSER".to_string(),
             ty: DocumentType::Pdf,
             file: content,
+            label: None,
         };
 
         let document = service.upload("fs", upload).await.unwrap();
@@ -169,6 +193,7 @@ mod document_service_integration_tests {
                     name: "UPLOAD_TEST_PARSER".to_string(),
                     ty: DocumentType::Pdf,
                     file: content,
+                    label: None,
                 };
 
                 let document = state
@@ -189,16 +214,20 @@ mod document_service_integration_tests {
 
                 let collection_1 = CreateCollectionPayload {
                     name: "DeleteDocumentTestCollection1".to_string(),
-                    model: embedder.default_model().0,
+                    model: Some(embedder.default_model().0),
+                    sparse_model: None,
                     vector_provider: vector_db.id().to_string(),
                     embedding_provider: embedder.id().to_string(),
+                    index_config: VectorIndexConfig::default(),
                 };
 
                 let collection_2 = CreateCollectionPayload {
                     name: "DeleteDocumentTestCollection2".to_string(),
-                    model: embedder.default_model().0,
+                    model: Some(embedder.default_model().0),
+                    sparse_model: None,
                     vector_provider: vector_db.id().to_string(),
                     embedding_provider: embedder.id().to_string(),
+                    index_config: VectorIndexConfig::default(),
                 };
 
                 let collection_1 = state
@@ -223,12 +252,14 @@ mod document_service_integration_tests {
                     document_id: document.id,
                     collection_id: collection_1.id,
                     chunks: &[&content],
+                    chunk_ids: None,
                 };
 
                 let embeddings_2 = CreateEmbeddings {
                     document_id: document.id,
                     collection_id: collection_2.id,
                     chunks: &[&content],
+                    chunk_ids: None,
                 };
 
                 state
@@ -315,4 +346,680 @@ mod document_service_integration_tests {
             }
         }
     }
+
+    #[test]
+    async fn generates_questions_from_chunks(state: TestState) {
+        let service = state.app.services.document.clone();
+
+        let upload = DocumentUpload {
+            name: "GENERATE_TEST_TXT".to_string(),
+            ty: DocumentType::Text(TextDocumentType::Txt),
+            file: b"Penguins live in the southern hemisphere.",
+            label: None,
+        };
+
+        let document = service.upload("fs", upload).await.unwrap();
+
+        let endpoint = stub::spawn("1. Where do penguins live?\n2. What do penguins eat?").await;
+        let generator = Arc::new(OpenAiChat::new(&endpoint).with_model("stub"));
+
+        let payload = GenerateQuestionsPayload {
+            name: "Test_generated_dataset".to_string(),
+            documents: vec![document.id],
+            chunks_per_document: Some(3),
+            questions_per_chunk: Some(1),
+            model: None,
+            system_prompt: None,
+            prompt: Some("Question for: {chunk}".to_string()),
+        };
+
+        let job = generate_questions(
+            &state.app.services,
+            &state.app.jobs,
+            Some(generator),
+            payload,
+        )
+        .await
+        .unwrap();
+
+        let JobKind::Questions { dataset_id, .. } = job.kind else {
+            panic!("expected a question generation job");
+        };
+
+        let job = loop {
+            let job = state.app.jobs.get(job.id).unwrap();
+            if job.status != JobStatus::Running {
+                break job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        };
+
+        assert_eq!(JobStatus::Finished, job.status, "{:?}", job.errors);
+
+        let questions = state
+            .app
+            .services
+            .eval
+            .list_questions(dataset_id)
+            .await
+            .unwrap();
+
+        assert!(!questions.is_empty());
+        for question in questions {
+            assert_eq!("Where do penguins live?", question.question);
+            assert_eq!(vec![document.id], question.documents);
+            assert_eq!(1, question.spans.len());
+        }
+
+        state
+            .app
+            .services
+            .eval
+            .delete_dataset(dataset_id)
+            .await
+            .unwrap();
+
+        service.delete(document.id).await.unwrap();
+    }
+
+    #[test]
+    async fn stores_chunks_per_config(state: TestState) {
+        let service = state.app.services.document.clone();
+
+        let upload = DocumentUpload {
+            name: "CHUNKS_TEST_TXT".to_string(),
+            ty: DocumentType::Text(TextDocumentType::Txt),
+            file: b"Penguins live in the southern hemisphere. Puffins live in the north.",
+            label: None,
+        };
+
+        let document = service.upload("fs", upload).await.unwrap();
+
+        service
+            .update_chunker(document.id, ChunkConfig::sliding(30, 5).unwrap())
+            .await
+            .unwrap();
+
+        let chunks = service.get_or_create_chunks(document.id).await.unwrap();
+        assert!(chunks.len() > 1);
+
+        let content = service.get_content(document.id).await.unwrap();
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(i as i32, chunk.chunk_index);
+            let (start, end) = (chunk.start_offset.unwrap(), chunk.end_offset.unwrap());
+            assert_eq!(chunk.content, content[start as usize..end as usize]);
+        }
+
+        // Chunks are reused for the same configuration
+        let reused = service.get_or_create_chunks(document.id).await.unwrap();
+        assert_eq!(
+            chunks.iter().map(|c| c.id).collect::<Vec<_>>(),
+            reused.iter().map(|c| c.id).collect::<Vec<_>>()
+        );
+
+        service
+            .update_chunker(document.id, ChunkConfig::sliding(1000, 0).unwrap())
+            .await
+            .unwrap();
+
+        let rechunked = service.get_or_create_chunks(document.id).await.unwrap();
+        assert_eq!(1, rechunked.len());
+        assert_ne!(chunks[0].config_hash, rechunked[0].config_hash);
+
+        let current = service.list_chunks(document.id, true).await.unwrap();
+        assert_eq!(1, current.len());
+
+        let all = service.list_chunks(document.id, false).await.unwrap();
+        assert_eq!(chunks.len() + 1, all.len());
+
+        let found = service
+            .search_chunks(ChunkSearch {
+                query: "puffins".to_string(),
+                document_id: Some(document.id),
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert!(!found.is_empty());
+        assert!(found.iter().all(|c| c.content.contains("Puffins")));
+
+        service.delete(document.id).await.unwrap();
+
+        assert!(service.list_chunks(document.id, false).await.is_err());
+    }
+
+    #[test]
+    async fn curates_manual_chunks(state: TestState) {
+        let service = state.app.services.document.clone();
+
+        let upload = DocumentUpload {
+            name: "MANUAL_CHUNKS_TEST_TXT".to_string(),
+            ty: DocumentType::Text(TextDocumentType::Txt),
+            file: b"Penguins live in the south. Puffins live in the north.",
+            label: None,
+        };
+
+        let document = service.upload("fs", upload).await.unwrap();
+
+        let config = service.get_config(document.id).await.unwrap();
+        assert_eq!(ChunkSource::Automatic, config.chunk_source);
+
+        let saved = service
+            .save_manual_chunks(
+                document.id,
+                SaveChunksPayload {
+                    chunks: Some(vec![
+                        "Penguins live".to_string(),
+                        "in the south.".to_string(),
+                        "Puffins live in the north.".to_string(),
+                    ]),
+                    preview: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(3, saved.len());
+        assert_eq!(Some(28), saved[2].start_offset);
+
+        let config = service.get_config(document.id).await.unwrap();
+        assert_eq!(ChunkSource::Manual, config.chunk_source);
+
+        let edited = service
+            .edit_manual_chunks(
+                document.id,
+                vec![ChunkEdit::Merge {
+                    index: 0,
+                    separator: None,
+                }],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(2, edited.len());
+        assert_eq!(saved[0].id, edited[0].id);
+        assert_eq!("Penguins live in the south.", edited[0].content);
+        assert_eq!(Some(0), edited[0].start_offset);
+
+        // Embedding uses the curated chunks
+        let chunks = service.get_or_create_chunks(document.id).await.unwrap();
+        assert_eq!(
+            edited.iter().map(|c| c.id).collect::<Vec<_>>(),
+            chunks.iter().map(|c| c.id).collect::<Vec<_>>()
+        );
+
+        let invalid = service
+            .edit_manual_chunks(document.id, vec![ChunkEdit::Delete { index: 2 }])
+            .await;
+        assert!(invalid.is_err());
+
+        // Changing the content makes the curated chunks stale until they are saved again
+        tokio::fs::write(&document.path, b"Penguins live in the south.")
+            .await
+            .unwrap();
+        service.sync("fs").await.unwrap();
+
+        let config = service.get_config(document.id).await.unwrap();
+        assert_eq!(ChunkSource::Manual, config.chunk_source);
+        assert!(config.stale_chunks);
+        assert!(matches!(
+            service
+                .get_or_create_chunks(document.id)
+                .await
+                .unwrap_err()
+                .error,
+            ChonkitErr::Chunks(_)
+        ));
+
+        service
+            .save_manual_chunks(
+                document.id,
+                SaveChunksPayload {
+                    chunks: Some(vec!["Penguins live in the south.".to_string()]),
+                    preview: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let config = service.get_config(document.id).await.unwrap();
+        assert!(!config.stale_chunks);
+        assert_eq!(
+            1,
+            service
+                .get_or_create_chunks(document.id)
+                .await
+                .unwrap()
+                .len()
+        );
+
+        assert_eq!(1, service.delete_manual_chunks(document.id).await.unwrap());
+
+        let config = service.get_config(document.id).await.unwrap();
+        assert_eq!(ChunkSource::Automatic, config.chunk_source);
+
+        let missing = service
+            .edit_manual_chunks(document.id, vec![ChunkEdit::Delete { index: 0 }])
+            .await;
+        assert!(missing.is_err());
+
+        service.delete(document.id).await.unwrap();
+    }
+
+    #[test]
+    async fn syncs_presets_with_linked_documents(state: TestState) {
+        let documents = state.app.services.document.clone();
+        let presets = state.app.services.preset.clone();
+
+        let labeled = documents
+            .upload(
+                "fs",
+                DocumentUpload::new(
+                    "PRESET_LABELED_TEST_TXT".to_string(),
+                    DocumentType::Text(TextDocumentType::Txt),
+                    b"Penguins live in the south.",
+                ),
+            )
+            .await
+            .unwrap();
+
+        let selected = documents
+            .upload(
+                "fs",
+                DocumentUpload::new(
+                    "PRESET_SELECTED_TEST_TXT".to_string(),
+                    DocumentType::Text(TextDocumentType::Txt),
+                    b"Puffins live in the north.",
+                ),
+            )
+            .await
+            .unwrap();
+
+        documents
+            .update_document(
+                labeled.id,
+                DocumentUpdatePayload {
+                    label: Some("preset-test".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let preset = presets
+            .create_preset(PresetPayload {
+                name: " preset-test ".to_string(),
+                parse_config: ParseConfig::new(1, 0),
+                chunk_config: ChunkConfig::sliding(100, 10).unwrap(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!("preset-test", preset.name);
+
+        let duplicate = presets
+            .create_preset(PresetPayload {
+                name: "preset-test".to_string(),
+                parse_config: ParseConfig::default(),
+                chunk_config: ChunkConfig::snapping_default(),
+            })
+            .await;
+        assert!(duplicate.is_err());
+
+        let empty = presets
+            .assign_preset(preset.id, AssignPresetPayload::default())
+            .await;
+        assert!(empty.is_err());
+
+        let mut linked = presets
+            .assign_preset(
+                preset.id,
+                AssignPresetPayload {
+                    documents: vec![selected.id],
+                    label: Some("preset-test".to_string()),
+                    tags: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        linked.sort();
+
+        let mut expected = vec![labeled.id, selected.id];
+        expected.sort();
+        assert_eq!(expected, linked);
+
+        for id in [labeled.id, selected.id] {
+            let config = documents.get_config(id).await.unwrap();
+            assert_eq!(Some(preset.id), config.preset_id);
+            assert_eq!(1, config.parse_config.unwrap().start);
+            assert!(matches!(
+                config.chunk_config,
+                Some(ChunkConfig::Sliding(ref c)) if c.size == 100
+            ));
+        }
+
+        // Editing a document's configuration detaches it from the preset
+        documents
+            .update_chunker(selected.id, ChunkConfig::snapping_default())
+            .await
+            .unwrap();
+
+        let updated = presets
+            .update_preset(
+                preset.id,
+                PresetPayload {
+                    name: "preset-test".to_string(),
+                    parse_config: ParseConfig::new(2, 0),
+                    chunk_config: ChunkConfig::sliding(200, 20).unwrap(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(vec![labeled.id], updated.documents);
+
+        let config = documents.get_config(labeled.id).await.unwrap();
+        assert_eq!(2, config.parse_config.unwrap().start);
+        assert!(matches!(
+            config.chunk_config,
+            Some(ChunkConfig::Sliding(ref c)) if c.size == 200
+        ));
+
+        let config = documents.get_config(selected.id).await.unwrap();
+        assert_eq!(None, config.preset_id);
+        assert_eq!(1, config.parse_config.unwrap().start);
+        assert!(matches!(
+            config.chunk_config,
+            Some(ChunkConfig::Snapping(_))
+        ));
+
+        presets.delete_preset(preset.id).await.unwrap();
+        assert!(presets.get_preset(preset.id).await.is_err());
+
+        // Unlinked documents keep the preset's configuration
+        let config = documents.get_config(labeled.id).await.unwrap();
+        assert_eq!(None, config.preset_id);
+        assert_eq!(2, config.parse_config.unwrap().start);
+
+        documents.delete(labeled.id).await.unwrap();
+        documents.delete(selected.id).await.unwrap();
+    }
+
+    #[test]
+    async fn applies_config_rules_on_upload(state: TestState) {
+        let documents = state.app.services.document.clone();
+        let presets = state.app.services.preset.clone();
+        let rules = state.app.services.rule.clone();
+
+        let markdown = rules
+            .create_rule(RulePayload {
+                name: "rules-test-md".to_string(),
+                priority: 0,
+                criteria: RuleCriteria {
+                    types: vec!["md".to_string()],
+                    name_pattern: Some(regex::Regex::new("^RULES_TEST").unwrap()),
+                    ..Default::default()
+                },
+                preset_id: None,
+                parse_config: None,
+                chunk_config: Some(ChunkConfig::sliding(300, 30).unwrap()),
+            })
+            .await
+            .unwrap();
+
+        let invalid = rules
+            .create_rule(RulePayload {
+                name: "rules-test-invalid".to_string(),
+                priority: 0,
+                criteria: RuleCriteria::default(),
+                preset_id: None,
+                parse_config: None,
+                chunk_config: None,
+            })
+            .await;
+        assert!(invalid.is_err());
+
+        let preset = presets
+            .create_preset(PresetPayload {
+                name: "rules-test".to_string(),
+                parse_config: ParseConfig::new(3, 0),
+                chunk_config: ChunkConfig::sliding(400, 40).unwrap(),
+            })
+            .await
+            .unwrap();
+
+        let labeled = rules
+            .create_rule(RulePayload {
+                name: "rules-test-labeled".to_string(),
+                priority: 10,
+                criteria: RuleCriteria {
+                    label: Some("rules-test".to_string()),
+                    ..Default::default()
+                },
+                preset_id: Some(preset.id),
+                parse_config: None,
+                chunk_config: None,
+            })
+            .await
+            .unwrap();
+
+        let listed = rules.list_rules().await.unwrap();
+        let position = |id| listed.iter().position(|rule| rule.id == id).unwrap();
+        assert!(position(labeled.id) < position(markdown.id));
+
+        let first = documents
+            .upload(
+                "fs",
+                DocumentUpload::new(
+                    "RULES_TEST_FIRST.md".to_string(),
+                    DocumentType::Text(TextDocumentType::Md),
+                    b"# Penguins\n\nPenguins live in the south.",
+                ),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(None, first.preset_id);
+        assert_eq!(0, first.parse_config.as_ref().unwrap().start);
+        assert!(matches!(
+            first.chunk_config,
+            Some(ChunkConfig::Sliding(ref c)) if c.size == 300
+        ));
+
+        let second = documents
+            .upload(
+                "fs",
+                DocumentUpload::new(
+                    "RULES_TEST_SECOND.md".to_string(),
+                    DocumentType::Text(TextDocumentType::Md),
+                    b"# Puffins\n\nPuffins live in the north.",
+                )
+                .with_label(Some("rules-test".to_string())),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(Some(preset.id), second.preset_id);
+        let config = documents.get_config(second.id).await.unwrap();
+        assert_eq!(Some(preset.id), config.preset_id);
+        assert_eq!(3, config.parse_config.unwrap().start);
+
+        let unmatched = documents
+            .upload(
+                "fs",
+                DocumentUpload::new(
+                    "UNMATCHED_RULES_TEST.txt".to_string(),
+                    DocumentType::Text(TextDocumentType::Txt),
+                    b"Albatrosses live everywhere.",
+                ),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            unmatched.chunk_config,
+            Some(ChunkConfig::Snapping(_))
+        ));
+
+        // Presets used by rules cannot be deleted
+        let error = presets.delete_preset(preset.id).await.unwrap_err().error;
+        assert!(matches!(error, ChonkitErr::AlreadyExists(_)));
+
+        rules.delete_rule(markdown.id).await.unwrap();
+        rules.delete_rule(labeled.id).await.unwrap();
+        assert!(rules.get_rule(labeled.id).await.is_err());
+        presets.delete_preset(preset.id).await.unwrap();
+
+        for id in [first.id, second.id, unmatched.id] {
+            documents.delete(id).await.unwrap();
+        }
+    }
+
+    #[test]
+    async fn updates_and_filters_document_metadata(state: TestState) {
+        let documents = state.app.services.document.clone();
+
+        let mut ids = vec![];
+        for (name, ty) in [
+            ("METADATA_TEST_A.md", TextDocumentType::Md),
+            ("METADATA_TEST_B.txt", TextDocumentType::Txt),
+            ("METADATA_TEST_C.txt", TextDocumentType::Txt),
+        ] {
+            let document = documents
+                .upload(
+                    "fs",
+                    DocumentUpload::new(
+                        name.to_string(),
+                        DocumentType::Text(ty),
+                        format!("Metadata test document {name}.").as_bytes(),
+                    ),
+                )
+                .await
+                .unwrap();
+            ids.push(document.id);
+        }
+
+        let renamed = documents
+            .update_document(
+                ids[0],
+                DocumentUpdatePayload {
+                    name: Some(" METADATA_TEST_RENAMED.md ".to_string()),
+                    label: Some("metadata-test".to_string()),
+                    tags: Some(vec!["birds".to_string(), "south".to_string()]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!("METADATA_TEST_RENAMED.md", renamed.name);
+        assert_eq!(Some("metadata-test"), renamed.label.as_deref());
+
+        let empty = documents
+            .update_document(ids[0], DocumentUpdatePayload::default())
+            .await;
+        assert!(empty.is_err());
+
+        let mut updated = documents
+            .update_documents(DocumentBulkUpdatePayload {
+                documents: vec![ids[1]],
+                filter: Some(DocumentFilter {
+                    label: Some("metadata-test".to_string()),
+                    ..Default::default()
+                }),
+                update: DocumentUpdatePayload {
+                    add_tags: vec!["birds".to_string(), "north".to_string()],
+                    remove_tags: vec!["south".to_string()],
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+        updated.sort();
+
+        let mut expected = vec![ids[0], ids[1]];
+        expected.sort();
+        assert_eq!(expected, updated);
+
+        let first = documents.get_document(ids[0]).await.unwrap();
+        assert_eq!(
+            Some(vec!["birds".to_string(), "north".to_string()]),
+            first.tags
+        );
+
+        let rename = documents
+            .update_documents(DocumentBulkUpdatePayload {
+                documents: vec![ids[1]],
+                filter: None,
+                update: DocumentUpdatePayload {
+                    name: Some("METADATA_TEST".to_string()),
+                    ..Default::default()
+                },
+            })
+            .await;
+        assert!(rename.is_err());
+
+        let list = |filter: DocumentFilter| {
+            let documents = documents.clone();
+            let ids = &ids;
+            async move {
+                let mut listed: Vec<_> = documents
+                    .list_documents(
+                        PaginationSort::new_default_sort(Pagination::new(100, 1)),
+                        filter,
+                    )
+                    .await
+                    .unwrap()
+                    .items
+                    .into_iter()
+                    .map(|document| document.id)
+                    .filter(|id| ids.contains(id))
+                    .collect();
+                listed.sort();
+                listed
+            }
+        };
+
+        let tagged = list(DocumentFilter {
+            tags: vec!["north".to_string(), "west".to_string()],
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(expected, tagged);
+
+        let all_tags = list(DocumentFilter {
+            all_tags: vec!["north".to_string(), "west".to_string()],
+            ..Default::default()
+        })
+        .await;
+        assert!(all_tags.is_empty());
+
+        let markdown = list(DocumentFilter {
+            ext: vec!["md".to_string()],
+            all_tags: vec!["birds".to_string(), "north".to_string()],
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(vec![ids[0]], markdown);
+
+        let created = list(DocumentFilter {
+            created_after: Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+            created_before: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(3, created.len());
+
+        let matching = documents
+            .list_document_ids(DocumentFilter {
+                label: Some("metadata-test".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(vec![ids[0]], matching);
+
+        for id in ids {
+            documents.delete(id).await.unwrap();
+        }
+    }
 }
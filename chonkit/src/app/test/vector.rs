@@ -6,13 +6,35 @@
 #[suitest::suite(integration_tests)]
 mod vector_service_integration_tests {
     use crate::{
-        app::test::{TestState, TestStateConfig},
+        app::{
+            clone::clone_collection,
+            eval::run_evaluation,
+            job::{JobKind, JobResult, JobStatus},
+            sweep::sweep_chunk_configs,
+            test::{TestState, TestStateConfig},
+        },
         config::DEFAULT_COLLECTION_NAME,
         core::{
-            model::document::{DocumentInsert, DocumentType, TextDocumentType},
+            chunk::ChunkConfig,
+            eval::{EvalMetric, SweepChunker, SweepGrid},
+            model::{
+                collection::{EmbeddingInsert, ReconciliationReport},
+                document::{DocumentInsert, DocumentType, TextDocumentType},
+            },
             provider::ProviderFactory,
             repo::{document::DocumentRepo, vector::VectorRepo},
-            service::vector::dto::{CreateCollectionPayload, CreateEmbeddings, SearchPayload},
+            service::{
+                document::dto::DocumentUpload,
+                eval::dto::{
+                    CreateEvalDatasetPayload, EvalQuestionPayload, RunEvaluationPayload,
+                    SweepPayload,
+                },
+                vector::dto::{
+                    CloneCollectionPayload, CreateCollectionPayload, CreateEmbeddings,
+                    SearchPayload,
+                },
+            },
+            vector::VectorIndexConfig,
         },
         error::ChonkitErr,
     };
@@ -86,15 +108,19 @@ mod vector_service_integration_tests {
                 .unwrap();
 
             assert_eq!(collection.name, collection_name);
-            assert_eq!(collection.model, embedder.default_model().0);
+            assert_eq!(collection.model, Some(embedder.default_model().0));
             assert_eq!(collection.embedder, embedder.id());
             assert_eq!(collection.provider, *provider);
 
             let v_collection = vector_db.get_collection(&collection_name).await.unwrap();
 
-            let size = embedder.size(&collection.model).await.unwrap().unwrap();
+            let size = embedder
+                .size(collection.model.as_ref().unwrap())
+                .await
+                .unwrap()
+                .unwrap();
 
-            assert_eq!(size, v_collection.size);
+            assert_eq!(Some(size), v_collection.size);
 
             // Assert this can be called again without errors.
             service
@@ -127,24 +153,30 @@ mod vector_service_integration_tests {
                 .unwrap();
 
             let params = CreateCollectionPayload {
-                model: model.0.clone(),
+                model: Some(model.0.clone()),
+                sparse_model: None,
                 name: name.to_string(),
                 vector_provider: vector_db.id().to_string(),
                 embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
             };
 
             let collection = service.create_collection(params).await.unwrap();
 
             assert_eq!(collection.name, name);
-            assert_eq!(collection.model, model.0);
+            assert_eq!(collection.model, Some(model.0));
             assert_eq!(collection.embedder, embedder.id());
             assert_eq!(collection.provider, vector_db.id());
 
             let v_collection = vector_db.get_collection(name).await.unwrap();
 
-            let size = embedder.size(&collection.model).await.unwrap().unwrap();
+            let size = embedder
+                .size(collection.model.as_ref().unwrap())
+                .await
+                .unwrap()
+                .unwrap();
 
-            assert_eq!(size, v_collection.size);
+            assert_eq!(Some(size), v_collection.size);
         }
     }
 
@@ -165,10 +197,12 @@ mod vector_service_integration_tests {
             let name = "Test_collection_0";
 
             let params = CreateCollectionPayload {
-                model: "invalid_model".to_string(),
+                model: Some("invalid_model".to_string()),
+                sparse_model: None,
                 name: name.to_string(),
                 vector_provider: vector_db.id().to_string(),
                 embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
             };
 
             let result = service.create_collection(params).await;
@@ -194,10 +228,12 @@ mod vector_service_integration_tests {
                 format!("{DEFAULT_COLLECTION_NAME}_{}_{}", provider, embedder.id());
 
             let params = CreateCollectionPayload {
-                model: embedder.default_model().0,
+                model: Some(embedder.default_model().0),
+                sparse_model: None,
                 name: collection_name,
                 vector_provider: vector_db.id().to_string(),
                 embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
             };
 
             let result = service.create_collection(params).await;
@@ -244,6 +280,7 @@ mod vector_service_integration_tests {
                 document_id: document.id,
                 collection_id: default.id,
                 chunks: &[content],
+                chunk_ids: None,
             };
 
             let collection = service
@@ -257,8 +294,17 @@ mod vector_service_integration_tests {
                 query: content.to_string(),
                 collection_id: Some(collection.id),
                 limit: Some(1),
+                alias: None,
                 collection_name: None,
                 provider: None,
+                collections: vec![],
+                aliases: vec![],
+                vector: None,
+                rescore: None,
+                oversampling: None,
+                mmr_lambda: None,
+                max_per_document: None,
+                candidates: None,
             };
 
             let results = service.search(search).await.unwrap();
@@ -305,9 +351,11 @@ mod vector_service_integration_tests {
 
             let create = CreateCollectionPayload {
                 name: collection_name.to_string(),
-                model: embedder.default_model().0,
+                model: Some(embedder.default_model().0),
+                sparse_model: None,
                 vector_provider: vector_db.id().to_string(),
                 embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
             };
 
             let collection = service.create_collection(create).await.unwrap();
@@ -328,6 +376,7 @@ mod vector_service_integration_tests {
                 document_id: document.id,
                 collection_id: collection.id,
                 chunks: &[content],
+                chunk_ids: None,
             };
 
             service.create_embeddings(embeddings).await.unwrap();
@@ -383,6 +432,7 @@ mod vector_service_integration_tests {
                 document_id: document.id,
                 collection_id: default.id,
                 chunks: &[content],
+                chunk_ids: None,
             };
 
             service.create_embeddings(create.clone()).await.unwrap();
@@ -396,4 +446,734 @@ mod vector_service_integration_tests {
             assert_eq!(1, amount);
         }
     }
+
+    #[test]
+    async fn reconcile_reports_and_deletes_orphans(state: TestState) {
+        let service = &state.app.services.vector;
+        let postgres = &state.app.providers.database;
+        let embedder = state
+            .app
+            .providers
+            .embedding
+            .get_provider("fembed")
+            .unwrap()
+            .clone();
+
+        for provider in state.active_vector_providers.iter() {
+            let vector_db = state.app.providers.vector.get_provider(provider).unwrap();
+
+            let create = CreateCollectionPayload {
+                name: "Test_collection_reconcile".to_string(),
+                model: Some(embedder.default_model().0),
+                sparse_model: None,
+                vector_provider: vector_db.id().to_string(),
+                embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
+            };
+
+            let collection = service.create_collection(create).await.unwrap();
+
+            // Vectors whose embedding entry is missing
+            let untracked = postgres
+                .insert(DocumentInsert::new(
+                    "test_document",
+                    "test_path_reconcile_0",
+                    DocumentType::Text(TextDocumentType::Txt),
+                    "SHA256_reconcile_0",
+                    "fs",
+                ))
+                .await
+                .unwrap();
+
+            // Vectors whose document is deleted
+            let deleted = postgres
+                .insert(DocumentInsert::new(
+                    "test_document",
+                    "test_path_reconcile_1",
+                    DocumentType::Text(TextDocumentType::Txt),
+                    "SHA256_reconcile_1",
+                    "fs",
+                ))
+                .await
+                .unwrap();
+
+            // Embedding entry without vectors
+            let missing = postgres
+                .insert(DocumentInsert::new(
+                    "test_document",
+                    "test_path_reconcile_2",
+                    DocumentType::Text(TextDocumentType::Txt),
+                    "SHA256_reconcile_2",
+                    "fs",
+                ))
+                .await
+                .unwrap();
+
+            for document_id in [untracked.id, deleted.id] {
+                service
+                    .create_embeddings(CreateEmbeddings {
+                        document_id,
+                        collection_id: collection.id,
+                        chunks: &["Hello", "World"],
+                        chunk_ids: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+
+            postgres
+                .delete_embeddings(untracked.id, collection.id)
+                .await
+                .unwrap();
+            postgres.remove_by_id(deleted.id, None).await.unwrap();
+            postgres
+                .insert_embeddings(EmbeddingInsert::new(missing.id, collection.id))
+                .await
+                .unwrap();
+
+            let mut report = service.reconcile().await.unwrap();
+
+            let mut orphans: Vec<_> = report
+                .orphaned_vectors
+                .iter()
+                .filter(|orphan| orphan.collection_id == collection.id)
+                .map(|orphan| (orphan.document_id, orphan.count, orphan.document_exists))
+                .collect();
+            orphans.sort_by_key(|(_, _, exists)| *exists);
+
+            assert_eq!(
+                vec![(deleted.id, 2, false), (untracked.id, 2, true)],
+                orphans
+            );
+
+            assert!(report
+                .missing_vectors
+                .iter()
+                .any(|e| e.document_id == missing.id && e.collection_id == collection.id));
+
+            service.delete_orphans(&mut report).await;
+
+            // Vectors of existing documents are only reported
+            let count = service
+                .count_embeddings(collection.id, untracked.id)
+                .await
+                .unwrap();
+            assert_eq!(2, count);
+
+            let count = service
+                .count_embeddings(collection.id, deleted.id)
+                .await
+                .unwrap();
+            assert_eq!(0, count);
+
+            // Vector collections missing from the repository are only reported
+            postgres.delete_collection(collection.id).await.unwrap();
+
+            let is_orphaned = |report: &ReconciliationReport| {
+                report
+                    .orphaned_collections
+                    .iter()
+                    .any(|c| c.name == collection.name && c.provider == collection.provider)
+            };
+
+            let mut report = service.reconcile().await.unwrap();
+            assert!(is_orphaned(&report));
+
+            service.delete_orphans(&mut report).await;
+
+            let report = service.reconcile().await.unwrap();
+            assert!(is_orphaned(&report));
+
+            service
+                .delete_orphaned_collection(&collection.provider, &collection.name)
+                .await
+                .unwrap();
+
+            let report = service.reconcile().await.unwrap();
+            assert!(!is_orphaned(&report));
+
+            postgres.remove_by_id(untracked.id, None).await.unwrap();
+            postgres.remove_by_id(missing.id, None).await.unwrap();
+        }
+    }
+
+    #[test]
+    async fn clone_collection_reembeds_documents(state: TestState) {
+        let service = &state.app.services.vector;
+        let embedder = state
+            .app
+            .providers
+            .embedding
+            .get_provider("fembed")
+            .unwrap()
+            .clone();
+
+        let document = state
+            .app
+            .services
+            .document
+            .upload(
+                "fs",
+                DocumentUpload {
+                    name: "CLONE_TEST_TXT".to_string(),
+                    ty: DocumentType::Text(TextDocumentType::Txt),
+                    file: b"Hello world, this is a document to clone.",
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        for provider in state.active_vector_providers.iter() {
+            let vector_db = state.app.providers.vector.get_provider(provider).unwrap();
+
+            let create = CreateCollectionPayload {
+                name: "Test_collection_clone_source".to_string(),
+                model: Some(embedder.default_model().0),
+                sparse_model: None,
+                vector_provider: vector_db.id().to_string(),
+                embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
+            };
+
+            let source = service.create_collection(create).await.unwrap();
+
+            state
+                .app
+                .services
+                .embed_document(document.id, source.id)
+                .await
+                .unwrap();
+
+            let payload = CloneCollectionPayload {
+                name: "Test_collection_clone_target".to_string(),
+                model: None,
+                sparse_model: None,
+                embedding_provider: None,
+                vector_provider: None,
+                index_config: None,
+                alias: Some("test_clone_alias".to_string()),
+            };
+
+            let job = clone_collection(&state.app.services, &state.app.jobs, source.id, payload)
+                .await
+                .unwrap();
+
+            assert_eq!(1, job.total);
+
+            let job = loop {
+                let job = state.app.jobs.get(job.id).unwrap();
+                if job.status != JobStatus::Running {
+                    break job;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            };
+
+            assert_eq!(JobStatus::Finished, job.status);
+            assert_eq!(1, job.processed);
+            assert_eq!(0, job.failed);
+
+            let JobKind::Clone {
+                target_collection, ..
+            } = job.kind
+            else {
+                panic!("expected a clone job");
+            };
+
+            let target = service.get_collection(target_collection).await.unwrap();
+            assert_eq!(source.model, target.model);
+            assert_eq!(source.provider, target.provider);
+
+            let count = service
+                .count_embeddings(target.id, document.id)
+                .await
+                .unwrap();
+            assert!(count > 0);
+
+            let alias = state
+                .app
+                .providers
+                .database
+                .get_alias("test_clone_alias")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(target.id, alias.collection_id);
+
+            service.delete_collection(target.id).await.unwrap();
+            service.delete_collection(source.id).await.unwrap();
+        }
+
+        state
+            .app
+            .services
+            .document
+            .delete(document.id)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    async fn alias_swaps_and_resolves(state: TestState) {
+        let service = &state.app.services.vector;
+        let embedder = state
+            .app
+            .providers
+            .embedding
+            .get_provider("fembed")
+            .unwrap()
+            .clone();
+
+        let mut collections = vec![];
+
+        for provider in state.active_vector_providers.iter() {
+            let vector_db = state.app.providers.vector.get_provider(provider).unwrap();
+
+            for (i, content) in ["Alias first", "Alias second"].into_iter().enumerate() {
+                let create = CreateCollectionPayload {
+                    name: format!("Test_collection_alias_{i}"),
+                    model: Some(embedder.default_model().0),
+                    sparse_model: None,
+                    vector_provider: vector_db.id().to_string(),
+                    embedding_provider: embedder.id().to_string(),
+                    index_config: VectorIndexConfig::default(),
+                };
+
+                let collection = service.create_collection(create).await.unwrap();
+
+                vector_db
+                    .insert_embeddings(
+                        uuid::Uuid::new_v4(),
+                        &collection.name,
+                        &[content],
+                        None,
+                        crate::core::vector::Vectors {
+                            dense: Some(
+                                embedder
+                                    .embed(&[content], &embedder.default_model().0)
+                                    .await
+                                    .unwrap(),
+                            ),
+                            sparse: None,
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                collections.push((collection, content));
+            }
+        }
+
+        let search = |alias: &str| SearchPayload {
+            query: "Alias".to_string(),
+            collection_id: None,
+            limit: Some(1),
+            alias: Some(alias.to_string()),
+            collection_name: None,
+            provider: None,
+            collections: vec![],
+            aliases: vec![],
+            vector: None,
+            rescore: None,
+            oversampling: None,
+            mmr_lambda: None,
+            max_per_document: None,
+            candidates: None,
+        };
+
+        // Swapping across collections of all providers
+        for (collection, content) in collections.iter() {
+            let alias = service
+                .swap_alias("test_alias", collection.id)
+                .await
+                .unwrap();
+            assert_eq!(collection.id, alias.collection_id);
+
+            let results = service.search(search("test_alias")).await.unwrap();
+            assert_eq!(vec![content.to_string()], results);
+        }
+
+        let invalid = service
+            .swap_alias("not valid!", collections[0].0.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(invalid.error, ChonkitErr::InvalidAlias(_)));
+
+        service.delete_alias("test_alias").await.unwrap();
+
+        let missing = service.search(search("test_alias")).await.unwrap_err();
+        assert!(matches!(missing.error, ChonkitErr::DoesNotExist(_)));
+
+        for (collection, _) in collections {
+            service.delete_collection(collection.id).await.unwrap();
+        }
+    }
+
+    #[test]
+    async fn federated_search_fuses_collections(state: TestState) {
+        let service = &state.app.services.vector;
+        let embedder = state
+            .app
+            .providers
+            .embedding
+            .get_provider("fembed")
+            .unwrap()
+            .clone();
+
+        let mut collections = vec![];
+
+        for provider in state.active_vector_providers.iter() {
+            let vector_db = state.app.providers.vector.get_provider(provider).unwrap();
+
+            for (i, content) in ["Federated first", "Federated second"]
+                .into_iter()
+                .enumerate()
+            {
+                let create = CreateCollectionPayload {
+                    name: format!("Test_collection_federated_{i}"),
+                    model: Some(embedder.default_model().0),
+                    sparse_model: None,
+                    vector_provider: vector_db.id().to_string(),
+                    embedding_provider: embedder.id().to_string(),
+                    index_config: VectorIndexConfig::default(),
+                };
+
+                let collection = service.create_collection(create).await.unwrap();
+
+                vector_db
+                    .insert_embeddings(
+                        uuid::Uuid::new_v4(),
+                        &collection.name,
+                        &[content],
+                        None,
+                        crate::core::vector::Vectors {
+                            dense: Some(
+                                embedder
+                                    .embed(&[content], &embedder.default_model().0)
+                                    .await
+                                    .unwrap(),
+                            ),
+                            sparse: None,
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                collections.push(collection);
+            }
+        }
+
+        let search = SearchPayload {
+            query: "Federated".to_string(),
+            collection_id: None,
+            limit: Some(10),
+            alias: None,
+            collection_name: None,
+            provider: None,
+            collections: collections.iter().map(|c| c.id).collect(),
+            aliases: vec![],
+            vector: None,
+            rescore: None,
+            oversampling: None,
+            mmr_lambda: None,
+            max_per_document: None,
+            candidates: None,
+        };
+
+        assert!(search.is_federated());
+
+        let hits = service.federated_search(search).await.unwrap();
+
+        assert_eq!(collections.len(), hits.len());
+
+        for collection in collections.iter() {
+            assert!(hits.iter().any(|hit| hit.collection_id == collection.id
+                && hit.collection_name == collection.name
+                && hit.provider == collection.provider));
+        }
+
+        for hit in hits.iter() {
+            // Every collection contains a single hit
+            assert_eq!(1., hit.normalized_score);
+            assert_eq!(1. / (crate::core::search::RRF_K + 1.), hit.rrf_score);
+        }
+
+        for collection in collections {
+            service.delete_collection(collection.id).await.unwrap();
+        }
+    }
+
+    #[test]
+    async fn evaluation_scores_collections(state: TestState) {
+        let service = &state.app.services.vector;
+        let embedder = state
+            .app
+            .providers
+            .embedding
+            .get_provider("fembed")
+            .unwrap()
+            .clone();
+
+        let expected_document = uuid::Uuid::new_v4();
+
+        let dataset = state
+            .app
+            .services
+            .eval
+            .create_dataset(CreateEvalDatasetPayload {
+                name: "Test_dataset".to_string(),
+                questions: vec![EvalQuestionPayload {
+                    question: "Where do penguins live?".to_string(),
+                    documents: vec![expected_document],
+                    spans: vec![],
+                }],
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(1, dataset.questions.len());
+
+        for provider in state.active_vector_providers.iter() {
+            let vector_db = state.app.providers.vector.get_provider(provider).unwrap();
+
+            let create = CreateCollectionPayload {
+                name: "Test_collection_eval".to_string(),
+                model: Some(embedder.default_model().0),
+                sparse_model: None,
+                vector_provider: vector_db.id().to_string(),
+                embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
+            };
+
+            let collection = service.create_collection(create).await.unwrap();
+
+            for (document_id, content) in [
+                (
+                    expected_document,
+                    "Penguins live in the southern hemisphere.",
+                ),
+                (uuid::Uuid::new_v4(), "Compilers translate source code."),
+            ] {
+                vector_db
+                    .insert_embeddings(
+                        document_id,
+                        &collection.name,
+                        &[content],
+                        None,
+                        crate::core::vector::Vectors {
+                            dense: Some(
+                                embedder
+                                    .embed(&[content], &embedder.default_model().0)
+                                    .await
+                                    .unwrap(),
+                            ),
+                            sparse: None,
+                        },
+                    )
+                    .await
+                    .unwrap();
+            }
+
+            let run = run_evaluation(
+                &state.app.services,
+                dataset.dataset.id,
+                RunEvaluationPayload {
+                    collection_id: collection.id,
+                    k: Some(1),
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(Some(collection.id), run.collection_id);
+            assert_eq!(1, run.k);
+            assert_eq!(1., run.metrics.recall);
+            assert_eq!(1., run.metrics.mrr);
+            assert_eq!(1, run.questions.len());
+
+            let stored = state.app.services.eval.get_run(run.id).await.unwrap();
+            assert_eq!(run.metrics, stored.metrics);
+
+            service.delete_collection(collection.id).await.unwrap();
+
+            // Runs are kept after their collection is deleted
+            let stored = state.app.services.eval.get_run(run.id).await.unwrap();
+            assert_eq!(None, stored.collection_id);
+        }
+
+        let runs = state
+            .app
+            .services
+            .eval
+            .list_runs(dataset.dataset.id)
+            .await
+            .unwrap();
+        assert_eq!(state.active_vector_providers.len(), runs.len());
+
+        state
+            .app
+            .services
+            .eval
+            .delete_dataset(dataset.dataset.id)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    async fn sweep_evaluates_and_applies_chunk_configs(state: TestState) {
+        let service = &state.app.services.vector;
+        let embedder = state
+            .app
+            .providers
+            .embedding
+            .get_provider("fembed")
+            .unwrap()
+            .clone();
+
+        let document = state
+            .app
+            .services
+            .document
+            .upload(
+                "fs",
+                DocumentUpload {
+                    name: "SWEEP_TEST_TXT".to_string(),
+                    ty: DocumentType::Text(TextDocumentType::Txt),
+                    file: b"Penguins live in the southern hemisphere. Compilers translate source code.",
+                    label: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let dataset = state
+            .app
+            .services
+            .eval
+            .create_dataset(CreateEvalDatasetPayload {
+                name: "Test_dataset_sweep".to_string(),
+                questions: vec![EvalQuestionPayload {
+                    question: "Where do penguins live?".to_string(),
+                    documents: vec![],
+                    spans: vec!["southern hemisphere".to_string()],
+                }],
+            })
+            .await
+            .unwrap();
+
+        for provider in state.active_vector_providers.iter() {
+            let vector_db = state.app.providers.vector.get_provider(provider).unwrap();
+
+            let create = CreateCollectionPayload {
+                name: "Test_collection_sweep".to_string(),
+                model: Some(embedder.default_model().0),
+                sparse_model: None,
+                vector_provider: vector_db.id().to_string(),
+                embedding_provider: embedder.id().to_string(),
+                index_config: VectorIndexConfig::default(),
+            };
+
+            let template = service.create_collection(create).await.unwrap();
+
+            let payload = SweepPayload {
+                collection_id: template.id,
+                documents: vec![document.id],
+                grid: SweepGrid {
+                    chunkers: vec![SweepChunker::Sliding],
+                    sizes: vec![30, 1000],
+                    overlaps: vec![0],
+                    thresholds: vec![],
+                },
+                k: Some(1),
+                metric: EvalMetric::Recall,
+                max_chunks: None,
+                max_duration: None,
+                apply: true,
+            };
+
+            let job = sweep_chunk_configs(
+                &state.app.services,
+                &state.app.jobs,
+                dataset.dataset.id,
+                payload,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(2, job.total);
+
+            let job = loop {
+                let job = state.app.jobs.get(job.id).unwrap();
+                if job.status != JobStatus::Running {
+                    break job;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            };
+
+            assert_eq!(JobStatus::Finished, job.status, "{:?}", job.errors);
+            assert_eq!(2, job.processed);
+
+            let Some(JobResult::Sweep(report)) = job.result else {
+                panic!("expected a sweep report");
+            };
+
+            assert!(report.applied);
+            assert!(report.candidates.iter().all(|c| c.run_id.is_some()));
+
+            let best = &report.candidates[report.best.unwrap()];
+            assert_eq!(Some(1.), best.metrics.map(|m| m.recall));
+
+            let config = state
+                .app
+                .services
+                .document
+                .get_config(document.id)
+                .await
+                .unwrap();
+            let (Some(ChunkConfig::Sliding(applied)), ChunkConfig::Sliding(best)) =
+                (config.chunk_config, &best.config)
+            else {
+                panic!("expected sliding configs");
+            };
+            assert_eq!(best.size, applied.size);
+
+            // Only the template collection is kept
+            let names: Vec<String> = service
+                .list_collections(Default::default())
+                .await
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|collection| collection.name)
+                .collect();
+            assert!(!names.iter().any(|name| name.starts_with("Sweep_")));
+
+            service.delete_collection(template.id).await.unwrap();
+        }
+
+        let runs = state
+            .app
+            .services
+            .eval
+            .list_runs(dataset.dataset.id)
+            .await
+            .unwrap();
+        assert_eq!(2 * state.active_vector_providers.len(), runs.len());
+
+        state
+            .app
+            .services
+            .eval
+            .delete_dataset(dataset.dataset.id)
+            .await
+            .unwrap();
+
+        state
+            .app
+            .services
+            .document
+            .delete(document.id)
+            .await
+            .unwrap();
+    }
 }
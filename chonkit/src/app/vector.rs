@@ -3,7 +3,7 @@ use crate::{
         DEFAULT_COLLECTION_EMBEDDING_MODEL, DEFAULT_COLLECTION_EMBEDDING_PROVIDER,
         DEFAULT_COLLECTION_ID, DEFAULT_COLLECTION_NAME, DEFAULT_COLLECTION_SIZE,
     },
-    core::vector::CreateVectorCollection,
+    core::vector::{CreateVectorCollection, VectorIndexConfig},
 };
 
 impl Default for CreateVectorCollection<'static> {
@@ -11,9 +11,11 @@ impl Default for CreateVectorCollection<'static> {
         Self {
             collection_id: DEFAULT_COLLECTION_ID,
             name: DEFAULT_COLLECTION_NAME,
-            size: DEFAULT_COLLECTION_SIZE,
+            size: Some(DEFAULT_COLLECTION_SIZE),
             embedding_provider: DEFAULT_COLLECTION_EMBEDDING_PROVIDER,
-            embedding_model: DEFAULT_COLLECTION_EMBEDDING_MODEL,
+            embedding_model: Some(DEFAULT_COLLECTION_EMBEDDING_MODEL),
+            sparse_model: None,
+            index_config: VectorIndexConfig::default(),
         }
     }
 }
@@ -23,3 +25,6 @@ pub mod qdrant;
 
 #[cfg(feature = "weaviate")]
 pub mod weaviate;
+
+#[cfg(feature = "pg-vectors")]
+pub mod postgres;
@@ -0,0 +1,586 @@
+use crate::core::embedder::SparseEmbedding;
+use crate::core::model::{List, Pagination};
+use crate::core::vector::{
+    CreateVectorCollection, Distance, PointPage, QuantizationSearch, QueryVector, SearchHit,
+    StoredChunk, StoredPoint, VectorCollection, VectorDb, Vectors,
+};
+use crate::error::ChonkitError;
+use crate::{err, map_err};
+use sqlx::{prelude::FromRow, PgPool, Postgres, QueryBuilder};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Maximum amount of vectors inserted in a single query, keeping the amount of
+/// bound parameters below the Postgres limit.
+const INSERT_BATCH_SIZE: usize = 1000;
+
+// Values of the `distance` column.
+
+const DISTANCE_COSINE: &str = "cosine";
+const DISTANCE_DOT: &str = "dot";
+const DISTANCE_EUCLIDEAN: &str = "euclidean";
+const DISTANCE_MANHATTAN: &str = "manhattan";
+
+/// Alias for an arced Postgres vector storage instance.
+pub type PgVectorDb = Arc<PgVectors>;
+
+/// Vector storage in the application's Postgres database.
+///
+/// Supports both dense and sparse vectors. Vectors are stored as arrays and searched
+/// by brute force, making this provider suitable for smaller collections and for
+/// collections holding sparse vectors when the other providers do not support them.
+#[derive(Debug, Clone)]
+pub struct PgVectors {
+    pool: PgPool,
+}
+
+pub fn init(pool: PgPool) -> PgVectorDb {
+    info!("Using postgres for vector storage");
+    Arc::new(PgVectors { pool })
+}
+
+#[async_trait::async_trait]
+impl VectorDb for PgVectors {
+    fn id(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn list_vector_collections(&self) -> Result<Vec<VectorCollection>, ChonkitError> {
+        let collections = map_err!(
+            sqlx::query_as!(
+                VectorCollectionRow,
+                "SELECT collection_id, name, size, embedding_provider, embedding_model, sparse_model
+                 FROM vector_collections",
+            )
+            .fetch_all(&self.pool)
+            .await
+        );
+
+        Ok(collections
+            .into_iter()
+            .map(VectorCollection::from)
+            .collect())
+    }
+
+    async fn create_vector_collection(
+        &self,
+        data: CreateVectorCollection<'_>,
+    ) -> Result<(), ChonkitError> {
+        let name = data.name;
+
+        if data.index_config.quantization.is_some() {
+            return err!(
+                InvalidProvider,
+                "Quantization is not supported by the postgres vector provider"
+            );
+        }
+
+        match insert_collection(&self.pool, data, false).await {
+            Err(sqlx::Error::Database(e)) if e.code().is_some_and(|code| code == "23505") => {
+                err!(AlreadyExists, "Vector collection '{name}'")
+            }
+            result => {
+                map_err!(result);
+                Ok(())
+            }
+        }
+    }
+
+    async fn get_collection(&self, name: &str) -> Result<VectorCollection, ChonkitError> {
+        let collection = map_err!(
+            sqlx::query_as!(
+                VectorCollectionRow,
+                "SELECT collection_id, name, size, embedding_provider, embedding_model, sparse_model
+                 FROM vector_collections
+                 WHERE name = $1",
+                name
+            )
+            .fetch_optional(&self.pool)
+            .await
+        );
+
+        match collection {
+            Some(collection) => Ok(collection.into()),
+            None => err!(DoesNotExist, "Vector collection '{name}'"),
+        }
+    }
+
+    async fn delete_vector_collection(&self, name: &str) -> Result<(), ChonkitError> {
+        map_err!(
+            sqlx::query!("DELETE FROM vector_collections WHERE name = $1", name)
+                .execute(&self.pool)
+                .await
+        );
+        Ok(())
+    }
+
+    async fn create_default_collection(
+        &self,
+        data: CreateVectorCollection<'_>,
+    ) -> Result<(), ChonkitError> {
+        map_err!(insert_collection(&self.pool, data, true).await);
+        Ok(())
+    }
+
+    async fn query(
+        &self,
+        search: QueryVector,
+        collection: &str,
+        limit: u32,
+        _quantization: QuantizationSearch,
+        with_vectors: bool,
+    ) -> Result<Vec<SearchHit>, ChonkitError> {
+        let sparse = matches!(search, QueryVector::Sparse(_));
+
+        let results: Vec<SearchHitRow> = match search {
+            QueryVector::Dense(vector) => {
+                let distance = map_err!(
+                    sqlx::query_scalar!(
+                        "SELECT distance FROM vector_collections WHERE name = $1",
+                        collection
+                    )
+                    .fetch_one(&self.pool)
+                    .await
+                );
+
+                let query = format!(
+                    r#"
+                    SELECT content, score, document_id, chunk_index, chunk_id, dense, sparse_indices, sparse_values FROM (
+                        SELECT content, document_id, chunk_index, chunk_id, (
+                            SELECT ({score})::FLOAT8
+                            FROM UNNEST(dense, $2::REAL[]) AS v(a, b)
+                        ) AS score,
+                        CASE WHEN $4 THEN dense END AS dense,
+                        NULL::INT[] AS sparse_indices,
+                        NULL::REAL[] AS sparse_values
+                        FROM vectors
+                        WHERE collection = $1 AND dense IS NOT NULL
+                    ) AS scored
+                    ORDER BY score DESC NULLS LAST
+                    LIMIT $3
+                    "#,
+                    score = dense_score(&distance)
+                );
+
+                map_err!(
+                    sqlx::query_as(&query)
+                        .bind(collection)
+                        .bind(vector.into_iter().map(|x| x as f32).collect::<Vec<_>>())
+                        .bind(limit as i64)
+                        .bind(with_vectors)
+                        .fetch_all(&self.pool)
+                        .await
+                )
+            }
+
+            // Dot product of the dimensions present in both vectors
+            QueryVector::Sparse(SparseEmbedding { indices, values }) => map_err!(
+                sqlx::query_as!(
+                    SearchHitRow,
+                    r#"
+                    SELECT
+                        content AS "content!",
+                        score,
+                        document_id AS "document_id!",
+                        chunk_index,
+                        chunk_id,
+                        dense AS "dense: Vec<f32>",
+                        sparse_indices AS "sparse_indices: Vec<i32>",
+                        sparse_values AS "sparse_values: Vec<f32>"
+                    FROM (
+                        SELECT content, document_id, chunk_index, chunk_id, (
+                            SELECT SUM(d.value * q.value)::FLOAT8
+                            FROM UNNEST(sparse_indices, sparse_values) AS d(idx, value)
+                            JOIN UNNEST($2::INT[], $3::REAL[]) AS q(idx, value) ON d.idx = q.idx
+                        ) AS score,
+                        NULL::REAL[] AS dense,
+                        CASE WHEN $5 THEN sparse_indices END AS sparse_indices,
+                        CASE WHEN $5 THEN sparse_values END AS sparse_values
+                        FROM vectors
+                        WHERE collection = $1 AND sparse_indices IS NOT NULL
+                    ) AS scored
+                    WHERE score IS NOT NULL
+                    ORDER BY score DESC
+                    LIMIT $4
+                    "#,
+                    collection,
+                    &to_pg_indices(indices),
+                    &values,
+                    limit as i64,
+                    with_vectors
+                )
+                .fetch_all(&self.pool)
+                .await
+            ),
+        };
+
+        Ok(results
+            .into_iter()
+            .map(|row| {
+                let vector = match (row.dense, row.sparse_indices, row.sparse_values) {
+                    (Some(dense), _, _) if !sparse => Some(QueryVector::Dense(
+                        dense.into_iter().map(f64::from).collect(),
+                    )),
+                    (_, Some(indices), Some(values)) if sparse => {
+                        Some(QueryVector::Sparse(SparseEmbedding {
+                            indices: indices.into_iter().map(|i| i as u32).collect(),
+                            values,
+                        }))
+                    }
+                    _ => None,
+                };
+                SearchHit {
+                    content: row.content,
+                    score: row.score.unwrap_or(f64::MIN),
+                    document_id: Some(row.document_id),
+                    chunk_index: row.chunk_index.map(|index| index as usize),
+                    chunk_id: row.chunk_id,
+                    vector,
+                }
+            })
+            .collect())
+    }
+
+    async fn insert_embeddings(
+        &self,
+        document_id: Uuid,
+        collection: &str,
+        content: &[&str],
+        chunk_ids: Option<&[Uuid]>,
+        vectors: Vectors,
+    ) -> Result<(), ChonkitError> {
+        debug!("Inserting vectors to {collection}");
+
+        let Vectors { dense, sparse } = vectors;
+
+        debug_assert!(
+            dense.as_ref().is_none_or(|d| d.len() == content.len())
+                && sparse.as_ref().is_none_or(|s| s.len() == content.len()),
+            "Content length is different from embeddings!"
+        );
+
+        let mut dense = dense.map(Vec::into_iter);
+        let mut sparse = sparse.map(Vec::into_iter);
+
+        let mut tx = map_err!(self.pool.begin().await);
+
+        for (batch_index, batch) in content.chunks(INSERT_BATCH_SIZE).enumerate() {
+            let mut query = QueryBuilder::<Postgres>::new(
+                "INSERT INTO vectors(collection, document_id, chunk_index, chunk_id, content, dense, sparse_indices, sparse_values) ",
+            );
+
+            let offset = batch_index * INSERT_BATCH_SIZE;
+
+            query.push_values(batch.iter().enumerate(), |mut row, (index, content)| {
+                let dense = dense
+                    .as_mut()
+                    .and_then(Iterator::next)
+                    .map(|dense| dense.into_iter().map(|x| x as f32).collect::<Vec<_>>());
+
+                let (indices, values) = match sparse.as_mut().and_then(Iterator::next) {
+                    Some(SparseEmbedding { indices, values }) => {
+                        (Some(to_pg_indices(indices)), Some(values))
+                    }
+                    None => (None, None),
+                };
+
+                let chunk_id = chunk_ids.and_then(|ids| ids.get(offset + index)).copied();
+
+                row.push_bind(collection)
+                    .push_bind(document_id)
+                    .push_bind((offset + index) as i32)
+                    .push_bind(chunk_id)
+                    .push_bind(*content)
+                    .push_bind(dense)
+                    .push_bind(indices)
+                    .push_bind(values);
+            });
+
+            map_err!(query.build().execute(&mut *tx).await);
+        }
+
+        map_err!(tx.commit().await);
+
+        Ok(())
+    }
+
+    async fn delete_embeddings(
+        &self,
+        collection: &str,
+        document_id: Uuid,
+    ) -> Result<(), ChonkitError> {
+        map_err!(
+            sqlx::query!(
+                "DELETE FROM vectors WHERE collection = $1 AND document_id = $2",
+                collection,
+                document_id
+            )
+            .execute(&self.pool)
+            .await
+        );
+        Ok(())
+    }
+
+    async fn count_vectors(
+        &self,
+        collection: &str,
+        document_id: Uuid,
+    ) -> Result<usize, ChonkitError> {
+        let count = map_err!(
+            sqlx::query_scalar!(
+                r#"SELECT COUNT(*) AS "count!" FROM vectors WHERE collection = $1 AND document_id = $2"#,
+                collection,
+                document_id
+            )
+            .fetch_one(&self.pool)
+            .await
+        );
+        Ok(count as usize)
+    }
+
+    async fn count_vectors_by_document(
+        &self,
+        collection: &str,
+    ) -> Result<HashMap<Uuid, usize>, ChonkitError> {
+        let counts = map_err!(
+            sqlx::query!(
+                r#"SELECT document_id, COUNT(*) AS "count!" FROM vectors WHERE collection = $1 GROUP BY document_id"#,
+                collection
+            )
+            .fetch_all(&self.pool)
+            .await
+        );
+        Ok(counts
+            .into_iter()
+            .map(|row| (row.document_id, row.count as usize))
+            .collect())
+    }
+
+    async fn scroll(
+        &self,
+        collection: &str,
+        offset: Option<String>,
+        limit: u32,
+    ) -> Result<PointPage, ChonkitError> {
+        let offset = match offset {
+            Some(offset) => Some(map_err!(offset.parse::<Uuid>())),
+            None => None,
+        };
+
+        let points = map_err!(
+            sqlx::query_as!(
+                StoredPointRow,
+                "SELECT id, document_id, chunk_index, content, dense, sparse_indices, sparse_values
+                 FROM vectors
+                 WHERE collection = $1 AND ($2::UUID IS NULL OR id > $2)
+                 ORDER BY id
+                 LIMIT $3",
+                collection,
+                offset,
+                limit as i64
+            )
+            .fetch_all(&self.pool)
+            .await
+        );
+
+        let next_offset = if points.len() == limit as usize {
+            points.last().map(|point| point.id.to_string())
+        } else {
+            None
+        };
+
+        Ok(PointPage {
+            points: points.into_iter().map(StoredPoint::from).collect(),
+            next_offset,
+        })
+    }
+
+    async fn list_chunks(
+        &self,
+        collection: &str,
+        document_id: Uuid,
+        pagination: Pagination,
+    ) -> Result<List<StoredChunk>, ChonkitError> {
+        let total = self.count_vectors(collection, document_id).await?;
+
+        let (limit, offset) = pagination.to_limit_offset();
+
+        let chunks = map_err!(
+            sqlx::query_as!(
+                StoredChunkRow,
+                "SELECT id, chunk_index, content FROM vectors
+                 WHERE collection = $1 AND document_id = $2
+                 ORDER BY chunk_index ASC NULLS LAST, id
+                 LIMIT $3 OFFSET $4",
+                collection,
+                document_id,
+                limit,
+                offset
+            )
+            .fetch_all(&self.pool)
+            .await
+        );
+
+        Ok(List::new(
+            Some(total),
+            chunks.into_iter().map(StoredChunk::from).collect(),
+        ))
+    }
+
+    async fn set_alias(&self, _alias: &str, _collection: &str) -> Result<(), ChonkitError> {
+        // Aliases are stored in the same database and resolved by chonkit.
+        Ok(())
+    }
+
+    async fn delete_alias(&self, _alias: &str) -> Result<(), ChonkitError> {
+        Ok(())
+    }
+}
+
+async fn insert_collection(
+    pool: &PgPool,
+    data: CreateVectorCollection<'_>,
+    ignore_existing: bool,
+) -> Result<(), sqlx::Error> {
+    let CreateVectorCollection {
+        collection_id,
+        name,
+        size,
+        embedding_provider,
+        embedding_model,
+        sparse_model,
+        index_config,
+    } = data;
+
+    let distance = match index_config.distance {
+        Distance::Cosine => DISTANCE_COSINE,
+        Distance::Dot => DISTANCE_DOT,
+        Distance::Euclidean => DISTANCE_EUCLIDEAN,
+        Distance::Manhattan => DISTANCE_MANHATTAN,
+    };
+
+    let mut query = QueryBuilder::<Postgres>::new(
+        "INSERT INTO vector_collections(collection_id, name, size, embedding_provider, embedding_model, sparse_model, distance) ",
+    );
+
+    query.push_values([()], |mut row, _| {
+        row.push_bind(collection_id)
+            .push_bind(name)
+            .push_bind(size.map(|size| size as i32))
+            .push_bind(embedding_provider)
+            .push_bind(embedding_model)
+            .push_bind(sparse_model)
+            .push_bind(distance);
+    });
+
+    if ignore_existing {
+        query.push(" ON CONFLICT(name) DO NOTHING");
+    }
+
+    query.build().execute(pool).await?;
+
+    Ok(())
+}
+
+/// SQL expression scoring the dense vector `a` against the query vector `b`
+/// with the given distance metric. Higher scores are always better.
+fn dense_score(distance: &str) -> &'static str {
+    match distance {
+        DISTANCE_DOT => "SUM(a * b)",
+        DISTANCE_EUCLIDEAN => "-SQRT(SUM((a - b) * (a - b)))",
+        DISTANCE_MANHATTAN => "-SUM(ABS(a - b))",
+        _ => "SUM(a * b) / NULLIF(SQRT(SUM(a * a)) * SQRT(SUM(b * b)), 0)",
+    }
+}
+
+/// Sparse model vocabularies are well below the range of `INT`.
+fn to_pg_indices(indices: Vec<u32>) -> Vec<i32> {
+    indices.into_iter().map(|i| i as i32).collect()
+}
+
+#[derive(Debug)]
+struct VectorCollectionRow {
+    collection_id: Uuid,
+    name: String,
+    size: Option<i32>,
+    embedding_provider: String,
+    embedding_model: Option<String>,
+    sparse_model: Option<String>,
+}
+
+impl From<VectorCollectionRow> for VectorCollection {
+    fn from(row: VectorCollectionRow) -> Self {
+        VectorCollection::new(
+            row.collection_id,
+            row.name,
+            row.size.map(|size| size as usize),
+            row.embedding_provider,
+            row.embedding_model,
+            row.sparse_model,
+        )
+    }
+}
+
+#[derive(Debug)]
+struct StoredChunkRow {
+    id: Uuid,
+    chunk_index: Option<i32>,
+    content: String,
+}
+
+impl From<StoredChunkRow> for StoredChunk {
+    fn from(row: StoredChunkRow) -> Self {
+        StoredChunk {
+            id: row.id.to_string(),
+            index: row.chunk_index.map(|index| index as usize),
+            content: row.content,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SearchHitRow {
+    content: String,
+    score: Option<f64>,
+    document_id: Uuid,
+    chunk_index: Option<i32>,
+    chunk_id: Option<Uuid>,
+    dense: Option<Vec<f32>>,
+    sparse_indices: Option<Vec<i32>>,
+    sparse_values: Option<Vec<f32>>,
+}
+
+#[derive(Debug)]
+struct StoredPointRow {
+    id: Uuid,
+    document_id: Uuid,
+    chunk_index: Option<i32>,
+    content: String,
+    dense: Option<Vec<f32>>,
+    sparse_indices: Option<Vec<i32>>,
+    sparse_values: Option<Vec<f32>>,
+}
+
+impl From<StoredPointRow> for StoredPoint {
+    fn from(row: StoredPointRow) -> Self {
+        let sparse = match (row.sparse_indices, row.sparse_values) {
+            (Some(indices), Some(values)) => Some(SparseEmbedding {
+                indices: indices.into_iter().map(|i| i as u32).collect(),
+                values,
+            }),
+            _ => None,
+        };
+        StoredPoint {
+            id: row.id.to_string(),
+            document_id: row.document_id,
+            chunk_index: row.chunk_index.map(|index| index as usize),
+            content: row.content,
+            dense: row
+                .dense
+                .map(|dense| dense.into_iter().map(f64::from).collect()),
+            sparse,
+        }
+    }
+}
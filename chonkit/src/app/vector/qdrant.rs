@@ -1,121 +1,238 @@
+use crate::core::embedder::SparseEmbedding;
+use crate::core::model::{List, Pagination};
 use crate::core::vector::{
-    CreateVectorCollection, VectorCollection, VectorDb, COLLECTION_EMBEDDING_MODEL_PROPERTY,
-    COLLECTION_EMBEDDING_PROVIDER_PROPERTY, COLLECTION_ID_PROPERTY, COLLECTION_NAME_PROPERTY,
-    COLLECTION_SIZE_PROPERTY, CONTENT_PROPERTY, DOCUMENT_ID_PROPERTY,
+    CreateVectorCollection, QuantizationSearch, QueryVector, VectorCollection, VectorDb,
+    VectorIndexConfig, Vectors, CHUNK_ID_PROPERTY, CHUNK_INDEX_PROPERTY,
+    COLLECTION_EMBEDDING_MODEL_PROPERTY, COLLECTION_EMBEDDING_PROVIDER_PROPERTY,
+    COLLECTION_ID_PROPERTY, COLLECTION_NAME_PROPERTY, COLLECTION_SIZE_PROPERTY,
+    COLLECTION_SPARSE_MODEL_PROPERTY, CONTENT_PROPERTY, DOCUMENT_ID_PROPERTY,
+};
+use crate::core::vector::{
+    PointPage, ProductCompression, Quantization, SearchHit, StoredChunk, StoredPoint,
 };
 use crate::error::{ChonkitErr, ChonkitError};
 use crate::{err, map_err};
 use qdrant_client::qdrant::vectors_config::Config;
 use qdrant_client::qdrant::with_payload_selector::SelectorOptions;
 use qdrant_client::qdrant::{
-    value, Condition, CreateCollection, DeletePointsBuilder, Distance, Filter,
-    GetCollectionInfoResponse, PointStruct, SearchParams, SearchPoints, UpsertPointsBuilder,
-    VectorParams, VectorsConfig, WithPayloadSelector,
+    point_id::PointIdOptions, quantization_config, value, vectors::VectorsOptions,
+    BinaryQuantization, CompressionRatio, Condition, CountPointsBuilder, CreateAliasBuilder,
+    CreateCollection, CreateFieldIndexCollectionBuilder, DeletePointsBuilder, Distance, FieldType,
+    Filter, GetPointsBuilder, HnswConfigDiff, PayloadIncludeSelector, PointId, PointStruct,
+    PointsIdsList, ProductQuantization, QuantizationConfig, QuantizationSearchParams,
+    QuantizationType, RetrievedPoint, ScalarQuantization, ScrollPointsBuilder, SearchParams,
+    SearchPoints, SparseIndexConfig, SparseIndices, SparseVectorConfig, SparseVectorParams,
+    UpsertPointsBuilder, Vector, VectorParams, VectorParamsMap, VectorsConfig, WithPayloadSelector,
 };
 use qdrant_client::{Payload, Qdrant, QdrantError};
-use serde_json::json;
-use std::collections::HashMap;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 /// Alias for an arced Qdrant instance.
+pub type QdrantDb = Arc<QdrantVectors>;
+
+/// Qdrant vector storage.
 ///
-/// Since Qdrant does not support collection properties, we have to create a vector
-/// representing them (which is how this implementation can actually get info about a collection).
-/// Every collection has a "null" vector, i.e. a vector that is the same size as the embeddings,
-/// but with all values set to 0.0. The null vector is used to get the properties of the collection.
+/// Since Qdrant does not support collection properties, the collection information
+/// (ID, providers and models) is read from the application's collections in Postgres.
+/// The vector size is read from the Qdrant collection configuration.
 ///
-/// It is jank, but there is no other way to do it.
-pub type QdrantDb = Arc<Qdrant>;
+/// Dense vectors are stored as the collection's unnamed vector, while sparse vectors
+/// are stored as the named sparse vector [SPARSE_VECTOR_NAME].
+pub struct QdrantVectors {
+    client: Qdrant,
+    postgres: PgPool,
+}
+
+/// Name of the sparse vector in collections holding sparse vectors.
+pub const SPARSE_VECTOR_NAME: &str = "sparse";
+
+/// Payload key of the "null" vectors previous versions stored collection
+/// information in. See [remove_id_vectors].
+const ID_VECTOR_PAYLOAD_KEY: &str = "collection_info";
 
-pub fn init(url: &str) -> QdrantDb {
+/// Amount of points fetched per scroll request.
+const SCROLL_LIMIT: u32 = 256;
+
+pub fn init(url: &str, postgres: PgPool) -> QdrantDb {
     info!("Connecting to qdrant at {url}");
-    Arc::new(
-        Qdrant::from_url(url)
-            .build()
-            .expect("error initialising qdrant"),
-    )
+    let client = Qdrant::from_url(url)
+        .build()
+        .expect("error initialising qdrant");
+    Arc::new(QdrantVectors { client, postgres })
 }
 
 #[async_trait::async_trait]
-impl VectorDb for Qdrant {
+impl VectorDb for QdrantVectors {
     fn id(&self) -> &'static str {
         "qdrant"
     }
 
     async fn list_vector_collections(&self) -> Result<Vec<VectorCollection>, ChonkitError> {
-        let collection_names = map_err!(self.list_collections().await)
+        let collection_names = map_err!(self.client.list_collections().await)
             .collections
             .into_iter()
             .map(|col| col.name)
             .collect::<Vec<_>>();
 
+        let mut rows: HashMap<String, CollectionRow> = map_err!(
+            sqlx::query_as!(
+                CollectionRow,
+                "SELECT id, name, model, sparse_model, embedder FROM collections WHERE provider = $1",
+                self.id()
+            )
+            .fetch_all(&self.postgres)
+            .await
+        )
+        .into_iter()
+        .map(|row| (row.name.clone(), row))
+        .collect();
+
         let mut collections = vec![];
 
         for name in collection_names {
-            let info = map_err!(self.collection_info(&name).await);
-            let size = get_collection_size(&info);
-            if let Some(size) = size {
-                let info = get_id_vector(self, &name, size).await?;
-                collections.push(info);
-            }
+            let Some(row) = rows.remove(&name) else {
+                // Not created by chonkit
+                debug!("Skipping collection '{name}' without collection info");
+                continue;
+            };
+            let size = self.get_dense_size(&name).await?;
+            collections.push(row.into_vector_collection(size));
         }
 
         Ok(collections)
     }
 
+    async fn list_collection_names(&self) -> Result<Vec<String>, ChonkitError> {
+        let collection_names = map_err!(self.client.list_collections().await)
+            .collections
+            .into_iter()
+            .map(|col| col.name);
+
+        let tracked: HashSet<String> = map_err!(
+            sqlx::query_scalar!(
+                "SELECT name FROM collections WHERE provider = $1",
+                self.id()
+            )
+            .fetch_all(&self.postgres)
+            .await
+        )
+        .into_iter()
+        .collect();
+
+        let mut names = vec![];
+
+        for name in collection_names {
+            // Untracked collections are only listed if they hold chonkit's points
+            // so that collections not created by chonkit are never reported
+            if tracked.contains(&name) || self.holds_chonkit_points(&name).await? {
+                names.push(name);
+            }
+        }
+
+        Ok(names)
+    }
+
     async fn create_vector_collection(
         &self,
         data: CreateVectorCollection<'_>,
     ) -> Result<(), ChonkitError> {
-        let CreateVectorCollection { name, size, .. } = data;
+        let CreateVectorCollection {
+            name,
+            size,
+            sparse_model,
+            index_config,
+            ..
+        } = data;
+
+        let VectorIndexConfig {
+            distance,
+            hnsw_m,
+            hnsw_ef_construct,
+            on_disk,
+            quantization,
+        } = index_config;
 
-        let config = VectorsConfig {
-            config: Some(Config::Params(VectorParams {
+        let config = match size {
+            Some(size) => Config::Params(VectorParams {
                 size: size as u64,
-                distance: Distance::Cosine.into(),
+                distance: qdrant_distance(distance).into(),
+                on_disk: Some(on_disk),
+                quantization_config: quantization.map(qdrant_quantization),
                 ..Default::default()
-            })),
+            }),
+            // Collections holding only sparse vectors have no dense vectors configured
+            None => Config::ParamsMap(VectorParamsMap::default()),
+        };
+
+        let sparse_config = sparse_model.map(|_| SparseVectorConfig {
+            map: HashMap::from([(
+                SPARSE_VECTOR_NAME.to_string(),
+                SparseVectorParams {
+                    index: Some(SparseIndexConfig {
+                        on_disk: Some(on_disk),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )]),
+        });
+
+        let hnsw_config = HnswConfigDiff {
+            m: hnsw_m,
+            ef_construct: hnsw_ef_construct,
+            on_disk: Some(on_disk),
+            ..Default::default()
         };
 
         let res = map_err!(
-            self.create_collection(CreateCollection {
-                collection_name: name.to_string(),
-                vectors_config: Some(config),
-                ..Default::default()
-            })
-            .await
+            self.client
+                .create_collection(CreateCollection {
+                    collection_name: name.to_string(),
+                    vectors_config: Some(VectorsConfig {
+                        config: Some(config)
+                    }),
+                    sparse_vectors_config: sparse_config,
+                    hnsw_config: Some(hnsw_config),
+                    on_disk_payload: Some(on_disk),
+                    ..Default::default()
+                })
+                .await
         );
 
-        map_err!(create_id_vector(self, data).await);
-
         debug_assert!(res.result);
 
+        create_chunk_index(&self.client, name).await?;
+
         Ok(())
     }
 
     async fn get_collection(&self, name: &str) -> Result<VectorCollection, ChonkitError> {
-        let info = map_err!(self.collection_info(name).await);
-        let size = get_collection_size(&info);
-        let Some(size) = size else {
-            #[cfg(debug_assertions)]
-            {
-                debug!("{info:?}")
-            }
-            return err!(
-                DoesNotExist,
-                "Size information for vector collection '{name}'"
-            );
+        let row = map_err!(
+            sqlx::query_as!(
+                CollectionRow,
+                "SELECT id, name, model, sparse_model, embedder FROM collections WHERE name = $1 AND provider = $2",
+                name,
+                self.id()
+            )
+            .fetch_optional(&self.postgres)
+            .await
+        );
+
+        let Some(row) = row else {
+            return err!(DoesNotExist, "Collection info for '{name}'");
         };
 
-        let info = get_id_vector(self, name, size).await?;
+        let size = self.get_dense_size(name).await?;
 
-        Ok(info)
+        Ok(row.into_vector_collection(size))
     }
 
     async fn delete_vector_collection(&self, name: &str) -> Result<(), ChonkitError> {
-        map_err!(self.delete_collection(name).await);
+        map_err!(self.client.delete_collection(name).await);
         Ok(())
     }
 
@@ -137,34 +254,87 @@ impl VectorDb for Qdrant {
 
     async fn query(
         &self,
-        search: Vec<f64>,
+        search: QueryVector,
         collection: &str,
         limit: u32,
-    ) -> Result<Vec<String>, ChonkitError> {
+        quantization: QuantizationSearch,
+        with_vectors: bool,
+    ) -> Result<Vec<SearchHit>, ChonkitError> {
+        let sparse = matches!(search, QueryVector::Sparse(_));
+        let (vector, sparse_indices, vector_name) = match search {
+            QueryVector::Dense(vector) => {
+                (vector.into_iter().map(|x| x as f32).collect(), None, None)
+            }
+            QueryVector::Sparse(SparseEmbedding { indices, values }) => (
+                values,
+                Some(SparseIndices { data: indices }),
+                Some(SPARSE_VECTOR_NAME.to_string()),
+            ),
+        };
+
         let search_points = SearchPoints {
             collection_name: collection.to_string(),
-            vector: search.into_iter().map(|x| x as f32).collect(),
+            vector,
+            sparse_indices,
+            vector_name,
             filter: None,
             limit: limit as u64,
             with_payload: Some(WithPayloadSelector {
                 selector_options: Some(SelectorOptions::Enable(true)),
             }),
-            params: Some(SearchParams::default()),
+            with_vectors: Some(with_vectors.into()),
+            params: Some(SearchParams {
+                quantization: Some(QuantizationSearchParams {
+                    rescore: quantization.rescore,
+                    oversampling: quantization.oversampling,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
             ..Default::default()
         };
 
-        let search_result = map_err!(self.search_points(search_points).await);
+        let search_result = map_err!(self.client.search_points(search_points).await);
 
         let results = search_result
             .result
             .into_iter()
-            .filter_map(|mut point| point.payload.remove(CONTENT_PROPERTY)?.kind)
-            .filter_map(|value| match value {
-                value::Kind::StringValue(s) => Some(s),
-                v => {
-                    warn!("Found unsupported value kind: {v:?}");
-                    None
-                }
+            .filter_map(|mut point| {
+                let content = match point.payload.remove(CONTENT_PROPERTY)?.kind? {
+                    value::Kind::StringValue(s) => s,
+                    v => {
+                        warn!("Found unsupported value kind: {v:?}");
+                        return None;
+                    }
+                };
+                let document_id = point
+                    .payload
+                    .get(DOCUMENT_ID_PROPERTY)
+                    .and_then(|v| v.as_str())
+                    .and_then(|id| id.parse().ok());
+                let chunk_index = point
+                    .payload
+                    .get(CHUNK_INDEX_PROPERTY)
+                    .and_then(|v| v.as_integer())
+                    .map(|i| i as usize);
+                let chunk_id = point
+                    .payload
+                    .get(CHUNK_ID_PROPERTY)
+                    .and_then(|v| v.as_str())
+                    .and_then(|id| id.parse().ok());
+                let vector = match stored_vectors(point.vectors) {
+                    (_, Some(vector)) if sparse => Some(QueryVector::Sparse(vector)),
+                    (Some(vector), _) if !sparse => Some(QueryVector::Dense(vector)),
+                    _ => None,
+                };
+                Some(SearchHit {
+                    content,
+                    score: point.score as f64,
+                    document_id,
+                    chunk_index,
+                    chunk_id,
+                    vector,
+                })
             })
             .collect();
 
@@ -176,36 +346,47 @@ impl VectorDb for Qdrant {
         document_id: Uuid,
         collection: &str,
         content: &[&str],
-        vectors: Vec<Vec<f64>>,
+        chunk_ids: Option<&[Uuid]>,
+        vectors: Vectors,
     ) -> Result<(), ChonkitError> {
         debug!("Inserting vectors to {collection}");
 
-        debug_assert_eq!(
-            content.len(),
-            vectors.len(),
+        let Vectors { dense, sparse } = vectors;
+
+        debug_assert!(
+            dense.as_ref().is_none_or(|d| d.len() == content.len())
+                && sparse.as_ref().is_none_or(|s| s.len() == content.len()),
             "Content length is different from embeddings!"
         );
 
-        let points: Vec<PointStruct> = vectors
-            .into_iter()
-            .zip(content.iter())
-            .map(|(embedding, content)| {
+        let mut dense = dense.map(Vec::into_iter);
+        let mut sparse = sparse.map(Vec::into_iter);
+
+        let points: Vec<PointStruct> = content
+            .iter()
+            .enumerate()
+            .map(|(index, content)| {
                 let mut payload = Payload::new();
                 payload.insert(CONTENT_PROPERTY, content.to_string());
                 payload.insert(DOCUMENT_ID_PROPERTY, document_id.to_string());
+                payload.insert(CHUNK_INDEX_PROPERTY, index as i64);
+                if let Some(chunk_id) = chunk_ids.and_then(|ids| ids.get(index)) {
+                    payload.insert(CHUNK_ID_PROPERTY, chunk_id.to_string());
+                }
                 PointStruct::new(
                     uuid::Uuid::new_v4().to_string(),
-                    embedding
-                        .into_iter()
-                        .map(|x| x as f32)
-                        .collect::<Vec<f32>>(),
+                    point_vectors(
+                        dense.as_mut().and_then(Iterator::next),
+                        sparse.as_mut().and_then(Iterator::next),
+                    ),
                     payload,
                 )
             })
             .collect();
 
         map_err!(
-            self.upsert_points(UpsertPointsBuilder::new(collection, points).wait(true))
+            self.client
+                .upsert_points(UpsertPointsBuilder::new(collection, points).wait(true))
                 .await
         );
 
@@ -218,15 +399,13 @@ impl VectorDb for Qdrant {
         document_id: uuid::Uuid,
     ) -> Result<(), ChonkitError> {
         map_err!(
-            self.delete_points(
-                DeletePointsBuilder::new(collection)
-                    .points(Filter::must([Condition::matches(
-                        DOCUMENT_ID_PROPERTY,
-                        document_id.to_string(),
-                    )]))
-                    .wait(true),
-            )
-            .await
+            self.client
+                .delete_points(
+                    DeletePointsBuilder::new(collection)
+                        .points(document_filter(document_id))
+                        .wait(true),
+                )
+                .await
         );
 
         Ok(())
@@ -237,117 +416,535 @@ impl VectorDb for Qdrant {
         collection: &str,
         document_id: Uuid,
     ) -> Result<usize, ChonkitError> {
-        use qdrant_client::qdrant::{Condition, Filter, ScrollPointsBuilder};
-
-        let scroll = map_err!(
-            self.scroll(
-                ScrollPointsBuilder::new(collection)
-                    .filter(Filter::must([Condition::matches(
-                        DOCUMENT_ID_PROPERTY,
-                        document_id.to_string(),
-                    )]))
-                    .with_payload(false)
-                    .with_vectors(false),
-            )
+        self.count_points(collection, document_filter(document_id))
             .await
+    }
+
+    async fn count_vectors_by_document(
+        &self,
+        collection: &str,
+    ) -> Result<HashMap<Uuid, usize>, ChonkitError> {
+        let mut counts = HashMap::new();
+        let mut offset = None;
+
+        loop {
+            let mut scroll = ScrollPointsBuilder::new(collection)
+                .limit(SCROLL_LIMIT)
+                .with_payload(PayloadIncludeSelector::new(vec![
+                    DOCUMENT_ID_PROPERTY.to_string()
+                ]))
+                .with_vectors(false);
+
+            if let Some(offset) = offset {
+                scroll = scroll.offset(offset);
+            }
+
+            let response = map_err!(self.client.scroll(scroll).await);
+
+            for point in response.result {
+                let Some(document_id) = point
+                    .payload
+                    .get(DOCUMENT_ID_PROPERTY)
+                    .and_then(|v| v.as_str())
+                    .and_then(|id| id.parse::<Uuid>().ok())
+                else {
+                    continue;
+                };
+                *counts.entry(document_id).or_default() += 1;
+            }
+
+            offset = response.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        Ok(counts)
+    }
+
+    async fn scroll(
+        &self,
+        collection: &str,
+        offset: Option<String>,
+        limit: u32,
+    ) -> Result<PointPage, ChonkitError> {
+        let mut scroll = ScrollPointsBuilder::new(collection)
+            .limit(limit)
+            .with_payload(true)
+            .with_vectors(true);
+
+        if let Some(offset) = offset {
+            let offset: PointId = match offset.parse::<u64>() {
+                Ok(num) => num.into(),
+                Err(_) => offset.into(),
+            };
+            scroll = scroll.offset(offset);
+        }
+
+        let response = map_err!(self.client.scroll(scroll).await);
+
+        let points = response
+            .result
+            .into_iter()
+            .filter_map(|point| {
+                let id = match point.id?.point_id_options? {
+                    PointIdOptions::Num(id) => id.to_string(),
+                    PointIdOptions::Uuid(id) => id,
+                };
+                let payload = point.payload;
+                let document_id = payload
+                    .get(DOCUMENT_ID_PROPERTY)?
+                    .as_str()?
+                    .parse::<Uuid>()
+                    .ok()?;
+                let content = payload.get(CONTENT_PROPERTY)?.as_str()?.to_string();
+                let chunk_index = payload
+                    .get(CHUNK_INDEX_PROPERTY)
+                    .and_then(|v| v.as_integer())
+                    .map(|i| i as usize);
+                let (dense, sparse) = stored_vectors(point.vectors);
+                Some(StoredPoint {
+                    id,
+                    document_id,
+                    chunk_index,
+                    content,
+                    dense,
+                    sparse,
+                })
+            })
+            .collect();
+
+        let next_offset = response
+            .next_page_offset
+            .and_then(|id| id.point_id_options)
+            .map(|id| match id {
+                PointIdOptions::Num(id) => id.to_string(),
+                PointIdOptions::Uuid(id) => id,
+            });
+
+        Ok(PointPage {
+            points,
+            next_offset,
+        })
+    }
+
+    async fn list_chunks(
+        &self,
+        collection: &str,
+        document_id: Uuid,
+        pagination: Pagination,
+    ) -> Result<List<StoredChunk>, ChonkitError> {
+        let (limit, offset) = pagination.to_limit_offset();
+        let (limit, offset) = (limit as usize, offset as usize);
+
+        let total = self
+            .count_points(collection, document_filter(document_id))
+            .await?;
+
+        // Points without the chunk index are excluded when ordering by it
+        let unindexed_filter = Filter::must([
+            Condition::matches(DOCUMENT_ID_PROPERTY, document_id.to_string()),
+            Condition::is_empty(CHUNK_INDEX_PROPERTY),
+        ]);
+
+        let unindexed = self
+            .count_points(collection, unindexed_filter.clone())
+            .await?;
+        let indexed = total - unindexed;
+
+        let mut items = vec![];
+
+        if offset < indexed {
+            let scroll = ScrollPointsBuilder::new(collection)
+                .filter(document_filter(document_id))
+                .order_by(CHUNK_INDEX_PROPERTY)
+                .limit((offset + limit) as u32)
+                .with_payload(true)
+                .with_vectors(false);
+
+            let response = map_err!(self.client.scroll(scroll).await);
+
+            items.extend(
+                response
+                    .result
+                    .into_iter()
+                    .skip(offset)
+                    .filter_map(stored_chunk),
+            );
+        }
+
+        // Chunks without an index are listed last, ordered by their IDs
+        let remaining = limit.saturating_sub(items.len());
+
+        if remaining > 0 && unindexed > 0 {
+            let skip = offset.saturating_sub(indexed);
+
+            let scroll = ScrollPointsBuilder::new(collection)
+                .filter(unindexed_filter)
+                .limit((skip + remaining) as u32)
+                .with_payload(true)
+                .with_vectors(false);
+
+            let response = map_err!(self.client.scroll(scroll).await);
+
+            items.extend(
+                response
+                    .result
+                    .into_iter()
+                    .skip(skip)
+                    .filter_map(stored_chunk),
+            );
+        }
+
+        Ok(List::new(Some(total), items))
+    }
+
+    async fn set_alias(&self, alias: &str, collection: &str) -> Result<(), ChonkitError> {
+        // Creating an existing alias points it to the new collection in a single operation.
+        map_err!(
+            self.client
+                .create_alias(CreateAliasBuilder::new(collection, alias))
+                .await
         );
+        Ok(())
+    }
 
-        Ok(scroll.result.len())
+    async fn delete_alias(&self, alias: &str) -> Result<(), ChonkitError> {
+        match self.client.delete_alias(alias).await {
+            Err(QdrantError::ResponseError { status })
+                if matches!(status.code(), tonic::Code::NotFound) =>
+            {
+                Ok(())
+            }
+            result => {
+                map_err!(result);
+                Ok(())
+            }
+        }
     }
 }
 
-async fn create_id_vector(
-    qdrant: &Qdrant,
-    collection: CreateVectorCollection<'_>,
-) -> Result<(), QdrantError> {
-    let mut payload = Payload::new();
-    payload.insert("collection_info", json! { collection });
-
-    let point = PointStruct::new(
-        uuid::Uuid::nil().to_string(),
-        vec![0.0; collection.size],
-        payload,
-    );
+fn stored_chunk(point: RetrievedPoint) -> Option<StoredChunk> {
+    let id = match point.id?.point_id_options? {
+        PointIdOptions::Num(id) => id.to_string(),
+        PointIdOptions::Uuid(id) => id,
+    };
+    let content = point
+        .payload
+        .get(CONTENT_PROPERTY)
+        .and_then(|v| v.as_str())
+        .cloned()
+        .unwrap_or_default();
+    let index = point
+        .payload
+        .get(CHUNK_INDEX_PROPERTY)
+        .and_then(|v| v.as_integer())
+        .map(|i| i as usize);
+    Some(StoredChunk { id, index, content })
+}
 
-    qdrant
-        .upsert_points(UpsertPointsBuilder::new(collection.name, vec![point]).wait(true))
-        .await?;
+fn document_filter(document_id: Uuid) -> Filter {
+    Filter::must([Condition::matches(
+        DOCUMENT_ID_PROPERTY,
+        document_id.to_string(),
+    )])
+}
 
-    Ok(())
+fn qdrant_distance(distance: crate::core::vector::Distance) -> Distance {
+    use crate::core::vector::Distance as D;
+    match distance {
+        D::Cosine => Distance::Cosine,
+        D::Dot => Distance::Dot,
+        D::Euclidean => Distance::Euclid,
+        D::Manhattan => Distance::Manhattan,
+    }
 }
 
-async fn get_id_vector(
-    qdrant: &Qdrant,
-    name: &str,
-    size: usize,
-) -> Result<VectorCollection, ChonkitError> {
-    let search_points = SearchPoints {
-        collection_name: name.to_string(),
-        vector: vec![0.0; size],
-        filter: Some(qdrant_client::qdrant::Filter::must(vec![
-            Condition::has_id(vec![Uuid::nil().to_string()]),
-        ])),
-        limit: 1,
-        with_payload: Some(WithPayloadSelector {
-            selector_options: Some(SelectorOptions::Enable(true)),
+fn qdrant_quantization(quantization: Quantization) -> QuantizationConfig {
+    let quantization = match quantization {
+        Quantization::Scalar {
+            quantile,
+            always_ram,
+        } => quantization_config::Quantization::Scalar(ScalarQuantization {
+            r#type: QuantizationType::Int8.into(),
+            quantile,
+            always_ram: Some(always_ram),
         }),
-        params: Some(SearchParams::default()),
-        ..Default::default()
+        Quantization::Product {
+            compression,
+            always_ram,
+        } => {
+            let compression = match compression {
+                ProductCompression::X4 => CompressionRatio::X4,
+                ProductCompression::X8 => CompressionRatio::X8,
+                ProductCompression::X16 => CompressionRatio::X16,
+                ProductCompression::X32 => CompressionRatio::X32,
+                ProductCompression::X64 => CompressionRatio::X64,
+            };
+            quantization_config::Quantization::Product(ProductQuantization {
+                compression: compression.into(),
+                always_ram: Some(always_ram),
+            })
+        }
+        Quantization::Binary { always_ram } => {
+            quantization_config::Quantization::Binary(BinaryQuantization {
+                always_ram: Some(always_ram),
+            })
+        }
     };
 
-    let mut search_result = map_err!(qdrant.search_points(search_points).await);
+    QuantizationConfig {
+        quantization: Some(quantization),
+    }
+}
 
-    let results = &mut search_result.result[0];
+/// Create the vectors of a point. Dense vectors are stored as the unnamed vector,
+/// which is referred to by the empty name when used with named vectors.
+fn point_vectors(
+    dense: Option<Vec<f64>>,
+    sparse: Option<SparseEmbedding>,
+) -> qdrant_client::qdrant::Vectors {
+    let dense = dense.map(|dense| dense.into_iter().map(|x| x as f32).collect::<Vec<f32>>());
 
-    let Some(info_string) = results.payload.remove("collection_info") else {
-        return err!(DoesNotExist, "Collection info vector for '{name}'");
-    };
+    match (dense, sparse) {
+        (Some(dense), None) => dense.into(),
+        (dense, sparse) => {
+            let mut vectors = HashMap::new();
+            if let Some(dense) = dense {
+                vectors.insert(String::new(), Vector::new_dense(dense));
+            }
+            if let Some(SparseEmbedding { indices, values }) = sparse {
+                vectors.insert(
+                    SPARSE_VECTOR_NAME.to_string(),
+                    Vector::new_sparse(indices, values),
+                );
+            }
+            vectors.into()
+        }
+    }
+}
 
-    let Some(kind) = info_string.kind else {
-        return err!(DoesNotExist, "Collection info vector for '{name}'");
-    };
+/// Split the vectors of a point into its dense and sparse vectors.
+fn stored_vectors(
+    vectors: Option<qdrant_client::qdrant::Vectors>,
+) -> (Option<Vec<f64>>, Option<SparseEmbedding>) {
+    let dense = |vector: Vector| vector.data.into_iter().map(f64::from).collect::<Vec<_>>();
 
-    let value = match kind {
-        value::Kind::StructValue(s) => s,
-        v => {
-            warn!("Found unsupported value kind: {v:?}");
-            return err!(DoesNotExist, "Collection info vector for '{name}'");
+    match vectors.and_then(|vectors| vectors.vectors_options) {
+        Some(VectorsOptions::Vector(vector)) => (Some(dense(vector)), None),
+        Some(VectorsOptions::Vectors(mut named)) => {
+            let sparse = named
+                .vectors
+                .remove(SPARSE_VECTOR_NAME)
+                .and_then(|vector| vector.try_into_sparse().ok())
+                .map(|(indices, values)| SparseEmbedding { indices, values });
+            (named.vectors.remove("").map(dense), sparse)
         }
-    };
+        None => (None, None),
+    }
+}
 
-    let config = match VectorCollection::try_from_map(value.fields) {
-        Some(c) => c,
-        None => {
-            tracing::error!("Invalid collection info vector for '{name}'");
-            return err!(DoesNotExist, "Collection info vector for '{name}'");
-        }
-    };
+impl QdrantVectors {
+    /// Returns the exact amount of points in the collection matching the filter.
+    async fn count_points(&self, collection: &str, filter: Filter) -> Result<usize, ChonkitError> {
+        let count = map_err!(
+            self.client
+                .count(
+                    CountPointsBuilder::new(collection)
+                        .filter(filter)
+                        .exact(true)
+                )
+                .await
+        );
 
-    Ok(config)
+        Ok(count.result.map(|r| r.count as usize).unwrap_or_default())
+    }
+
+    /// Returns the size of the collection's dense vectors, `None` if it only holds sparse vectors.
+    async fn get_dense_size(&self, name: &str) -> Result<Option<usize>, ChonkitError> {
+        let info = map_err!(self.client.collection_info(name).await).result;
+
+        let config = info
+            .and_then(|info| info.config)
+            .and_then(|config| config.params)
+            .and_then(|params| params.vectors_config)
+            .and_then(|vectors| vectors.config);
+
+        Ok(match config {
+            Some(Config::Params(params)) => Some(params.size as usize),
+            Some(Config::ParamsMap(map)) => map.map.get("").map(|params| params.size as usize),
+            None => None,
+        })
+    }
+
+    /// Check whether the collection's points carry chonkit's payload.
+    /// Empty collections are not considered to hold chonkit's points.
+    async fn holds_chonkit_points(&self, name: &str) -> Result<bool, ChonkitError> {
+        let scroll = ScrollPointsBuilder::new(name)
+            .limit(1)
+            .with_payload(true)
+            .with_vectors(false);
+
+        let points = map_err!(self.client.scroll(scroll).await).result;
+
+        Ok(points.first().is_some_and(|point| {
+            point.payload.contains_key(CONTENT_PROPERTY)
+                && point
+                    .payload
+                    .get(DOCUMENT_ID_PROPERTY)
+                    .and_then(|id| id.as_str())
+                    .is_some_and(|id| id.parse::<Uuid>().is_ok())
+        }))
+    }
 }
 
-fn get_collection_size(info: &GetCollectionInfoResponse) -> Option<usize> {
-    let config = info
+/// Create the payload index on the chunk index of a collection's points if it does not exist.
+/// Needed to list the points of a document ordered by their chunk index.
+///
+/// * `client`: Qdrant client.
+/// * `collection`: Collection name.
+async fn create_chunk_index(client: &Qdrant, collection: &str) -> Result<(), ChonkitError> {
+    let info = map_err!(client.collection_info(collection).await);
+
+    let indexed = info
         .result
-        .as_ref()?
-        .config
-        .as_ref()?
-        .params
-        .as_ref()?
-        .vectors_config
-        .as_ref()?
-        .config
-        .as_ref()?;
-    match config {
-        Config::Params(VectorParams { size, .. }) => Some(*size as usize),
-        Config::ParamsMap(pm) => {
-            warn!("Found unexpected params map! {pm:?}");
-            None
+        .is_some_and(|info| info.payload_schema.contains_key(CHUNK_INDEX_PROPERTY));
+
+    if indexed {
+        return Ok(());
+    }
+
+    map_err!(
+        client
+            .create_field_index(
+                CreateFieldIndexCollectionBuilder::new(
+                    collection,
+                    CHUNK_INDEX_PROPERTY,
+                    FieldType::Integer
+                )
+                .wait(true)
+            )
+            .await
+    );
+
+    Ok(())
+}
+
+/// Create the chunk index payload index in collections created before it was introduced.
+/// Safe to run multiple times.
+pub async fn create_chunk_indexes(qdrant: &QdrantVectors) -> Result<(), ChonkitError> {
+    for name in qdrant.list_collection_names().await? {
+        create_chunk_index(&qdrant.client, &name).await?;
+    }
+    Ok(())
+}
+
+/// Remove the "null" vectors previous versions stored collection information in.
+/// Those vectors showed up in search results and broke collections using dot product distance.
+///
+/// The collection information of a removed vector is used to insert the collection into the
+/// collections table if it is missing from it. Safe to run multiple times.
+pub async fn remove_id_vectors(qdrant: &QdrantVectors) -> Result<(), ChonkitError> {
+    let collection_names = map_err!(qdrant.client.list_collections().await)
+        .collections
+        .into_iter()
+        .map(|col| col.name);
+
+    for name in collection_names {
+        let mut points = map_err!(
+            qdrant
+                .client
+                .get_points(
+                    GetPointsBuilder::new(&name, vec![Uuid::nil().to_string().into()])
+                        .with_payload(true)
+                        .with_vectors(false)
+                )
+                .await
+        )
+        .result;
+
+        let Some(mut point) = points.pop() else {
+            continue;
+        };
+
+        let Some(value::Kind::StructValue(info)) = point
+            .payload
+            .remove(ID_VECTOR_PAYLOAD_KEY)
+            .and_then(|value| value.kind)
+        else {
+            continue;
+        };
+
+        match VectorCollection::try_from_map(info.fields) {
+            Some(collection) => {
+                let inserted = map_err!(
+                    sqlx::query!(
+                        "INSERT INTO collections(id, name, model, sparse_model, embedder, provider)
+                         VALUES ($1, $2, $3, $4, $5, $6)
+                         ON CONFLICT DO NOTHING",
+                        collection.id,
+                        collection.name,
+                        collection.embedding_model,
+                        collection.sparse_model,
+                        collection.embedding_provider,
+                        qdrant.id()
+                    )
+                    .execute(&qdrant.postgres)
+                    .await
+                )
+                .rows_affected();
+
+                if inserted > 0 {
+                    info!("Restored collection info of '{name}' from its id vector");
+                }
+            }
+            None => warn!("Invalid id vector in '{name}'; Removing without restoring"),
         }
+
+        map_err!(
+            qdrant
+                .client
+                .delete_points(
+                    DeletePointsBuilder::new(&name)
+                        .points(PointsIdsList {
+                            ids: vec![Uuid::nil().to_string().into()],
+                        })
+                        .wait(true),
+                )
+                .await
+        );
+
+        info!("Removed id vector from '{name}'");
+    }
+
+    Ok(())
+}
+
+/// Collection information stored in the collections table.
+#[derive(Debug)]
+struct CollectionRow {
+    id: Uuid,
+    name: String,
+    model: Option<String>,
+    sparse_model: Option<String>,
+    embedder: String,
+}
+
+impl CollectionRow {
+    fn into_vector_collection(self, size: Option<usize>) -> VectorCollection {
+        VectorCollection::new(
+            self.id,
+            self.name,
+            size,
+            self.embedder,
+            self.model,
+            self.sparse_model,
+        )
     }
 }
 
 impl VectorCollection {
+    /// Parse the collection information of an id vector.
     fn try_from_map(map: HashMap<String, qdrant_client::qdrant::Value>) -> Option<Self> {
         macro_rules! get_for_type {
             ($map:ident, $field:ident, $const:ident, $kind:ident) => {{
@@ -365,14 +962,27 @@ impl VectorCollection {
             }};
         }
 
+        // Null for collections holding only sparse vectors and missing
+        // for collections created before sparse vectors were supported.
+        macro_rules! get_optional_for_type {
+            ($map:ident, $const:ident, $kind:ident) => {{
+                match $map.get($const).and_then(|value| value.kind.as_ref()) {
+                    Some(value::Kind::$kind(value)) => Some(value),
+                    None | Some(value::Kind::NullValue(_)) => None,
+                    Some(_) => {
+                        tracing::error!("Invalid '{}' property", $const);
+                        return None;
+                    }
+                }
+            }};
+        }
+
         let name = get_for_type!(map, name, COLLECTION_NAME_PROPERTY, StringValue);
-        let size = get_for_type!(map, size, COLLECTION_SIZE_PROPERTY, IntegerValue);
-        let embedding_model = get_for_type!(
-            map,
-            embedding_model,
-            COLLECTION_EMBEDDING_MODEL_PROPERTY,
-            StringValue
-        );
+        let size = get_optional_for_type!(map, COLLECTION_SIZE_PROPERTY, IntegerValue);
+        let embedding_model =
+            get_optional_for_type!(map, COLLECTION_EMBEDDING_MODEL_PROPERTY, StringValue);
+        let sparse_model =
+            get_optional_for_type!(map, COLLECTION_SPARSE_MODEL_PROPERTY, StringValue);
         let embedding_provider = get_for_type!(
             map,
             embedding_provider,
@@ -392,9 +1002,10 @@ impl VectorCollection {
         Some(VectorCollection::new(
             id,
             name.to_string(),
-            *size as usize,
+            size.map(|size| *size as usize),
             embedding_provider.to_string(),
-            embedding_model.to_string(),
+            embedding_model.cloned(),
+            sparse_model.cloned(),
         ))
     }
 }
@@ -404,26 +1015,59 @@ impl VectorCollection {
 mod qdrant_tests {
     use crate::{
         app::{
-            test::{init_qdrant, AsyncContainer},
-            vector::qdrant::QdrantDb,
+            test::{init_postgres, init_qdrant, AsyncContainer, PostgresContainer},
+            vector::qdrant::{remove_id_vectors, QdrantDb, ID_VECTOR_PAYLOAD_KEY},
         },
         config::{
             DEFAULT_COLLECTION_EMBEDDING_MODEL, DEFAULT_COLLECTION_EMBEDDING_PROVIDER,
             DEFAULT_COLLECTION_NAME, DEFAULT_COLLECTION_SIZE,
         },
-        core::vector::{CreateVectorCollection, VectorDb},
+        core::{
+            embedder::SparseEmbedding,
+            model::Pagination,
+            vector::{
+                CreateVectorCollection, Distance, Quantization, QuantizationSearch, QueryVector,
+                VectorDb, VectorIndexConfig, Vectors,
+            },
+        },
     };
+    use qdrant_client::{
+        qdrant::{quantization_config, vectors_config::Config, GetPointsBuilder, PointStruct},
+        qdrant::{UpsertPointsBuilder, Vector},
+        Payload,
+    };
+    use serde_json::json;
+    use sqlx::PgPool;
     use suitest::before_all;
     use uuid::Uuid;
 
     #[before_all]
-    async fn setup() -> (QdrantDb, AsyncContainer) {
-        let (qdrant, img) = init_qdrant().await;
+    async fn setup() -> (QdrantDb, PgPool, AsyncContainer, PostgresContainer) {
+        let (postgres, pg_img) = init_postgres().await;
+        let (qdrant, qd_img) = init_qdrant(postgres.clone()).await;
 
         let data = CreateVectorCollection::default();
 
+        insert_collection_info(&postgres, &data).await;
         qdrant.create_default_collection(data).await.unwrap();
-        (qdrant, img)
+
+        (qdrant, postgres, qd_img, pg_img)
+    }
+
+    /// Insert the collection information the service would insert when creating a collection.
+    async fn insert_collection_info(postgres: &PgPool, data: &CreateVectorCollection<'_>) {
+        sqlx::query!(
+            "INSERT INTO collections(id, name, model, sparse_model, embedder, provider)
+             VALUES ($1, $2, $3, $4, $5, 'qdrant')",
+            data.collection_id,
+            data.name,
+            data.embedding_model,
+            data.sparse_model,
+            data.embedding_provider
+        )
+        .execute(postgres)
+        .await
+        .unwrap();
     }
 
     #[test]
@@ -434,29 +1078,384 @@ mod qdrant_tests {
             .unwrap();
 
         assert_eq!(DEFAULT_COLLECTION_NAME, default.name);
-        assert_eq!(DEFAULT_COLLECTION_SIZE, default.size);
+        assert_eq!(Some(DEFAULT_COLLECTION_SIZE), default.size);
         assert_eq!(
             DEFAULT_COLLECTION_EMBEDDING_PROVIDER,
             default.embedding_provider
         );
-        assert_eq!(DEFAULT_COLLECTION_EMBEDDING_MODEL, default.embedding_model);
+        assert_eq!(
+            Some(DEFAULT_COLLECTION_EMBEDDING_MODEL),
+            default.embedding_model.as_deref()
+        );
+        assert!(default.sparse_model.is_none());
     }
 
     #[test]
-    async fn creates_collection(qdrant: QdrantDb) {
+    async fn creates_collection(qdrant: QdrantDb, postgres: PgPool) {
         let name = "My_collection_0";
         let id = Uuid::new_v4();
 
-        let data = CreateVectorCollection::new(id, name, 420, "openai", "text-embedding-ada-002");
+        let data = CreateVectorCollection::new(
+            id,
+            name,
+            Some(420),
+            "openai",
+            Some("text-embedding-ada-002"),
+            None,
+        );
 
+        insert_collection_info(postgres, &data).await;
         qdrant.create_vector_collection(data).await.unwrap();
 
         let collection = qdrant.get_collection(name).await.unwrap();
 
         assert_eq!(id, collection.id);
         assert_eq!(name, collection.name);
-        assert_eq!(420, collection.size);
+        assert_eq!(Some(420), collection.size);
         assert_eq!("openai", collection.embedding_provider);
-        assert_eq!("text-embedding-ada-002", collection.embedding_model);
+        assert_eq!(
+            Some("text-embedding-ada-002"),
+            collection.embedding_model.as_deref()
+        );
+    }
+
+    #[test]
+    async fn creates_collection_with_index_config(qdrant: QdrantDb) {
+        let name = "My_collection_1";
+
+        let index_config = VectorIndexConfig {
+            distance: Distance::Dot,
+            hnsw_m: Some(32),
+            hnsw_ef_construct: Some(200),
+            on_disk: true,
+            quantization: Some(Quantization::Scalar {
+                quantile: Some(0.99),
+                always_ram: true,
+            }),
+        };
+
+        let data = CreateVectorCollection::new(
+            Uuid::new_v4(),
+            name,
+            Some(420),
+            "openai",
+            Some("text-embedding-ada-002"),
+            None,
+        )
+        .with_index_config(index_config);
+
+        qdrant.create_vector_collection(data).await.unwrap();
+
+        let config = qdrant
+            .client
+            .collection_info(name)
+            .await
+            .unwrap()
+            .result
+            .unwrap()
+            .config
+            .unwrap();
+
+        let hnsw = config.hnsw_config.unwrap();
+        assert_eq!(Some(32), hnsw.m);
+        assert_eq!(Some(200), hnsw.ef_construct);
+        assert_eq!(Some(true), hnsw.on_disk);
+
+        let Some(Config::Params(params)) = config.params.unwrap().vectors_config.unwrap().config
+        else {
+            panic!("collection has no dense vector params")
+        };
+        assert_eq!(qdrant_client::qdrant::Distance::Dot as i32, params.distance);
+        assert_eq!(Some(true), params.on_disk);
+
+        let Some(quantization_config::Quantization::Scalar(scalar)) =
+            params.quantization_config.unwrap().quantization
+        else {
+            panic!("collection vectors are not scalar quantized")
+        };
+        assert_eq!(Some(0.99), scalar.quantile);
+        assert_eq!(Some(true), scalar.always_ram);
+    }
+
+    #[test]
+    async fn creates_and_queries_sparse_collection(qdrant: QdrantDb, postgres: PgPool) {
+        let name = "My_sparse_collection_0";
+        let id = Uuid::new_v4();
+
+        let data = CreateVectorCollection::new(
+            id,
+            name,
+            None,
+            "fembed",
+            None,
+            Some("Qdrant/Splade_PP_en_v1"),
+        );
+
+        insert_collection_info(postgres, &data).await;
+        qdrant.create_vector_collection(data).await.unwrap();
+
+        let collection = qdrant.get_collection(name).await.unwrap();
+
+        assert_eq!(id, collection.id);
+        assert_eq!(None, collection.size);
+        assert_eq!(None, collection.embedding_model);
+        assert_eq!(
+            Some("Qdrant/Splade_PP_en_v1"),
+            collection.sparse_model.as_deref()
+        );
+
+        let sparse = |indices: Vec<u32>, values: Vec<f32>| SparseEmbedding { indices, values };
+
+        let vectors = Vectors {
+            dense: None,
+            sparse: Some(vec![
+                sparse(vec![1, 5], vec![0.5, 0.5]),
+                sparse(vec![2, 7], vec![1.0, 0.2]),
+            ]),
+        };
+
+        let document_id = Uuid::new_v4();
+
+        let chunk_ids = [Uuid::new_v4(), Uuid::new_v4()];
+
+        qdrant
+            .insert_embeddings(
+                document_id,
+                name,
+                &["first", "second"],
+                Some(&chunk_ids),
+                vectors,
+            )
+            .await
+            .unwrap();
+
+        let query = QueryVector::Sparse(sparse(vec![2], vec![1.0]));
+        let results = qdrant
+            .query(query, name, 1, QuantizationSearch::default(), true)
+            .await
+            .unwrap();
+
+        assert_eq!(1, results.len());
+        assert_eq!("second", results[0].content);
+        assert_eq!(Some(document_id), results[0].document_id);
+        assert_eq!(Some(chunk_ids[1]), results[0].chunk_id);
+        assert!((results[0].score - 1.0).abs() < 1e-6);
+        assert!(matches!(
+            &results[0].vector,
+            Some(QueryVector::Sparse(vector)) if vector.indices == vec![2, 7]
+        ));
+    }
+
+    #[test]
+    async fn counts_and_lists_chunks(qdrant: QdrantDb, postgres: PgPool) {
+        let name = "My_sparse_collection_1";
+
+        let data = CreateVectorCollection::new(
+            Uuid::new_v4(),
+            name,
+            None,
+            "fembed",
+            None,
+            Some("Qdrant/Splade_PP_en_v1"),
+        );
+
+        insert_collection_info(postgres, &data).await;
+        qdrant.create_vector_collection(data).await.unwrap();
+
+        let sparse = |n: usize| {
+            (0..n as u32)
+                .map(|i| SparseEmbedding {
+                    indices: vec![i],
+                    values: vec![1.0],
+                })
+                .collect()
+        };
+
+        let document_id = Uuid::new_v4();
+
+        qdrant
+            .insert_embeddings(
+                document_id,
+                name,
+                &["first", "second", "third"],
+                None,
+                Vectors {
+                    dense: None,
+                    sparse: Some(sparse(3)),
+                },
+            )
+            .await
+            .unwrap();
+
+        qdrant
+            .insert_embeddings(
+                Uuid::new_v4(),
+                name,
+                &["other"],
+                None,
+                Vectors {
+                    dense: None,
+                    sparse: Some(sparse(1)),
+                },
+            )
+            .await
+            .unwrap();
+
+        let count = qdrant.count_vectors(name, document_id).await.unwrap();
+        assert_eq!(3, count);
+
+        let page = qdrant
+            .list_chunks(name, document_id, Pagination::new(2, 1))
+            .await
+            .unwrap();
+
+        assert_eq!(Some(3), page.total);
+        assert_eq!(
+            vec![(Some(0), "first"), (Some(1), "second")],
+            page.items
+                .iter()
+                .map(|chunk| (chunk.index, chunk.content.as_str()))
+                .collect::<Vec<_>>()
+        );
+
+        let page = qdrant
+            .list_chunks(name, document_id, Pagination::new(2, 2))
+            .await
+            .unwrap();
+
+        assert_eq!(1, page.items.len());
+        assert_eq!(Some(2), page.items[0].index);
+        assert_eq!("third", page.items[0].content);
+    }
+
+    #[test]
+    async fn ignores_collections_without_info(qdrant: QdrantDb) {
+        let name = "My_collection_2";
+
+        let data = CreateVectorCollection::new(
+            Uuid::new_v4(),
+            name,
+            Some(420),
+            "openai",
+            Some("text-embedding-ada-002"),
+            None,
+        );
+
+        qdrant.create_vector_collection(data).await.unwrap();
+
+        assert!(qdrant.get_collection(name).await.is_err());
+        assert!(qdrant
+            .list_vector_collections()
+            .await
+            .unwrap()
+            .iter()
+            .all(|collection| collection.name != name));
+    }
+
+    #[test]
+    async fn lists_untracked_collection_names(qdrant: QdrantDb) {
+        let untracked = "My_collection_4";
+        let unrelated = "My_collection_5";
+
+        for name in [untracked, unrelated] {
+            let data = CreateVectorCollection::new(
+                Uuid::new_v4(),
+                name,
+                Some(2),
+                "openai",
+                Some("text-embedding-ada-002"),
+                None,
+            );
+            qdrant.create_vector_collection(data).await.unwrap();
+        }
+
+        let vectors = Vectors {
+            dense: Some(vec![vec![1.0, 0.0]]),
+            sparse: None,
+        };
+
+        qdrant
+            .insert_embeddings(Uuid::new_v4(), untracked, &["content"], None, vectors)
+            .await
+            .unwrap();
+
+        let point = PointStruct::new(
+            Uuid::new_v4().to_string(),
+            Vector::new_dense(vec![1.0, 0.0]),
+            Payload::try_from(json!({ "foo": "bar" })).unwrap(),
+        );
+
+        qdrant
+            .client
+            .upsert_points(UpsertPointsBuilder::new(unrelated, vec![point]).wait(true))
+            .await
+            .unwrap();
+
+        let names = qdrant.list_collection_names().await.unwrap();
+
+        assert!(names.iter().any(|name| name == DEFAULT_COLLECTION_NAME));
+        assert!(names.iter().any(|name| name == untracked));
+        assert!(!names.iter().any(|name| name == unrelated));
+
+        // Neither has collection information
+        let collections = qdrant.list_vector_collections().await.unwrap();
+        assert!(!collections.iter().any(|c| c.name == untracked));
+    }
+
+    #[test]
+    async fn removes_id_vectors(qdrant: QdrantDb, postgres: PgPool) {
+        let name = "My_collection_3";
+        let id = Uuid::new_v4();
+
+        let data = CreateVectorCollection::new(
+            id,
+            name,
+            Some(4),
+            "openai",
+            Some("text-embedding-ada-002"),
+            None,
+        );
+
+        let mut payload = Payload::new();
+        payload.insert(ID_VECTOR_PAYLOAD_KEY, json!(data));
+        let point = PointStruct::new(
+            Uuid::nil().to_string(),
+            Vector::new_dense(vec![0.0; 4]),
+            payload,
+        );
+
+        qdrant.create_vector_collection(data).await.unwrap();
+        qdrant
+            .client
+            .upsert_points(UpsertPointsBuilder::new(name, vec![point]).wait(true))
+            .await
+            .unwrap();
+
+        remove_id_vectors(qdrant).await.unwrap();
+
+        let points = qdrant
+            .client
+            .get_points(GetPointsBuilder::new(
+                name,
+                vec![Uuid::nil().to_string().into()],
+            ))
+            .await
+            .unwrap()
+            .result;
+        assert!(points.is_empty());
+
+        // Collection info is restored from the id vector
+        let collection = qdrant.get_collection(name).await.unwrap();
+        assert_eq!(id, collection.id);
+        assert_eq!(Some(4), collection.size);
+
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM collections WHERE name = $1"#,
+            name
+        )
+        .fetch_one(postgres)
+        .await
+        .unwrap();
+        assert_eq!(1, count);
     }
 }
@@ -1,11 +1,18 @@
 use crate::core::vector::{
-    CreateVectorCollection, VectorCollection, VectorDb, COLLECTION_EMBEDDING_MODEL_PROPERTY,
-    COLLECTION_EMBEDDING_PROVIDER_PROPERTY, COLLECTION_ID_PROPERTY, COLLECTION_NAME_PROPERTY,
-    COLLECTION_SIZE_PROPERTY, CONTENT_PROPERTY, DOCUMENT_ID_PROPERTY,
+    CreateVectorCollection, Distance, QuantizationSearch, QueryVector, VectorCollection, VectorDb,
+    VectorIndexConfig, Vectors, CHUNK_ID_PROPERTY, CHUNK_INDEX_PROPERTY,
+    COLLECTION_EMBEDDING_MODEL_PROPERTY, COLLECTION_EMBEDDING_PROVIDER_PROPERTY,
+    COLLECTION_ID_PROPERTY, COLLECTION_NAME_PROPERTY, COLLECTION_SIZE_PROPERTY, CONTENT_PROPERTY,
+    DOCUMENT_ID_PROPERTY,
+};
+use crate::core::{
+    model::{List, Pagination},
+    vector::{PointPage, SearchHit, StoredChunk, StoredPoint},
 };
 use crate::{err, error::ChonkitError, map_err};
-use dto::{QueryResult, WeaviateError};
+use dto::{AggregateResult, QueryResult, WeaviateError};
 use serde_json::json;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::info;
 use uuid::Uuid;
@@ -14,13 +21,18 @@ use weaviate_community::{
         batch::{BatchDeleteRequest, MatchConfig},
         error::SchemaError,
         objects::{ConsistencyLevel, MultiObjects, Object},
-        query::GetQuery,
-        schema::{Class, Properties, PropertyBuilder},
+        query::{GetQuery, RawQuery},
+        schema::{
+            Class, DistanceMetric, Properties, Property, PropertyBuilder,
+            VectorIndexConfig as WeaviateIndexConfig,
+        },
     },
     WeaviateClient,
 };
 
 /// Alias for an arced Weaviate instance.
+///
+/// Only dense vectors are supported.
 pub type WeaviateDb = Arc<WeaviateClient>;
 
 pub fn init(url: &str) -> WeaviateDb {
@@ -56,8 +68,26 @@ impl VectorDb for WeaviateClient {
         &self,
         data: CreateVectorCollection<'_>,
     ) -> Result<(), ChonkitError> {
-        let class = Class::builder(data.name);
-        let props = create_collection_properties(data);
+        // Weaviate keeps the HNSW index in memory and always persists vectors to disk
+        if data.index_config.on_disk {
+            return err!(
+                InvalidProvider,
+                "On disk collections are not supported by weaviate"
+            );
+        }
+
+        if data.index_config.quantization.is_some() {
+            return err!(InvalidProvider, "Quantization is not supported by weaviate");
+        }
+
+        let class = Class::builder(data.name)
+            .with_vector_index_config(create_vector_index_config(&data.index_config));
+        let Some(props) = create_collection_properties(data) else {
+            return err!(
+                Weaviate,
+                "Only collections with dense vectors are supported"
+            );
+        };
         let class = class.with_properties(props).build();
 
         if let Err(e) = self.schema.create_class(&class).await {
@@ -85,8 +115,14 @@ impl VectorDb for WeaviateClient {
         &self,
         data: CreateVectorCollection<'_>,
     ) -> Result<(), ChonkitError> {
-        let class = Class::builder(data.name);
-        let props = create_collection_properties(data);
+        let class = Class::builder(data.name)
+            .with_vector_index_config(create_vector_index_config(&data.index_config));
+        let Some(props) = create_collection_properties(data) else {
+            return err!(
+                Weaviate,
+                "Only collections with dense vectors are supported"
+            );
+        };
         let class = class
             .with_description("Default vector collection")
             .with_properties(props)
@@ -111,16 +147,39 @@ impl VectorDb for WeaviateClient {
 
     async fn query(
         &self,
-        search: Vec<f64>,
+        search: QueryVector,
         collection: &str,
         limit: u32,
-    ) -> Result<Vec<String>, ChonkitError> {
+        _quantization: QuantizationSearch,
+        with_vectors: bool,
+    ) -> Result<Vec<SearchHit>, ChonkitError> {
+        let QueryVector::Dense(search) = search else {
+            return err!(
+                InvalidProvider,
+                "Sparse vectors are not supported by weaviate"
+            );
+        };
+
         // God help us all
         let near_vector = &format!("{{ vector: {search:?} }}");
-        let query = GetQuery::builder(collection, vec![CONTENT_PROPERTY])
-            .with_near_vector(near_vector)
-            .with_limit(limit)
-            .build();
+        let additional = if with_vectors {
+            vec!["distance", "vector"]
+        } else {
+            vec!["distance"]
+        };
+        let query = GetQuery::builder(
+            collection,
+            vec![
+                CONTENT_PROPERTY,
+                DOCUMENT_ID_PROPERTY,
+                CHUNK_INDEX_PROPERTY,
+                CHUNK_ID_PROPERTY,
+            ],
+        )
+        .with_near_vector(near_vector)
+        .with_limit(limit)
+        .with_additional(additional)
+        .build();
 
         let response = match self.query.get(query).await {
             Ok(res) => res,
@@ -141,9 +200,40 @@ impl VectorDb for WeaviateClient {
             results.clone()
         ))
         .into_iter()
-        .filter_map(|obj| obj.get(CONTENT_PROPERTY).cloned())
-        .map(serde_json::from_value::<String>)
-        .filter_map(Result::ok)
+        .filter_map(|obj| {
+            let content = obj.get(CONTENT_PROPERTY)?.as_str()?.to_string();
+            let document_id = obj
+                .get(DOCUMENT_ID_PROPERTY)
+                .and_then(|id| id.as_str())
+                .and_then(|id| id.parse().ok());
+            let chunk_index = obj
+                .get(CHUNK_INDEX_PROPERTY)
+                .and_then(serde_json::Value::as_f64)
+                .map(|i| i as usize);
+            let chunk_id = obj
+                .get(CHUNK_ID_PROPERTY)
+                .and_then(|id| id.as_str())
+                .and_then(|id| id.parse().ok());
+            // Weaviate returns distances, lower is better
+            let distance = obj
+                .get("_additional")
+                .and_then(|additional| additional.get("distance"))
+                .and_then(|distance| distance.as_f64())
+                .unwrap_or_default();
+            let vector = obj
+                .pointer("/_additional/vector")
+                .cloned()
+                .and_then(|vector| serde_json::from_value::<Vec<f64>>(vector).ok())
+                .map(QueryVector::Dense);
+            Some(SearchHit {
+                content,
+                score: 1. - distance,
+                document_id,
+                chunk_index,
+                chunk_id,
+                vector,
+            })
+        })
         .collect();
 
         Ok(results)
@@ -154,18 +244,35 @@ impl VectorDb for WeaviateClient {
         document_id: Uuid,
         collection: &str,
         content: &[&str],
-        vectors: Vec<Vec<f64>>,
+        chunk_ids: Option<&[Uuid]>,
+        vectors: Vectors,
     ) -> Result<(), ChonkitError> {
+        let Vectors {
+            dense: Some(vectors),
+            sparse: None,
+        } = vectors
+        else {
+            return err!(
+                InvalidProvider,
+                "Only dense vectors are supported by weaviate"
+            );
+        };
+
         debug_assert_eq!(content.len(), vectors.len());
 
         let objects = content
             .iter()
             .zip(vectors.into_iter())
-            .map(|(content, vector)| {
-                let properties = json!({
+            .enumerate()
+            .map(|(index, (content, vector))| {
+                let mut properties = json!({
                     CONTENT_PROPERTY: content,
-                    DOCUMENT_ID_PROPERTY: document_id
+                    DOCUMENT_ID_PROPERTY: document_id,
+                    CHUNK_INDEX_PROPERTY: index,
                 });
+                if let Some(chunk_id) = chunk_ids.and_then(|ids| ids.get(index)) {
+                    properties[CHUNK_ID_PROPERTY] = json!(chunk_id);
+                }
                 Object::builder(collection, properties)
                     .with_vector(vector)
                     .with_id(uuid::Uuid::new_v4())
@@ -217,14 +324,180 @@ impl VectorDb for WeaviateClient {
         collection: &str,
         document_id: Uuid,
     ) -> Result<usize, ChonkitError> {
-        let query = GetQuery::builder(collection, vec![DOCUMENT_ID_PROPERTY])
-            .with_where(&format!(
-                "{{ 
-                    path: [\"{DOCUMENT_ID_PROPERTY}\"],
-                    operator: Equal,
-                    valueText: \"{document_id}\" 
-                }}"
+        // The aggregate builder from the client outputs an invalid query
+        // when given a where filter, so the query is written by hand.
+        let query = RawQuery::new(&format!(
+            "{{ Aggregate {{ {collection}(where: {}) {{ meta {{ count }} }} }} }}",
+            document_where(document_id)
+        ));
+
+        let response = match self.query.raw(query).await {
+            Ok(res) => res,
+            Err(e) => return err!(Weaviate, "{}", e),
+        };
+
+        let result: AggregateResult = map_err!(serde_json::from_value(response));
+
+        let Some(count) = result
+            .data
+            .aggregate
+            .get(collection)
+            .and_then(|results| results.get(0))
+            .and_then(|result| result.pointer("/meta/count"))
+            .and_then(serde_json::Value::as_u64)
+        else {
+            return err!(
+                Weaviate,
+                "Response error - cannot read count of '{collection}' in {}",
+                result.data.aggregate
+            );
+        };
+
+        Ok(count as usize)
+    }
+
+    async fn count_vectors_by_document(
+        &self,
+        collection: &str,
+    ) -> Result<HashMap<Uuid, usize>, ChonkitError> {
+        let query = RawQuery::new(&format!(
+            "{{ Aggregate {{ {collection}(groupBy: [\"{DOCUMENT_ID_PROPERTY}\"]) {{ groupedBy {{ value }} meta {{ count }} }} }} }}"
+        ));
+
+        let response = match self.query.raw(query).await {
+            Ok(res) => res,
+            Err(e) => return err!(Weaviate, "{}", e),
+        };
+
+        let result: AggregateResult = map_err!(serde_json::from_value(response));
+
+        let Some(groups) = result
+            .data
+            .aggregate
+            .get(collection)
+            .and_then(serde_json::Value::as_array)
+        else {
+            return err!(
+                Weaviate,
+                "Response error - cannot index into '{collection}' in {}",
+                result.data.aggregate
+            );
+        };
+
+        Ok(groups
+            .iter()
+            .filter_map(|group| {
+                let document_id = group
+                    .pointer("/groupedBy/value")?
+                    .as_str()?
+                    .parse::<Uuid>()
+                    .ok()?;
+                let count = group.pointer("/meta/count")?.as_u64()?;
+                Some((document_id, count as usize))
+            })
+            .collect())
+    }
+
+    async fn scroll(
+        &self,
+        collection: &str,
+        offset: Option<String>,
+        limit: u32,
+    ) -> Result<PointPage, ChonkitError> {
+        let mut query = GetQuery::builder(
+            collection,
+            vec![CONTENT_PROPERTY, DOCUMENT_ID_PROPERTY, CHUNK_INDEX_PROPERTY],
+        )
+        .with_limit(limit)
+        .with_additional(vec!["id", "vector"]);
+
+        if let Some(offset) = offset {
+            query = query.with_after(map_err!(offset.parse::<Uuid>()));
+        }
+
+        let response = match self.query.get(query.build()).await {
+            Ok(res) => res,
+            Err(e) => return err!(Weaviate, "{}", e),
+        };
+
+        let result: QueryResult = map_err!(serde_json::from_value(response));
+
+        let Some(results) = result.data.get.get(collection) else {
+            return err!(
+                Weaviate,
+                "Response error - cannot index into '{collection}' in {}",
+                result.data.get
+            );
+        };
+
+        let objects = map_err!(serde_json::from_value::<Vec<serde_json::Value>>(
+            results.clone()
+        ));
+
+        // The cursor is the ID of the last object in the page
+        let next_offset = if objects.len() == limit as usize {
+            objects
+                .last()
+                .and_then(|obj| obj.pointer("/_additional/id"))
+                .and_then(serde_json::Value::as_str)
+                .map(String::from)
+        } else {
+            None
+        };
+
+        let points = objects
+            .into_iter()
+            .filter_map(|obj| {
+                let id = obj.pointer("/_additional/id")?.as_str()?.to_string();
+                let document_id = obj
+                    .get(DOCUMENT_ID_PROPERTY)?
+                    .as_str()?
+                    .parse::<Uuid>()
+                    .ok()?;
+                let content = obj.get(CONTENT_PROPERTY)?.as_str()?.to_string();
+                let chunk_index = obj
+                    .get(CHUNK_INDEX_PROPERTY)
+                    .and_then(serde_json::Value::as_f64)
+                    .map(|i| i as usize);
+                let dense = obj
+                    .pointer("/_additional/vector")
+                    .cloned()
+                    .and_then(|vector| serde_json::from_value::<Vec<f64>>(vector).ok());
+                Some(StoredPoint {
+                    id,
+                    document_id,
+                    chunk_index,
+                    content,
+                    dense,
+                    sparse: None,
+                })
+            })
+            .collect();
+
+        Ok(PointPage {
+            points,
+            next_offset,
+        })
+    }
+
+    async fn list_chunks(
+        &self,
+        collection: &str,
+        document_id: Uuid,
+        pagination: Pagination,
+    ) -> Result<List<StoredChunk>, ChonkitError> {
+        let total = self.count_vectors(collection, document_id).await?;
+
+        let (limit, offset) = pagination.to_limit_offset();
+
+        let query = GetQuery::builder(collection, vec![CONTENT_PROPERTY, CHUNK_INDEX_PROPERTY])
+            .with_where(&document_where(document_id))
+            .with_sort(&format!(
+                "[{{ path: [\"{CHUNK_INDEX_PROPERTY}\"], order: asc }}]"
             ))
+            .with_limit(limit as u32)
+            .with_offset(offset as u32)
+            .with_additional(vec!["id"])
             .build();
 
         let response = match self.query.get(query).await {
@@ -242,23 +515,117 @@ impl VectorDb for WeaviateClient {
             );
         };
 
-        let amount = map_err!(serde_json::from_value::<Vec<serde_json::Value>>(
+        let items = map_err!(serde_json::from_value::<Vec<serde_json::Value>>(
             results.clone()
         ))
-        .len();
+        .into_iter()
+        .filter_map(|obj| {
+            let id = obj.pointer("/_additional/id")?.as_str()?.to_string();
+            let content = obj.get(CONTENT_PROPERTY)?.as_str()?.to_string();
+            let index = obj
+                .get(CHUNK_INDEX_PROPERTY)
+                .and_then(serde_json::Value::as_f64)
+                .map(|i| i as usize);
+            Some(StoredChunk { id, index, content })
+        })
+        .collect();
+
+        Ok(List::new(Some(total), items))
+    }
+
+    async fn set_alias(&self, _alias: &str, _collection: &str) -> Result<(), ChonkitError> {
+        // Aliases are not supported by the client, they are resolved by chonkit.
+        Ok(())
+    }
 
-        Ok(amount)
+    async fn delete_alias(&self, _alias: &str) -> Result<(), ChonkitError> {
+        Ok(())
     }
 }
 
+/// Add the object properties missing from collections created before they were
+/// declared in the class schema. Weaviate only adds undeclared properties when
+/// objects carrying them are inserted, and querying a missing property fails.
+pub async fn add_object_properties(weaviate: &WeaviateDb) -> Result<(), ChonkitError> {
+    let classes = match weaviate.schema.get().await {
+        Ok(classes) => classes,
+        Err(e) => return err!(Weaviate, "{}", e),
+    };
+
+    for class in classes.classes {
+        let existing: Vec<String> = class
+            .properties
+            .as_ref()
+            .map(|props| props.0.iter().map(|prop| prop.name.clone()).collect())
+            .unwrap_or_default();
+
+        // Skip classes not created by chonkit
+        if !existing.iter().any(|name| name == COLLECTION_ID_PROPERTY) {
+            continue;
+        }
+
+        for property in create_object_properties() {
+            if existing.contains(&property.name) {
+                continue;
+            }
+
+            if let Err(e) = weaviate.schema.add_property(&class.class, &property).await {
+                return err!(Weaviate, "{}", e);
+            }
+
+            info!("Added '{}' property to '{}'", property.name, class.class);
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a where filter matching the objects of the given document.
+fn document_where(document_id: Uuid) -> String {
+    format!(
+        "{{ path: [\"{DOCUMENT_ID_PROPERTY}\"], operator: Equal, valueText: \"{document_id}\" }}"
+    )
+}
+
+/// Create the HNSW index configuration for a collection (weaviate class).
+/// Weaviate has no plain euclidean distance, so its squared variant is used,
+/// which results in the same ordering.
+fn create_vector_index_config(config: &VectorIndexConfig) -> WeaviateIndexConfig {
+    let distance = match config.distance {
+        Distance::Cosine => DistanceMetric::COSINE,
+        Distance::Dot => DistanceMetric::DOT,
+        Distance::Euclidean => DistanceMetric::L2SQUARED,
+        Distance::Manhattan => DistanceMetric::MANHATTAN,
+    };
+
+    let mut index_config = WeaviateIndexConfig::builder().with_distance(distance);
+
+    if let Some(m) = config.hnsw_m {
+        index_config = index_config.with_max_connections(m);
+    }
+
+    if let Some(ef_construct) = config.hnsw_ef_construct {
+        index_config = index_config.with_ef_construction(ef_construct);
+    }
+
+    index_config.build()
+}
+
 /// Create properties for a collection (weaviate class).
-fn create_collection_properties(data: CreateVectorCollection<'_>) -> Properties {
+/// Returns `None` if the collection is not meant to hold only dense vectors.
+fn create_collection_properties(data: CreateVectorCollection<'_>) -> Option<Properties> {
+    let (Some(size), Some(embedding_model), None) =
+        (data.size, data.embedding_model, data.sparse_model)
+    else {
+        return None;
+    };
+
     let id = PropertyBuilder::new(COLLECTION_ID_PROPERTY, vec!["text"])
         .with_description(&data.collection_id.to_string())
         .build();
 
     let size = PropertyBuilder::new(COLLECTION_SIZE_PROPERTY, vec!["int"])
-        .with_description(&data.size.to_string())
+        .with_description(&size.to_string())
         .build();
 
     let name = PropertyBuilder::new(COLLECTION_NAME_PROPERTY, vec!["text"])
@@ -271,10 +638,24 @@ fn create_collection_properties(data: CreateVectorCollection<'_>) -> Properties
             .build();
 
     let embedding_model = PropertyBuilder::new(COLLECTION_EMBEDDING_MODEL_PROPERTY, vec!["text"])
-        .with_description(data.embedding_model)
+        .with_description(embedding_model)
         .build();
 
-    Properties::new(vec![id, size, name, embedding_provider, embedding_model])
+    let mut properties = vec![id, size, name, embedding_provider, embedding_model];
+    properties.extend(create_object_properties());
+
+    Some(Properties::new(properties))
+}
+
+/// Create the properties of the objects stored in a collection (weaviate class).
+/// Declared with the class so they can be queried before any objects are inserted.
+fn create_object_properties() -> Vec<Property> {
+    vec![
+        PropertyBuilder::new(CONTENT_PROPERTY, vec!["text"]).build(),
+        PropertyBuilder::new(DOCUMENT_ID_PROPERTY, vec!["text"]).build(),
+        PropertyBuilder::new(CHUNK_INDEX_PROPERTY, vec!["int"]).build(),
+        PropertyBuilder::new(CHUNK_ID_PROPERTY, vec!["text"]).build(),
+    ]
 }
 
 /// Attempt to parse Weaviate GraphQL data to a [dto::WeaviateError].
@@ -348,7 +729,7 @@ impl TryFrom<Class> for VectorCollection {
             }
         }
 
-        if v_collection.size == 0 {
+        if v_collection.size.is_none() {
             return err!(Weaviate, "Missing 'size' property in class {class_name}",);
         }
 
@@ -386,6 +767,17 @@ mod dto {
         #[serde(rename = "Get")]
         pub get: serde_json::Value,
     }
+
+    #[derive(Debug, Deserialize)]
+    pub struct AggregateResult {
+        pub data: AggregateData,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct AggregateData {
+        #[serde(rename = "Aggregate")]
+        pub aggregate: serde_json::Value,
+    }
 }
 
 #[cfg(test)]
@@ -400,7 +792,9 @@ mod weaviate_tests {
             DEFAULT_COLLECTION_EMBEDDING_MODEL, DEFAULT_COLLECTION_EMBEDDING_PROVIDER,
             DEFAULT_COLLECTION_ID, DEFAULT_COLLECTION_NAME, DEFAULT_COLLECTION_SIZE,
         },
-        core::vector::{CreateVectorCollection, VectorDb},
+        core::vector::{
+            CreateVectorCollection, QuantizationSearch, QueryVector, VectorDb, Vectors,
+        },
     };
     use suitest::before_all;
     use uuid::Uuid;
@@ -424,12 +818,15 @@ mod weaviate_tests {
 
         assert_eq!(DEFAULT_COLLECTION_ID, default.id);
         assert_eq!(DEFAULT_COLLECTION_NAME, default.name);
-        assert_eq!(DEFAULT_COLLECTION_SIZE, default.size);
+        assert_eq!(Some(DEFAULT_COLLECTION_SIZE), default.size);
         assert_eq!(
             DEFAULT_COLLECTION_EMBEDDING_PROVIDER,
             default.embedding_provider
         );
-        assert_eq!(DEFAULT_COLLECTION_EMBEDDING_MODEL, default.embedding_model);
+        assert_eq!(
+            Some(DEFAULT_COLLECTION_EMBEDDING_MODEL),
+            default.embedding_model.as_deref()
+        );
     }
 
     #[test]
@@ -437,7 +834,14 @@ mod weaviate_tests {
         let name = "My_collection_0";
         let id = Uuid::new_v4();
 
-        let data = CreateVectorCollection::new(id, name, 420, "openai", "text-embedding-ada-002");
+        let data = CreateVectorCollection::new(
+            id,
+            name,
+            Some(420),
+            "openai",
+            Some("text-embedding-ada-002"),
+            None,
+        );
 
         weaver.create_vector_collection(data).await.unwrap();
 
@@ -445,8 +849,80 @@ mod weaviate_tests {
 
         assert_eq!(id, collection.id);
         assert_eq!(name, collection.name);
-        assert_eq!(420, collection.size);
+        assert_eq!(Some(420), collection.size);
         assert_eq!("openai", collection.embedding_provider);
-        assert_eq!("text-embedding-ada-002", collection.embedding_model);
+        assert_eq!(
+            Some("text-embedding-ada-002"),
+            collection.embedding_model.as_deref()
+        );
+    }
+
+    #[test]
+    async fn rejects_sparse_collection(weaver: WeaviateDb) {
+        let data = CreateVectorCollection::new(
+            Uuid::new_v4(),
+            "My_sparse_collection_0",
+            None,
+            "fembed",
+            None,
+            Some("Qdrant/Splade_PP_en_v1"),
+        );
+
+        assert!(weaver.create_vector_collection(data).await.is_err());
+    }
+
+    #[test]
+    async fn queries_chunk_properties(weaver: WeaviateDb) {
+        let name = "My_collection_1";
+
+        let data = CreateVectorCollection::new(
+            Uuid::new_v4(),
+            name,
+            Some(2),
+            "openai",
+            Some("text-embedding-ada-002"),
+            None,
+        );
+
+        weaver.create_vector_collection(data).await.unwrap();
+
+        let query = || QueryVector::Dense(vec![0.0, 1.0]);
+
+        // The object properties are declared with the class
+        let empty = weaver
+            .query(query(), name, 1, QuantizationSearch::default(), false)
+            .await
+            .unwrap();
+        assert!(empty.is_empty());
+
+        let document_id = Uuid::new_v4();
+        let chunk_ids = [Uuid::new_v4(), Uuid::new_v4()];
+
+        let vectors = Vectors {
+            dense: Some(vec![vec![1.0, 0.0], vec![0.0, 1.0]]),
+            sparse: None,
+        };
+
+        weaver
+            .insert_embeddings(
+                document_id,
+                name,
+                &["first", "second"],
+                Some(&chunk_ids),
+                vectors,
+            )
+            .await
+            .unwrap();
+
+        let results = weaver
+            .query(query(), name, 1, QuantizationSearch::default(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(1, results.len());
+        assert_eq!("second", results[0].content);
+        assert_eq!(Some(document_id), results[0].document_id);
+        assert_eq!(Some(1), results[0].chunk_index);
+        assert_eq!(Some(chunk_ids[1]), results[0].chunk_id);
     }
 }
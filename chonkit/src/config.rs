@@ -1,3 +1,4 @@
+use crate::app::reconcile::ReconcileMode;
 use clap::Parser;
 
 /// The ID for the default collection created on application startup.
@@ -44,6 +45,20 @@ pub struct StartArgs {
     #[arg(long)]
     cors_allowed_headers: Option<String>,
 
+    /// Reconcile the embeddings with the vector databases on startup.
+    /// One of `report` or `repair`.
+    #[arg(long)]
+    reconcile: Option<ReconcileMode>,
+
+    /// OpenAI compatible chat API used for generating evaluation questions, e.g. Ollama or vLLM.
+    /// Should not contain the `/v1` path. An API key can be set with `LLM_KEY`.
+    #[arg(long)]
+    llm_endpoint: Option<String>,
+
+    /// Chat model used for generating evaluation questions when requests do not specify one.
+    #[arg(long)]
+    llm_model: Option<String>,
+
     /// Qdrant URL.
     #[cfg(feature = "qdrant")]
     #[arg(short, long)]
@@ -55,6 +70,7 @@ pub struct StartArgs {
     weaviate_url: Option<String>,
 
     /// If using the [OpenAiEmbeddings][crate::app::embedder::openai::OpenAiEmbeddings] module, set its endpoint.
+    /// Can point to any OpenAI compatible API, such as feserver.
     #[cfg(feature = "openai")]
     #[arg(short, long)]
     openai_endpoint: Option<String>,
@@ -64,6 +80,67 @@ pub struct StartArgs {
     #[arg(short, long)]
     fembed_url: Option<String>,
 
+    /// Path to a JSON configuration file for the local fastembed embedder.
+    /// The other `fembed` arguments take precedence over the values in the file.
+    #[cfg(feature = "fe-local")]
+    #[arg(long)]
+    fembed_config: Option<String>,
+
+    /// Comma separated list of models to enable in the local fastembed embedder.
+    #[cfg(feature = "fe-local")]
+    #[arg(long)]
+    fembed_models: Option<String>,
+
+    /// Comma separated list of sparse models to enable in the local fastembed embedder.
+    #[cfg(feature = "fe-local")]
+    #[arg(long)]
+    fembed_sparse_models: Option<String>,
+
+    /// Directory where the local fastembed embedder caches downloaded models.
+    #[cfg(feature = "fe-local")]
+    #[arg(long)]
+    fembed_cache_dir: Option<String>,
+
+    /// Directory containing user provided ONNX models for the local fastembed embedder.
+    #[cfg(feature = "fe-local")]
+    #[arg(long)]
+    fembed_model_dir: Option<String>,
+
+    /// Seconds after which idle models are unloaded from the local fastembed embedder.
+    #[cfg(feature = "fe-local")]
+    #[arg(long)]
+    fembed_idle_timeout: Option<u64>,
+
+    /// Maximum amount of chunks sent in a single request to remote embedders.
+    #[cfg(any(feature = "openai", feature = "fe-remote"))]
+    #[arg(long)]
+    embed_batch_size: Option<usize>,
+
+    /// Maximum amount of estimated tokens sent in a single request to remote embedders.
+    #[cfg(any(feature = "openai", feature = "fe-remote"))]
+    #[arg(long)]
+    embed_batch_tokens: Option<usize>,
+
+    /// How many times requests to remote embedders are retried on 429 and 5xx responses.
+    #[cfg(any(feature = "openai", feature = "fe-remote"))]
+    #[arg(long)]
+    embed_max_retries: Option<u32>,
+
+    /// Timeout in seconds for requests to remote embedders.
+    #[cfg(any(feature = "openai", feature = "fe-remote"))]
+    #[arg(long)]
+    embed_timeout: Option<u64>,
+
+    /// Maximum amount of requests per minute sent to a remote embedder.
+    #[cfg(any(feature = "openai", feature = "fe-remote"))]
+    #[arg(long)]
+    embed_requests_per_minute: Option<usize>,
+
+    /// Maximum amount of estimated tokens per minute sent to a remote embedder.
+    #[cfg(any(feature = "openai", feature = "fe-remote"))]
+    #[arg(long)]
+    embed_tokens_per_minute: Option<usize>,
+
     /// Vault endpoint.
     #[cfg(feature = "auth-vault")]
     #[arg(long)]
@@ -114,6 +191,18 @@ macro_rules! arg {
             }
         }
     };
+    ($id:ident, $var:literal, optional $ty:ty) => {
+        impl StartArgs {
+            pub fn $id(&self) -> Option<$ty> {
+                self.$id.as_ref().cloned().or_else(|| {
+                    std::env::var($var).ok().map(|val| {
+                        val.parse()
+                            .unwrap_or_else(|_| panic!("Invalid value for {}: {val}", $var))
+                    })
+                })
+            }
+        }
+    };
 }
 
 impl StartArgs {
@@ -153,16 +242,84 @@ impl StartArgs {
         }
     }
 
+    pub fn llm_key(&self) -> Option<String> {
+        std::env::var("LLM_KEY").ok()
+    }
+
     #[cfg(feature = "openai")]
     pub fn open_ai_key(&self) -> String {
         std::env::var("OPENAI_KEY").expect("Missing OPENAI_KEY in env")
     }
+
+    /// Configuration for the local fastembed embedder. Arguments take
+    /// precedence over the config file.
+    #[cfg(feature = "fe-local")]
+    pub fn fembed_config(&self) -> chonkit_embedders::fastembed::local::LocalFastEmbedderConfig {
+        use chonkit_embedders::fastembed::local::LocalFastEmbedderConfig;
+
+        let mut config = match self.fembed_config_path() {
+            Some(path) => LocalFastEmbedderConfig::from_file(path),
+            None => LocalFastEmbedderConfig::default(),
+        };
+
+        if let Some(models) = self.fembed_models() {
+            config.models = LocalFastEmbedderConfig::parse_models(&models);
+        }
+        if let Some(models) = self.fembed_sparse_models() {
+            config.sparse_models = LocalFastEmbedderConfig::parse_models(&models);
+        }
+        if let Some(cache_dir) = self.fembed_cache_dir() {
+            config.cache_dir = Some(cache_dir.into());
+        }
+        if let Some(model_dir) = self.fembed_model_dir() {
+            config.model_dir = Some(model_dir.into());
+        }
+        if let Some(idle_timeout) = self.fembed_idle_timeout() {
+            config.idle_timeout = Some(idle_timeout);
+        }
+
+        config
+    }
+
+    #[cfg(feature = "fe-local")]
+    fn fembed_config_path(&self) -> Option<String> {
+        self.fembed_config
+            .clone()
+            .or_else(|| std::env::var("FEMBED_CONFIG").ok())
+    }
+
+    /// Request configuration for the remote embedders. Unset options use the defaults
+    /// from [RequestConfig][chonkit_embedders::request::RequestConfig].
+    #[cfg(any(feature = "openai", feature = "fe-remote"))]
+    pub fn embed_request_config(&self) -> chonkit_embedders::request::RequestConfig {
+        let mut config = chonkit_embedders::request::RequestConfig::default();
+
+        if let Some(batch_size) = self.embed_batch_size() {
+            config.batch_size = batch_size;
+        }
+        if let Some(batch_tokens) = self.embed_batch_tokens() {
+            config.batch_tokens = Some(batch_tokens);
+        }
+        if let Some(max_retries) = self.embed_max_retries() {
+            config.max_retries = max_retries;
+        }
+        if let Some(timeout) = self.embed_timeout() {
+            config.timeout = std::time::Duration::from_secs(timeout);
+        }
+        config.requests_per_minute = self.embed_requests_per_minute();
+        config.tokens_per_minute = self.embed_tokens_per_minute();
+
+        config
+    }
 }
 
 arg!(db_url,          "DATABASE_URL",    panic   "Database url not found; Pass --db-url or set DATABASE_URL");
 arg!(log,             "RUST_LOG",        default "info".to_string());
 arg!(upload_path,     "UPLOAD_PATH",     default DEFAULT_UPLOAD_PATH.to_string());
 arg!(address,         "ADDRESS",         default DEFAULT_ADDRESS.to_string());
+arg!(reconcile,       "RECONCILE",       optional ReconcileMode);
+arg!(llm_endpoint,    "LLM_ENDPOINT",    optional String);
+arg!(llm_model,       "LLM_MODEL",       optional String);
 
 #[cfg(feature = "qdrant")]
 arg!(qdrant_url,      "QDRANT_URL",      panic   "Qdrant url not found; Pass --qdrant-url or set QDRANT_URL");
@@ -173,6 +330,33 @@ arg!(weaviate_url,    "WEAVIATE_URL",    panic   "Weaviate url not found; Pass -
 #[cfg(feature = "fe-remote")]
 arg!(fembed_url,      "FEMBED_URL",      panic   "Fembed url not found; Pass --fembed-url or set FEMBED_URL");
 
+#[cfg(feature = "openai")]
+arg!(openai_endpoint,           "OPENAI_ENDPOINT",           optional String);
+
+#[cfg(feature = "fe-local")]
+arg!(fembed_models,             "FEMBED_MODELS",             optional String);
+#[cfg(feature = "fe-local")]
+arg!(fembed_sparse_models,      "FEMBED_SPARSE_MODELS",      optional String);
+#[cfg(feature = "fe-local")]
+arg!(fembed_cache_dir,          "FEMBED_CACHE_DIR",          optional String);
+#[cfg(feature = "fe-local")]
+arg!(fembed_model_dir,          "FEMBED_MODEL_DIR",          optional String);
+#[cfg(feature = "fe-local")]
+arg!(fembed_idle_timeout,       "FEMBED_IDLE_TIMEOUT",       optional u64);
+
+#[cfg(any(feature = "openai", feature = "fe-remote"))]
+arg!(embed_batch_size,          "EMBED_BATCH_SIZE",          optional usize);
+#[cfg(any(feature = "openai", feature = "fe-remote"))]
+arg!(embed_batch_tokens,        "EMBED_BATCH_TOKENS",        optional usize);
+#[cfg(any(feature = "openai", feature = "fe-remote"))]
+arg!(embed_max_retries,         "EMBED_MAX_RETRIES",         optional u32);
+#[cfg(any(feature = "openai", feature = "fe-remote"))]
+arg!(embed_timeout,             "EMBED_TIMEOUT",             optional u64);
+#[cfg(any(feature = "openai", feature = "fe-remote"))]
+arg!(embed_requests_per_minute, "EMBED_REQUESTS_PER_MINUTE", optional usize);
+#[cfg(any(feature = "openai", feature = "fe-remote"))]
+arg!(embed_tokens_per_minute,   "EMBED_TOKENS_PER_MINUTE",   optional usize);
+
 #[cfg(feature = "auth-vault")]
 arg!(vault_url,  "VAULT_URL",   panic "Vault url not found; Pass --vault-url or set VAULT_URL");
 #[cfg(feature = "auth-vault")]
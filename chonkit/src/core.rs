@@ -4,8 +4,12 @@
 pub mod chunk;
 pub mod document;
 pub mod embedder;
+pub mod eval;
+pub mod generator;
 pub mod model;
 pub mod provider;
 pub mod repo;
+pub mod rule;
+pub mod search;
 pub mod service;
 pub mod vector;
@@ -1,7 +1,11 @@
-use super::embedder::Embedder;
+use super::{
+    document::{parser::ParseConfig, sha256},
+    embedder::{Embedder, InputKind},
+};
 use crate::error::ChonkitError;
 use chunx::ChunkerError;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +13,7 @@ pub enum ChunkConfig {
     Sliding(SlidingWindowConfig),
     Snapping(SnappingWindowConfig),
     Semantic(SemanticWindowConfig),
+    Markdown(MarkdownWindowConfig),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -43,6 +48,12 @@ pub struct SemanticWindowConfig {
     pub embedding_provider: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownWindowConfig {
+    pub size: usize,
+}
+
 impl ChunkConfig {
     /// Create a `SlidingWindow` chunker.
     ///
@@ -138,6 +149,19 @@ impl ChunkConfig {
         };
         Self::Semantic(config)
     }
+
+    /// Create a `MarkdownWindow` chunker.
+    ///
+    /// * `size`: Maximum chunk size, sections larger than this are split by paragraph.
+    pub fn markdown(size: usize) -> Result<Self, ChunkerError> {
+        Ok(Self::Markdown(MarkdownWindowConfig { size }))
+    }
+
+    /// Create a default `MarkdownWindow` chunker.
+    pub fn markdown_default() -> Self {
+        let config = chunx::MarkdownWindow::default();
+        Self::Markdown(MarkdownWindowConfig { size: config.size })
+    }
 }
 
 impl std::fmt::Display for ChunkConfig {
@@ -146,6 +170,7 @@ impl std::fmt::Display for ChunkConfig {
             Self::Sliding(_) => write!(f, "SlidingWindow"),
             Self::Snapping(_) => write!(f, "SnappingWindow"),
             Self::Semantic(_) => write!(f, "SemanticWindow"),
+            Self::Markdown(_) => write!(f, "MarkdownWindow"),
         }
     }
 }
@@ -166,13 +191,290 @@ impl ChunkedDocument<'_> {
     }
 }
 
+/// Fingerprint of the inputs producing a document's chunks. Chunks with the same
+/// fingerprint can be reused instead of parsing and chunking the document again.
+///
+/// * `document_hash`: Hash of the document's content.
+/// * `parse_config`: Parsing configuration, `None` if the default parser is used.
+/// * `chunk_config`: Chunking configuration.
+pub fn config_fingerprint(
+    document_hash: &str,
+    parse_config: Option<&ParseConfig>,
+    chunk_config: &ChunkConfig,
+) -> String {
+    let configs = serde_json::json!([document_hash, parse_config, chunk_config]);
+    sha256(configs.to_string().as_bytes())
+}
+
+/// Find the byte offsets of the chunks in the content they were created from.
+/// Chunks are expected in order, but may overlap. Chunks not found in the content,
+/// e.g. due to the chunker changing them, have no offsets.
+///
+/// * `content`: The chunked content.
+/// * `chunks`: The chunks, in order.
+pub fn locate_chunks(content: &str, chunks: &[&str]) -> Vec<Option<(usize, usize)>> {
+    let mut cursor = 0;
+
+    chunks
+        .iter()
+        .map(|chunk| {
+            let start = cursor + content[cursor..].find(chunk)?;
+
+            // Next chunks start after this one's start, allowing for overlap
+            cursor = start + chunk.chars().next().map_or(0, char::len_utf8);
+
+            Some((start, start + chunk.len()))
+        })
+        .collect()
+}
+
+/// An edit of a manually curated chunk list. Indices refer to the list as it is when
+/// the edit is applied, i.e. after applying the preceding edits.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum ChunkEdit {
+    /// Replace the content of a chunk.
+    Update { index: usize, content: String },
+
+    /// Merge a chunk with the one following it. The contents are joined with the
+    /// separator, a space by default.
+    Merge {
+        index: usize,
+        separator: Option<String>,
+    },
+
+    /// Split a chunk in two at a byte offset of its content.
+    Split { index: usize, at: usize },
+
+    /// Remove a chunk.
+    Delete { index: usize },
+
+    /// Insert a new chunk at the index, shifting the chunks after it.
+    /// The index can be equal to the amount of chunks to append one.
+    Insert { index: usize, content: String },
+}
+
+/// A chunk of a manually curated chunk list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CuratedChunk {
+    /// ID of the stored chunk, `None` if the chunk was created by an edit.
+    /// Kept when the chunk is updated, merged into, or split.
+    pub id: Option<Uuid>,
+
+    pub content: String,
+}
+
+impl CuratedChunk {
+    pub fn new(content: String) -> Self {
+        Self { id: None, content }
+    }
+}
+
+/// Apply the edits to a chunk list in order. Either all edits are applied or none are.
+/// Returns a description of the first invalid edit on error.
+///
+/// * `chunks`: The chunk list to edit.
+/// * `edits`: The edits to apply.
+pub fn apply_chunk_edits(
+    chunks: &mut Vec<CuratedChunk>,
+    edits: &[ChunkEdit],
+) -> Result<(), String> {
+    let mut edited = chunks.clone();
+
+    for (i, edit) in edits.iter().enumerate() {
+        let len = edited.len();
+
+        let (index, in_range) = match edit {
+            ChunkEdit::Insert { index, .. } => (*index, *index <= len),
+            ChunkEdit::Merge { index, .. } => (*index, *index + 1 < len),
+            ChunkEdit::Update { index, .. }
+            | ChunkEdit::Split { index, .. }
+            | ChunkEdit::Delete { index } => (*index, *index < len),
+        };
+
+        if !in_range {
+            return Err(format!(
+                "Edit {i}: chunk index {index} out of range for {len} chunk(s)"
+            ));
+        }
+
+        match edit {
+            ChunkEdit::Update { index, content } | ChunkEdit::Insert { index, content }
+                if content.trim().is_empty() =>
+            {
+                return Err(format!("Edit {i}: chunk {index} cannot be empty"));
+            }
+            ChunkEdit::Update { index, content } => {
+                edited[*index].content = content.clone();
+            }
+            ChunkEdit::Insert { index, content } => {
+                edited.insert(*index, CuratedChunk::new(content.clone()));
+            }
+            ChunkEdit::Merge { index, separator } => {
+                let next = edited.remove(*index + 1);
+                let chunk = &mut edited[*index];
+                chunk.content.push_str(separator.as_deref().unwrap_or(" "));
+                chunk.content.push_str(&next.content);
+            }
+            ChunkEdit::Split { index, at } => {
+                let content = &edited[*index].content;
+
+                let valid = *at > 0 && *at < content.len() && content.is_char_boundary(*at);
+                let (head, tail) = content.split_at(if valid { *at } else { 0 });
+
+                if !valid || head.trim().is_empty() || tail.trim().is_empty() {
+                    return Err(format!(
+                        "Edit {i}: cannot split chunk {index} at {at} into two non-empty chunks"
+                    ));
+                }
+
+                let tail = CuratedChunk::new(tail.to_string());
+                edited[*index].content.truncate(*at);
+                edited.insert(*index + 1, tail);
+            }
+            ChunkEdit::Delete { index } => {
+                edited.remove(*index);
+            }
+        }
+    }
+
+    *chunks = edited;
+
+    Ok(())
+}
+
 pub struct SemanticEmbedder(pub std::sync::Arc<dyn Embedder + Send + Sync>);
 
 impl chunx::semantic::Embedder for SemanticEmbedder {
     type Error = ChonkitError;
 
     async fn embed(&self, input: &[&str], model: &str) -> Result<Vec<Vec<f64>>, Self::Error> {
-        let embeddings = self.0.embed(input, model).await?;
+        let embeddings = self.0.embed_as(input, model, InputKind::Document).await?;
         Ok(embeddings)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_overlapping_chunks() {
+        let content = "Hello world. Hello again.";
+        let chunks = ["Hello world.", "world. Hello", "Hello again.", "missing"];
+
+        let offsets = locate_chunks(content, &chunks);
+
+        assert_eq!(
+            vec![Some((0, 12)), Some((6, 18)), Some((13, 25)), None],
+            offsets
+        );
+    }
+
+    #[test]
+    fn locates_repeated_chunks() {
+        let content = "abcabc";
+        let offsets = locate_chunks(content, &["abc", "abc"]);
+        assert_eq!(vec![Some((0, 3)), Some((3, 6))], offsets);
+    }
+
+    #[test]
+    fn fingerprints_configs() {
+        let sliding = ChunkConfig::sliding_default();
+        let snapping = ChunkConfig::snapping_default();
+
+        let a = config_fingerprint("hash", None, &sliding);
+
+        assert_eq!(a, config_fingerprint("hash", None, &sliding));
+        assert_ne!(a, config_fingerprint("other", None, &sliding));
+        assert_ne!(a, config_fingerprint("hash", None, &snapping));
+        assert_ne!(
+            a,
+            config_fingerprint("hash", Some(&ParseConfig::default()), &sliding)
+        );
+    }
+
+    fn curated(contents: &[&str]) -> Vec<CuratedChunk> {
+        contents
+            .iter()
+            .map(|content| CuratedChunk {
+                id: Some(Uuid::new_v4()),
+                content: content.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn applies_chunk_edits() {
+        let original = curated(&["Hello", "world.", "Bad chunk", "Goodbye world."]);
+        let mut chunks = original.clone();
+
+        let edits = [
+            ChunkEdit::Merge {
+                index: 0,
+                separator: None,
+            },
+            ChunkEdit::Delete { index: 1 },
+            ChunkEdit::Split { index: 1, at: 8 },
+            ChunkEdit::Update {
+                index: 2,
+                content: "world!".to_string(),
+            },
+            ChunkEdit::Insert {
+                index: 3,
+                content: "The end.".to_string(),
+            },
+        ];
+
+        apply_chunk_edits(&mut chunks, &edits).unwrap();
+
+        let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(
+            vec!["Hello world.", "Goodbye ", "world!", "The end."],
+            contents
+        );
+
+        // Merged and split chunks keep their IDs, new chunks have none
+        assert_eq!(original[0].id, chunks[0].id);
+        assert_eq!(original[3].id, chunks[1].id);
+        assert_eq!(None, chunks[2].id);
+        assert_eq!(None, chunks[3].id);
+    }
+
+    #[test]
+    fn rejects_invalid_chunk_edits() {
+        let original = curated(&["Hello", "world"]);
+
+        let invalid = [
+            ChunkEdit::Delete { index: 2 },
+            ChunkEdit::Merge {
+                index: 1,
+                separator: None,
+            },
+            ChunkEdit::Insert {
+                index: 3,
+                content: "Out of range".to_string(),
+            },
+            ChunkEdit::Update {
+                index: 0,
+                content: "  ".to_string(),
+            },
+            ChunkEdit::Split { index: 0, at: 5 },
+            ChunkEdit::Split { index: 0, at: 0 },
+        ];
+
+        for edit in invalid {
+            let mut chunks = original.clone();
+            let edits = [ChunkEdit::Delete { index: 0 }, edit.clone()];
+            assert!(
+                apply_chunk_edits(&mut chunks, &edits[1..]).is_err(),
+                "{edit:?}"
+            );
+
+            // Nothing is applied if any edit fails
+            let mut chunks = original.clone();
+            let _ = apply_chunk_edits(&mut chunks, &[edits[0].clone(), edit]);
+            assert_eq!(original, chunks);
+        }
+    }
+}
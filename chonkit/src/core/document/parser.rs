@@ -73,6 +73,25 @@ impl ParseConfig {
     }
 }
 
+/// Text content of a document along with the pages it was parsed from.
+#[derive(Debug, Default)]
+pub struct ParsedContent {
+    /// The parsed text.
+    pub text: String,
+
+    /// Byte offsets into `text` at which pages start, paired with their page
+    /// numbers starting from 1. Empty for documents that have no pages.
+    pub pages: Vec<(usize, usize)>,
+}
+
+impl ParsedContent {
+    /// Get the number of the page the byte at `offset` is located on.
+    pub fn page_at(&self, offset: usize) -> Option<usize> {
+        let i = self.pages.partition_point(|(start, _)| *start <= offset);
+        self.pages.get(i.checked_sub(1)?).map(|(_, page)| *page)
+    }
+}
+
 /// Enumeration of all supported parser types.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -110,4 +129,15 @@ impl Parser {
             Self::Docx(p) => p.parse(input),
         }
     }
+
+    /// Parse the input and keep track of its pages, if the document type has them.
+    pub fn parse_with_pages(&self, input: &[u8]) -> Result<ParsedContent, ChonkitError> {
+        match self {
+            Self::Pdf(p) => p.parse_with_pages(input),
+            _ => Ok(ParsedContent {
+                text: self.parse(input)?,
+                pages: vec![],
+            }),
+        }
+    }
 }
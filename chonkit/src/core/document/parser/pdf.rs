@@ -1,4 +1,4 @@
-use super::ParseConfig;
+use super::{ParseConfig, ParsedContent};
 use crate::{err, error::ChonkitError, map_err};
 use pdfium_render::prelude::Pdfium;
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,11 @@ impl PdfParser {
 
 impl PdfParser {
     pub fn parse(&self, input: &[u8]) -> Result<String, ChonkitError> {
+        self.parse_with_pages(input).map(|parsed| parsed.text)
+    }
+
+    /// Parse the PDF and keep track of where each page starts in the output.
+    pub fn parse_with_pages(&self, input: &[u8]) -> Result<ParsedContent, ChonkitError> {
         let _start = Instant::now();
 
         let ParseConfig {
@@ -37,6 +42,7 @@ impl PdfParser {
         let input = map_err!(pdfium.load_pdf_from_byte_slice(input, None));
 
         let mut out = String::new();
+        let mut page_offsets = vec![];
 
         let pages = input.pages();
 
@@ -65,6 +71,8 @@ impl PdfParser {
             // page_num is 0 based
             let text = map_err!(page.text());
 
+            page_offsets.push((out.len(), page_num + 1));
+
             'lines: for line in text.all().lines() {
                 let line = line.trim();
 
@@ -102,6 +110,9 @@ impl PdfParser {
             );
         }
 
-        Ok(out)
+        Ok(ParsedContent {
+            text: out,
+            pages: page_offsets,
+        })
     }
 }
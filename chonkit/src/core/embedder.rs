@@ -1,4 +1,29 @@
-use crate::error::ChonkitError;
+use crate::{err, error::ChonkitError};
+use prompt::PromptTemplate;
+
+pub mod prompt;
+
+/// The kind of input being embedded.
+/// Some models expect different prompts for queries and the documents they are matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// A search query.
+    Query,
+
+    /// A document chunk.
+    Document,
+}
+
+/// Output of sparse models such as SPLADE. Only the non-zero
+/// dimensions of the vector are stored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SparseEmbedding {
+    /// Dimensions with non-zero values.
+    pub indices: Vec<u32>,
+
+    /// Values of the dimensions in `indices`.
+    pub values: Vec<f32>,
+}
 
 /// Operations related to embeddings and their models.
 #[async_trait::async_trait]
@@ -32,4 +57,57 @@ pub trait Embedder {
     /// * `content`: The text to embed.
     /// * `model`: The embedding model to use.
     async fn embed(&self, content: &[&str], model: &str) -> Result<Vec<Vec<f64>>, ChonkitError>;
+
+    /// Return the prompt template the given model expects, if any.
+    /// Defaults to the [prompt template registry][prompt::template].
+    ///
+    /// * `model`: The embedding model.
+    fn prompt_template(&self, model: &str) -> Option<PromptTemplate> {
+        prompt::template(model)
+    }
+
+    /// Get the vectors for the elements in `content`, applying the
+    /// model's prompt template for the given kind of input.
+    ///
+    /// * `content`: The text to embed.
+    /// * `model`: The embedding model to use.
+    /// * `kind`: Whether the content is a query or a document.
+    async fn embed_as(
+        &self,
+        content: &[&str],
+        model: &str,
+        kind: InputKind,
+    ) -> Result<Vec<Vec<f64>>, ChonkitError> {
+        let Some(template) = self.prompt_template(model) else {
+            return self.embed(content, model).await;
+        };
+
+        let content = template.apply(content, kind);
+        let content: Vec<&str> = content.iter().map(|s| s.as_str()).collect();
+
+        self.embed(&content, model).await
+    }
+
+    /// List all available sparse models in the embedder.
+    /// Embedders without sparse models return an empty list.
+    async fn list_sparse_models(&self) -> Result<Vec<String>, ChonkitError> {
+        Ok(vec![])
+    }
+
+    /// Get the sparse vectors for the elements in `content`.
+    /// Errors if the embedder does not support sparse models.
+    ///
+    /// * `content`: The text to embed.
+    /// * `model`: The sparse model to use.
+    async fn embed_sparse(
+        &self,
+        _content: &[&str],
+        model: &str,
+    ) -> Result<Vec<SparseEmbedding>, ChonkitError> {
+        let id = self.id();
+        err!(
+            InvalidEmbeddingModel,
+            "Sparse model '{model}' not supported by embedder '{id}'"
+        )
+    }
 }
@@ -0,0 +1,112 @@
+//! Prompt templates for models trained with instructions or prefixes
+//! distinguishing queries from the documents they are matched against.
+
+use super::InputKind;
+
+/// Instruction used by the English BGE models for queries.
+const BGE_EN_QUERY: &str = "Represent this sentence for searching relevant passages: ";
+
+/// Instruction used by the Chinese BGE models for queries.
+const BGE_ZH_QUERY: &str = "为这个句子生成表示以用于检索相关文章：";
+
+/// Prefixes prepended to the input before embedding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptTemplate {
+    /// Prepended to search queries.
+    pub query: &'static str,
+
+    /// Prepended to document chunks.
+    pub document: &'static str,
+}
+
+impl PromptTemplate {
+    pub const fn new(query: &'static str, document: &'static str) -> Self {
+        Self { query, document }
+    }
+
+    /// Return the prefix for the given input kind.
+    pub fn prefix(&self, kind: InputKind) -> &'static str {
+        match kind {
+            InputKind::Query => self.query,
+            InputKind::Document => self.document,
+        }
+    }
+
+    /// Prepend the prefix for `kind` to every element of `input`.
+    pub fn apply(&self, input: &[&str], kind: InputKind) -> Vec<String> {
+        let prefix = self.prefix(kind);
+        input.iter().map(|i| format!("{prefix}{i}")).collect()
+    }
+}
+
+/// Find the prompt template for the model, if it is known to use one.
+///
+/// Models are matched by the segments of their name, e.g. `intfloat/multilingual-e5-large`
+/// matches the e5 template, while `BAAI/bge-m3` does not use any.
+///
+/// * `model`: The embedding model name.
+pub fn template(model: &str) -> Option<PromptTemplate> {
+    let model = model.to_lowercase();
+    let segments: Vec<&str> = model.split(['/', '-', '_', '.']).collect();
+    let has = |segment: &str| segments.contains(&segment);
+
+    if has("e5") {
+        return Some(PromptTemplate::new("query: ", "passage: "));
+    }
+
+    if has("nomic") {
+        return Some(PromptTemplate::new("search_query: ", "search_document: "));
+    }
+
+    if has("bge") && !has("m3") {
+        if has("zh") {
+            return Some(PromptTemplate::new(BGE_ZH_QUERY, ""));
+        }
+        return Some(PromptTemplate::new(BGE_EN_QUERY, ""));
+    }
+
+    if has("mxbai") || (has("snowflake") && has("arctic")) {
+        return Some(PromptTemplate::new(BGE_EN_QUERY, ""));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_known_models() {
+        let e5 = template("intfloat/multilingual-e5-large").unwrap();
+        assert_eq!("query: ", e5.prefix(InputKind::Query));
+        assert_eq!("passage: ", e5.prefix(InputKind::Document));
+
+        let nomic = template("nomic-ai/nomic-embed-text-v1.5").unwrap();
+        assert_eq!("search_query: ", nomic.prefix(InputKind::Query));
+        assert_eq!("search_document: ", nomic.prefix(InputKind::Document));
+
+        let bge = template("Xenova/bge-base-en-v1.5").unwrap();
+        assert_eq!(BGE_EN_QUERY, bge.prefix(InputKind::Query));
+        assert_eq!("", bge.prefix(InputKind::Document));
+
+        let bge_zh = template("Xenova/bge-small-zh-v1.5").unwrap();
+        assert_eq!(BGE_ZH_QUERY, bge_zh.prefix(InputKind::Query));
+    }
+
+    #[test]
+    fn skips_unknown_models() {
+        assert!(template("BAAI/bge-m3").is_none());
+        assert!(template("Qdrant/all-MiniLM-L6-v2-onnx").is_none());
+        assert!(template("text-embedding-3-large").is_none());
+    }
+
+    #[test]
+    fn applies_prefix() {
+        let e5 = template("intfloat/e5-large-v2").unwrap();
+        assert_eq!(
+            vec!["passage: foo".to_string(), "passage: bar".to_string()],
+            e5.apply(&["foo", "bar"], InputKind::Document)
+        );
+    }
+}
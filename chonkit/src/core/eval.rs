@@ -0,0 +1,366 @@
+//! Retrieval quality metrics and chunking configuration sweeps.
+
+use super::chunk::{ChunkConfig, SlidingWindowConfig};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Retrieval metrics of a single question or averaged over multiple questions.
+///
+/// The targets of a question are its expected documents and spans. A retrieved chunk is
+/// relevant if it belongs to an expected document or contains an expected span, or is
+/// contained in one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetrievalMetrics {
+    /// Share of targets found in the top `k` chunks.
+    pub recall: f64,
+
+    /// Share of relevant chunks in the top `k` chunks.
+    pub precision: f64,
+
+    /// Reciprocal rank of the first relevant chunk, 0 if none were retrieved.
+    pub mrr: f64,
+
+    /// Normalized discounted cumulative gain. Only chunks finding a target
+    /// that was not found by a better ranked chunk count as gains.
+    pub ndcg: f64,
+}
+
+impl RetrievalMetrics {
+    /// Average the metrics of multiple questions.
+    pub fn mean(metrics: &[RetrievalMetrics]) -> Self {
+        if metrics.is_empty() {
+            return Self::default();
+        }
+
+        let n = metrics.len() as f64;
+        let sum = metrics.iter().fold(Self::default(), |acc, m| Self {
+            recall: acc.recall + m.recall,
+            precision: acc.precision + m.precision,
+            mrr: acc.mrr + m.mrr,
+            ndcg: acc.ndcg + m.ndcg,
+        });
+
+        Self {
+            recall: sum.recall / n,
+            precision: sum.precision / n,
+            mrr: sum.mrr / n,
+            ndcg: sum.ndcg / n,
+        }
+    }
+
+    pub fn get(&self, metric: EvalMetric) -> f64 {
+        match metric {
+            EvalMetric::Recall => self.recall,
+            EvalMetric::Precision => self.precision,
+            EvalMetric::Mrr => self.mrr,
+            EvalMetric::Ndcg => self.ndcg,
+        }
+    }
+}
+
+/// A single retrieval metric, used to rank evaluation results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum EvalMetric {
+    Recall,
+    Precision,
+    Mrr,
+    #[default]
+    Ndcg,
+}
+
+/// A chunk retrieved for a question.
+#[derive(Debug, Clone, Copy)]
+pub struct Retrieved<'a> {
+    pub document_id: Option<Uuid>,
+    pub content: &'a str,
+}
+
+/// Score the chunks retrieved for a question.
+///
+/// * `documents`: Expected documents.
+/// * `spans`: Expected text spans.
+/// * `retrieved`: Retrieved chunks, best first.
+/// * `k`: Amount of chunks to score.
+pub fn score(
+    documents: &[Uuid],
+    spans: &[String],
+    retrieved: &[Retrieved<'_>],
+    k: usize,
+) -> RetrievalMetrics {
+    let targets = documents.len() + spans.len();
+
+    if targets == 0 || k == 0 {
+        return RetrievalMetrics::default();
+    }
+
+    let mut found_documents = vec![false; documents.len()];
+    let mut found_spans = vec![false; spans.len()];
+
+    let mut relevant = 0;
+    let mut first_relevant = None;
+    let mut dcg = 0.;
+
+    for (rank, chunk) in retrieved.iter().take(k).enumerate() {
+        let mut is_relevant = false;
+        let mut is_new = false;
+
+        for (i, document) in documents.iter().enumerate() {
+            if chunk.document_id == Some(*document) {
+                is_relevant = true;
+                is_new |= !found_documents[i];
+                found_documents[i] = true;
+            }
+        }
+
+        for (i, span) in spans.iter().enumerate() {
+            if chunk.content.contains(span.as_str()) || span.contains(chunk.content) {
+                is_relevant = true;
+                is_new |= !found_spans[i];
+                found_spans[i] = true;
+            }
+        }
+
+        if !is_relevant {
+            continue;
+        }
+
+        relevant += 1;
+        first_relevant.get_or_insert(rank + 1);
+
+        if is_new {
+            dcg += discount(rank + 1);
+        }
+    }
+
+    let found = found_documents
+        .iter()
+        .chain(found_spans.iter())
+        .filter(|found| **found)
+        .count();
+
+    let idcg: f64 = (1..=targets.min(k)).map(discount).sum();
+
+    RetrievalMetrics {
+        recall: found as f64 / targets as f64,
+        precision: relevant as f64 / k as f64,
+        mrr: first_relevant.map_or(0., |rank| 1. / rank as f64),
+        ndcg: dcg / idcg,
+    }
+}
+
+/// Chunker types a sweep can explore.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SweepChunker {
+    Sliding,
+    Snapping,
+    Semantic,
+}
+
+/// A grid of chunking configurations. Every combination of the chunker types and
+/// their applicable parameters is a candidate.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepGrid {
+    pub chunkers: Vec<SweepChunker>,
+
+    /// Chunk sizes, applicable to all chunkers.
+    pub sizes: Vec<usize>,
+
+    /// Chunk overlaps, applicable to sliding and snapping chunkers.
+    /// Overlaps greater or equal to the size are skipped.
+    #[serde(default)]
+    pub overlaps: Vec<usize>,
+
+    /// Similarity thresholds, applicable to semantic chunkers.
+    #[serde(default)]
+    pub thresholds: Vec<f64>,
+}
+
+impl SweepGrid {
+    /// Expand the grid to chunking configurations. Chunker defaults are used for
+    /// parameters not in the grid.
+    ///
+    /// * `semantic`: Embedding provider and model used by semantic chunkers.
+    ///   Semantic chunkers are skipped if not given.
+    pub fn candidates(&self, semantic: Option<(&str, &str)>) -> Vec<ChunkConfig> {
+        let mut candidates = vec![];
+
+        for chunker in self.chunkers.iter() {
+            for size in self.sizes.iter().copied() {
+                match chunker {
+                    SweepChunker::Sliding => {
+                        for overlap in self.overlaps.iter().copied() {
+                            if overlap < size {
+                                candidates.push(ChunkConfig::Sliding(SlidingWindowConfig {
+                                    size,
+                                    overlap,
+                                }));
+                            }
+                        }
+                    }
+                    SweepChunker::Snapping => {
+                        for overlap in self.overlaps.iter().copied() {
+                            if overlap >= size {
+                                continue;
+                            }
+
+                            let mut config = ChunkConfig::snapping_default();
+                            if let ChunkConfig::Snapping(ref mut config) = config {
+                                config.size = size;
+                                config.overlap = overlap;
+                            }
+
+                            candidates.push(config);
+                        }
+                    }
+                    SweepChunker::Semantic => {
+                        let Some((provider, model)) = semantic else {
+                            continue;
+                        };
+
+                        for threshold in self.thresholds.iter().copied() {
+                            let mut config = ChunkConfig::semantic_default(
+                                provider.to_string(),
+                                model.to_string(),
+                            );
+
+                            if let ChunkConfig::Semantic(ref mut config) = config {
+                                config.size = size;
+                                config.threshold = threshold;
+                            }
+
+                            candidates.push(config);
+                        }
+                    }
+                }
+            }
+        }
+
+        candidates
+    }
+}
+
+/// Logarithmic discount of the gain at `rank`, starting from 1.
+fn discount(rank: usize) -> f64 {
+    1. / (rank as f64 + 1.).log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(document_id: Uuid, content: &str) -> Retrieved<'_> {
+        Retrieved {
+            document_id: Some(document_id),
+            content,
+        }
+    }
+
+    #[test]
+    fn scores_perfect_retrieval() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let retrieved = [chunk(a, "alpha"), chunk(b, "beta")];
+
+        let metrics = score(&[a, b], &[], &retrieved, 2);
+
+        assert_eq!(1., metrics.recall);
+        assert_eq!(1., metrics.precision);
+        assert_eq!(1., metrics.mrr);
+        assert!((metrics.ndcg - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scores_partial_retrieval() {
+        let (a, b, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let retrieved = [
+            chunk(other, "unrelated"),
+            chunk(a, "alpha one"),
+            // Same target again, relevant but no gain
+            chunk(a, "alpha two"),
+            chunk(other, "the beta span is here"),
+        ];
+
+        let metrics = score(&[a], &["beta span".to_string()], &retrieved, 4);
+
+        assert_eq!(1., metrics.recall);
+        assert_eq!(0.75, metrics.precision);
+        assert_eq!(0.5, metrics.mrr);
+
+        let dcg = 1. / 3f64.log2() + 1. / 5f64.log2();
+        let idcg = 1. + 1. / 3f64.log2();
+        assert!((metrics.ndcg - dcg / idcg).abs() < 1e-9);
+
+        // Only the top 1 is considered
+        let metrics = score(&[a, b], &[], &retrieved, 1);
+        assert_eq!(0., metrics.recall);
+        assert_eq!(0., metrics.mrr);
+        assert_eq!(0., metrics.ndcg);
+    }
+
+    #[test]
+    fn averages_metrics() {
+        let metrics = [
+            RetrievalMetrics {
+                recall: 1.,
+                precision: 0.5,
+                mrr: 1.,
+                ndcg: 1.,
+            },
+            RetrievalMetrics::default(),
+        ];
+
+        let mean = RetrievalMetrics::mean(&metrics);
+
+        assert_eq!(0.5, mean.recall);
+        assert_eq!(0.25, mean.precision);
+        assert_eq!(0.5, mean.mrr);
+        assert_eq!(0.5, mean.ndcg);
+    }
+
+    #[test]
+    fn expands_sweep_grid() {
+        let grid = SweepGrid {
+            chunkers: vec![
+                SweepChunker::Sliding,
+                SweepChunker::Snapping,
+                SweepChunker::Semantic,
+            ],
+            sizes: vec![100, 500],
+            overlaps: vec![0, 100],
+            thresholds: vec![0.5, 0.9],
+        };
+
+        let candidates = grid.candidates(Some(("fembed", "model")));
+
+        // Overlap 100 is skipped for size 100
+        let sliding = candidates
+            .iter()
+            .filter(|c| matches!(c, ChunkConfig::Sliding(_)))
+            .count();
+        let snapping = candidates
+            .iter()
+            .filter(|c| matches!(c, ChunkConfig::Snapping(_)))
+            .count();
+        let semantic = candidates
+            .iter()
+            .filter(|c| matches!(c, ChunkConfig::Semantic(_)))
+            .count();
+
+        assert_eq!(3, sliding);
+        assert_eq!(3, snapping);
+        assert_eq!(4, semantic);
+
+        let ChunkConfig::Semantic(ref config) = candidates[6] else {
+            panic!("expected semantic config");
+        };
+        assert_eq!(100, config.size);
+        assert_eq!(0.5, config.threshold);
+        assert_eq!("fembed", config.embedding_provider);
+
+        // Semantic candidates require an embedding model
+        assert_eq!(6, grid.candidates(None).len());
+    }
+}
@@ -0,0 +1,142 @@
+//! Text generation with language models, used for building evaluation datasets.
+
+use crate::error::ChonkitError;
+
+/// Default system prompt for generating questions.
+pub const DEFAULT_QUESTION_SYSTEM_PROMPT: &str = "You write questions for evaluating a document \
+search system. Every question must be answerable using only the given passage and must not \
+refer to the passage itself. Respond with one question per line and nothing else.";
+
+/// Default prompt for generating questions. See [render_prompt] for the placeholders.
+pub const DEFAULT_QUESTION_PROMPT: &str =
+    "Document: {document}\n\nPassage:\n{chunk}\n\nWrite {count} question(s) answerable by the passage.";
+
+/// Operations for generating text with a chat model.
+#[async_trait::async_trait]
+pub trait Generator {
+    /// Return the generator's identifier.
+    fn id(&self) -> &'static str;
+
+    /// The model used when none is specified.
+    fn default_model(&self) -> Option<&str>;
+
+    /// Generate a response to the prompt.
+    ///
+    /// * `model`: Chat model to use.
+    /// * `system`: System prompt.
+    /// * `prompt`: User prompt.
+    async fn chat(&self, model: &str, system: &str, prompt: &str) -> Result<String, ChonkitError>;
+}
+
+/// Replace the placeholders in a question prompt template.
+///
+/// * `{chunk}`: The chunk to generate questions for.
+/// * `{document}`: Name of the chunk's document.
+/// * `{count}`: Amount of questions to generate.
+pub fn render_prompt(template: &str, chunk: &str, document: &str, count: usize) -> String {
+    // The chunk is replaced last so placeholders in its content are kept as is
+    template
+        .replace("{document}", document)
+        .replace("{count}", &count.to_string())
+        .replace("{chunk}", chunk)
+}
+
+/// Extract questions from a response containing one question per line.
+/// Numbering, bullets and surrounding quotes are removed.
+///
+/// * `response`: Model response.
+/// * `count`: Maximum amount of questions to return.
+pub fn parse_questions(response: &str, count: usize) -> Vec<String> {
+    response
+        .lines()
+        .map(|line| {
+            let line = strip_list_marker(line.trim());
+            let line = line.strip_prefix("Q:").unwrap_or(line).trim();
+            line.trim_matches(['"', '\'']).trim()
+        })
+        .filter(|line| !line.is_empty())
+        .take(count)
+        .map(String::from)
+        .collect()
+}
+
+/// Strip a list marker followed by whitespace from the start of the line,
+/// i.e. a number followed by `.` or `)`, or a `-`, `*` or `•` bullet.
+/// Lines starting with numbers that are not list markers are kept as they are.
+fn strip_list_marker(line: &str) -> &str {
+    let rest = line.trim_start_matches(|c: char| c.is_ascii_digit());
+
+    let marker = if rest.len() < line.len() {
+        rest.strip_prefix(['.', ')'])
+    } else {
+        line.strip_prefix(['-', '*', '•'])
+    };
+
+    match marker {
+        Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+        _ => line,
+    }
+}
+
+/// Pick `count` evenly spaced indices out of `len`, always including the first one.
+pub fn sample_indices(len: usize, count: usize) -> Vec<usize> {
+    if count >= len {
+        return (0..len).collect();
+    }
+
+    (0..count).map(|i| i * len / count).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_prompt() {
+        let prompt = render_prompt(DEFAULT_QUESTION_PROMPT, "Penguins {count}", "birds.txt", 2);
+        assert_eq!(
+            "Document: birds.txt\n\nPassage:\nPenguins {count}\n\nWrite 2 question(s) answerable by the passage.",
+            prompt
+        );
+    }
+
+    #[test]
+    fn parses_questions() {
+        let response = "1. Where do penguins live?\n\n2) \"What do penguins eat?\"\n- Q: Can penguins fly?\n* How tall are penguins?";
+
+        assert_eq!(
+            vec![
+                "Where do penguins live?",
+                "What do penguins eat?",
+                "Can penguins fly?",
+            ],
+            parse_questions(response, 3)
+        );
+    }
+
+    #[test]
+    fn keeps_questions_starting_with_numbers() {
+        let response = "1. 2024 revenue?
+2) 3D printers?
+3.5 million users?
+42 is the answer?";
+
+        assert_eq!(
+            vec![
+                "2024 revenue?",
+                "3D printers?",
+                "3.5 million users?",
+                "42 is the answer?",
+            ],
+            parse_questions(response, 4)
+        );
+    }
+
+    #[test]
+    fn samples_evenly() {
+        assert_eq!(vec![0, 1, 2], sample_indices(3, 5));
+        assert_eq!(vec![0, 5], sample_indices(10, 2));
+        assert_eq!(vec![0, 3, 6], sample_indices(10, 3));
+        assert!(sample_indices(0, 3).is_empty());
+    }
+}
@@ -4,12 +4,24 @@ use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 use validify::{field_err, Validate, ValidationError};
 
+/// Document chunk models.
+pub mod chunk;
+
 /// Vector collection models.
 pub mod collection;
 
 /// Document models.
 pub mod document;
 
+/// Retrieval evaluation models.
+pub mod eval;
+
+/// Parsing and chunking preset models.
+pub mod preset;
+
+/// Document configuration rule models.
+pub mod rule;
+
 /// Used to obtain paginated lists with a total number of items in
 /// the tables.
 #[derive(Debug, Serialize)]
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Fingerprint of manually curated chunk lists. Never collides with configuration
+/// fingerprints since those are hex encoded hashes.
+pub const MANUAL_CHUNKS: &str = "manual";
+
+/// Where the chunks a document is embedded with come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ChunkSource {
+    /// Produced by the document's chunking configuration.
+    Automatic,
+
+    /// Saved and curated manually. Used instead of the chunking configuration.
+    Manual,
+}
+
+/// A chunk of a document, as produced by the document's parsing and chunking
+/// configuration at the time it was embedded.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Chunk {
+    pub id: Uuid,
+
+    pub document_id: Uuid,
+
+    /// Fingerprint of the document content and the configurations that produced the chunk.
+    pub config_hash: String,
+
+    /// Hash of the document content the chunk was created from.
+    /// `None` for chunks stored before the hash was tracked.
+    pub document_hash: Option<String>,
+
+    /// Position of the chunk in its document.
+    pub chunk_index: i32,
+
+    /// Byte offset of the chunk's start in the parsed document.
+    /// `None` if the chunker changed the chunk's content.
+    pub start_offset: Option<i32>,
+
+    /// Byte offset of the chunk's end in the parsed document.
+    pub end_offset: Option<i32>,
+
+    pub content: String,
+
+    pub content_hash: String,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct ChunkInsert<'a> {
+    pub id: Uuid,
+    pub chunk_index: i32,
+    pub start_offset: Option<i32>,
+    pub end_offset: Option<i32>,
+    pub content: &'a str,
+    pub content_hash: String,
+}
+
+impl<'a> ChunkInsert<'a> {
+    /// * `index`: Position of the chunk in its document.
+    /// * `content`: Chunk content.
+    /// * `offsets`: Start and end of the chunk in the parsed document, if known.
+    pub fn new(index: usize, content: &'a str, offsets: Option<(usize, usize)>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            chunk_index: index as i32,
+            start_offset: offsets.map(|(start, _)| start as i32),
+            end_offset: offsets.map(|(_, end)| end as i32),
+            content,
+            content_hash: crate::core::document::sha256(content.as_bytes()),
+        }
+    }
+
+    /// Use an existing ID for the chunk, keeping vectors linked to it valid.
+    pub fn with_id(mut self, id: Uuid) -> Self {
+        self.id = id;
+        self
+    }
+}
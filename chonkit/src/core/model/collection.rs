@@ -1,7 +1,8 @@
 use super::document::DocumentShort;
+use crate::core::vector::VectorIndexConfig;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
-use sqlx::prelude::FromRow;
+use sqlx::{prelude::FromRow, types::Json};
 use uuid::Uuid;
 
 /// Vector collection model.
@@ -12,34 +13,55 @@ pub struct Collection {
     pub id: Uuid,
     /// Collection name. Unique in combination with provider.
     pub name: String,
-    /// Embedding model used for the collection.
-    pub model: String,
+    /// Embedding model used for the collection's dense vectors.
+    pub model: Option<String>,
+    /// Embedding model used for the collection's sparse vectors.
+    pub sparse_model: Option<String>,
     /// Embedder provider ID.
     pub embedder: String,
     /// Vector database provider.
     pub provider: String,
+    /// Distance metric and index parameters of the collection.
+    #[schema(value_type = VectorIndexConfig)]
+    pub index_config: Json<VectorIndexConfig>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// At least one of `model` and `sparse_model` must be set.
 pub struct CollectionInsert<'a> {
     pub id: Uuid,
     pub name: &'a str,
-    pub model: &'a str,
+    pub model: Option<&'a str>,
+    pub sparse_model: Option<&'a str>,
     pub embedder: &'a str,
     pub provider: &'a str,
+    pub index_config: VectorIndexConfig,
 }
 
 impl<'a> CollectionInsert<'a> {
-    pub fn new(name: &'a str, model: &'a str, embedder: &'a str, provider: &'a str) -> Self {
+    pub fn new(
+        name: &'a str,
+        model: Option<&'a str>,
+        sparse_model: Option<&'a str>,
+        embedder: &'a str,
+        provider: &'a str,
+    ) -> Self {
         Self {
             id: Uuid::new_v4(),
             name,
             model,
+            sparse_model,
             embedder,
             provider,
+            index_config: VectorIndexConfig::default(),
         }
     }
+
+    pub fn with_index_config(mut self, index_config: VectorIndexConfig) -> Self {
+        self.index_config = index_config;
+        self
+    }
 }
 
 /// Collection struct for display purposes when listing documents.
@@ -48,17 +70,26 @@ impl<'a> CollectionInsert<'a> {
 pub struct CollectionShort {
     pub id: Uuid,
     pub name: String,
-    pub model: String,
+    pub model: Option<String>,
+    pub sparse_model: Option<String>,
     pub embedder: String,
     pub provider: String,
 }
 
 impl CollectionShort {
-    pub fn new(id: Uuid, name: String, model: String, embedder: String, provider: String) -> Self {
+    pub fn new(
+        id: Uuid,
+        name: String,
+        model: Option<String>,
+        sparse_model: Option<String>,
+        embedder: String,
+        provider: String,
+    ) -> Self {
         Self {
             id,
             name,
             model,
+            sparse_model,
             embedder,
             provider,
         }
@@ -106,6 +137,21 @@ pub struct Embedding {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A named alias pointing to a collection.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionAlias {
+    /// Alias name. Unique across all collections.
+    pub name: String,
+
+    /// The collection the alias currently points to.
+    pub collection_id: Uuid,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
 /// DTO for inserting.
 #[derive(Debug)]
 pub struct EmbeddingInsert {
@@ -123,3 +169,64 @@ impl EmbeddingInsert {
         }
     }
 }
+
+/// Result of importing a collection snapshot.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionImport {
+    /// The created collection.
+    pub collection: Collection,
+    /// Documents in the snapshot that do not exist in the repository.
+    /// Their points are not imported.
+    pub skipped_documents: Vec<Uuid>,
+}
+
+/// Differences between the embeddings tracked in the repository
+/// and the vectors stored in the vector databases.
+#[derive(Debug, Default, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReport {
+    /// Vector collections with no corresponding collection in the repository.
+    pub orphaned_collections: Vec<OrphanedCollection>,
+    /// Vectors of documents with no embedding entry in their collection.
+    pub orphaned_vectors: Vec<OrphanedVectors>,
+    /// Collections whose vector collection does not exist in their provider.
+    pub missing_collections: Vec<CollectionShort>,
+    /// Embedding entries with no vectors in their vector collection.
+    pub missing_vectors: Vec<Embedding>,
+    /// Whether the differences were repaired.
+    pub repaired: bool,
+    /// Errors encountered while repairing.
+    pub repair_errors: Vec<String>,
+}
+
+impl ReconciliationReport {
+    /// Returns `true` if the repository and vector databases are in sync.
+    pub fn is_clean(&self) -> bool {
+        self.orphaned_collections.is_empty()
+            && self.orphaned_vectors.is_empty()
+            && self.missing_collections.is_empty()
+            && self.missing_vectors.is_empty()
+    }
+}
+
+/// A vector collection not tracked by the repository.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedCollection {
+    pub name: String,
+    /// Vector database provider.
+    pub provider: String,
+}
+
+/// Vectors of a document with no embedding entry in their collection.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedVectors {
+    pub collection_id: Uuid,
+    pub document_id: Uuid,
+    /// Amount of vectors stored for the document.
+    pub count: usize,
+    /// `false` if the document was deleted, `true` if only its embedding entry is missing.
+    pub document_exists: bool,
+}
@@ -1,12 +1,13 @@
-use super::collection::CollectionShort;
+use super::{chunk::ChunkSource, collection::CollectionShort};
 use crate::{
     core::{chunk::ChunkConfig, document::parser::ParseConfig},
     err,
     error::ChonkitError,
 };
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
+use validify::{schema_err, schema_validation, Validate, ValidationErrors};
 
 pub mod config;
 
@@ -22,6 +23,14 @@ pub struct DocumentConfig {
     pub src: String,
     pub chunk_config: Option<ChunkConfig>,
     pub parse_config: Option<ParseConfig>,
+    /// Whether the document is embedded with chunks from its chunking configuration
+    /// or with a manually curated chunk list.
+    pub chunk_source: ChunkSource,
+    /// Whether the manually curated chunks were saved for a previous version of the
+    /// document's content. Stale chunks are not embedded until they are saved again.
+    pub stale_chunks: bool,
+    /// The preset the configurations are synced with, if any.
+    pub preset_id: Option<uuid::Uuid>,
 }
 
 impl DocumentConfig {
@@ -35,6 +44,9 @@ impl DocumentConfig {
             src: document.src,
             chunk_config: Some(chunk_config),
             parse_config: Some(parse_config),
+            chunk_source: ChunkSource::Automatic,
+            stale_chunks: false,
+            preset_id: None,
         }
     }
 }
@@ -220,10 +232,83 @@ impl<'a> DocumentInsert<'a> {
     }
 }
 
-/// DTO for updating.
-#[derive(Debug)]
+/// DTO for updating. Unset fields are left as is.
+#[derive(Debug, Default, Clone, Copy)]
 pub struct DocumentUpdate<'a> {
     pub name: Option<&'a str>,
+
+    /// An empty label removes the document's label.
     pub label: Option<&'a str>,
-    pub tags: Option<Vec<String>>,
+
+    /// Replaces the document's tags.
+    pub tags: Option<&'a [String]>,
+
+    /// Tags to add to the document, after replacing.
+    pub add_tags: &'a [String],
+
+    /// Tags to remove from the document, after adding.
+    pub remove_tags: &'a [String],
+}
+
+/// Selects documents by their metadata. Unset criteria match any document.
+#[derive(Debug, Default, Clone, Deserialize, Validate, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[validate(Self::validate)]
+pub struct DocumentFilter {
+    /// Document source.
+    pub src: Option<String>,
+
+    /// Document label.
+    pub label: Option<String>,
+
+    /// Documents with any of the tags.
+    pub tags: Vec<String>,
+
+    /// Documents with all of the tags.
+    pub all_tags: Vec<String>,
+
+    /// File extensions, e.g. `md` or `pdf`.
+    pub ext: Vec<String>,
+
+    /// Documents created at or after the date.
+    pub created_after: Option<DateTime<Utc>>,
+
+    /// Documents created before the date.
+    pub created_before: Option<DateTime<Utc>>,
+
+    /// If given and `true`, only documents that are ready for processing, i.e. that
+    /// have their parser and chunker configured. If `false`, only those that are not.
+    pub ready: Option<bool>,
+}
+
+impl DocumentFilter {
+    /// Filter by document source only.
+    pub fn src(src: &str) -> Self {
+        Self {
+            src: Some(src.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[schema_validation]
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        if self
+            .ext
+            .iter()
+            .any(|ext| DocumentType::try_from(ext.as_str()).is_err())
+        {
+            schema_err!(
+                "unsupported_ext",
+                "ext must be one of md, xml, json, csv, txt, pdf or docx"
+            );
+        }
+        if let (Some(after), Some(before)) = (self.created_after, self.created_before) {
+            if after >= before {
+                schema_err!(
+                    "created_after>=created_before",
+                    "createdAfter must be earlier than createdBefore"
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,123 @@
+use crate::core::{chunk::ChunkConfig, eval::RetrievalMetrics};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A named set of questions used to evaluate retrieval.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalDataset {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A question along with what it is expected to retrieve.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalQuestion {
+    pub id: Uuid,
+    pub dataset_id: Uuid,
+    pub question: String,
+    /// Documents whose chunks are relevant to the question.
+    pub documents: Vec<Uuid>,
+    /// Text spans whose chunks are relevant to the question.
+    pub spans: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct EvalQuestionInsert<'a> {
+    pub id: Uuid,
+    pub question: &'a str,
+    pub documents: &'a [Uuid],
+    pub spans: &'a [String],
+}
+
+impl<'a> EvalQuestionInsert<'a> {
+    pub fn new(question: &'a str, documents: &'a [Uuid], spans: &'a [String]) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            question,
+            documents,
+            spans,
+        }
+    }
+}
+
+/// Aggregate version of [EvalDataset] with its questions.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalDatasetDisplay {
+    pub dataset: EvalDataset,
+    pub questions: Vec<EvalQuestion>,
+}
+
+/// The results of evaluating a dataset against a collection.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalRun {
+    pub id: Uuid,
+
+    pub dataset_id: Uuid,
+
+    /// `None` if the collection was deleted since.
+    pub collection_id: Option<Uuid>,
+
+    pub collection_name: String,
+
+    pub embedder: String,
+
+    pub model: Option<String>,
+
+    pub sparse_model: Option<String>,
+
+    pub provider: String,
+
+    /// Amount of chunks retrieved per question.
+    pub k: i32,
+
+    /// Metrics averaged over all questions.
+    #[serde(flatten)]
+    pub metrics: RetrievalMetrics,
+
+    /// Chunking configurations of the documents the run involved, at the time of the run.
+    #[schema(value_type = HashMap<Uuid, ChunkConfig>)]
+    pub chunk_configs: Json<HashMap<Uuid, ChunkConfig>>,
+
+    /// Metrics of every question.
+    #[schema(value_type = Vec<EvalQuestionResult>)]
+    pub questions: Json<Vec<EvalQuestionResult>>,
+
+    pub created_at: DateTime<Utc>,
+}
+
+/// The metrics of a single question in an evaluation run.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalQuestionResult {
+    pub question_id: Uuid,
+
+    pub question: String,
+
+    #[serde(flatten)]
+    pub metrics: RetrievalMetrics,
+}
+
+pub struct EvalRunInsert<'a> {
+    pub id: Uuid,
+    pub dataset_id: Uuid,
+    pub collection_id: Uuid,
+    pub collection_name: &'a str,
+    pub embedder: &'a str,
+    pub model: Option<&'a str>,
+    pub sparse_model: Option<&'a str>,
+    pub provider: &'a str,
+    pub k: i32,
+    pub metrics: RetrievalMetrics,
+    pub chunk_configs: HashMap<Uuid, ChunkConfig>,
+    pub questions: Vec<EvalQuestionResult>,
+}
@@ -0,0 +1,63 @@
+use crate::core::{chunk::ChunkConfig, document::parser::ParseConfig};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+/// A named pair of parsing and chunking configurations shared across documents.
+/// The configurations of linked documents are kept in sync with the preset.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+    pub id: Uuid,
+
+    pub name: String,
+
+    #[schema(value_type = ParseConfig)]
+    pub parse_config: Json<ParseConfig>,
+
+    #[schema(value_type = ChunkConfig)]
+    pub chunk_config: Json<ChunkConfig>,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A preset along with the documents linked to it.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetDisplay {
+    pub preset: Preset,
+
+    /// IDs of the documents linked to the preset.
+    pub documents: Vec<Uuid>,
+}
+
+pub struct PresetInsert<'a> {
+    pub id: Uuid,
+    pub name: &'a str,
+    pub parse_config: ParseConfig,
+    pub chunk_config: ChunkConfig,
+}
+
+impl<'a> PresetInsert<'a> {
+    pub fn new(name: &'a str, parse_config: ParseConfig, chunk_config: ChunkConfig) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            parse_config,
+            chunk_config,
+        }
+    }
+}
+
+/// Selects documents by ID, label or tags. A document is selected if it matches any
+/// of the criteria.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DocumentSelection<'a> {
+    pub documents: &'a [Uuid],
+    pub label: Option<&'a str>,
+    /// Documents with any of the tags.
+    pub tags: &'a [String],
+}
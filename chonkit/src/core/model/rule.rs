@@ -0,0 +1,73 @@
+use crate::core::{chunk::ChunkConfig, document::parser::ParseConfig, rule::RuleCriteria};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+/// Chooses the initial parsing and chunking configurations of documents matching
+/// its criteria. Documents are either linked to the rule's preset, or get the
+/// rule's configurations, falling back to the defaults for the unset ones.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigRule {
+    pub id: Uuid,
+
+    pub name: String,
+
+    /// Rules with higher priorities are checked first.
+    pub priority: i32,
+
+    #[schema(value_type = RuleCriteria)]
+    pub criteria: Json<RuleCriteria>,
+
+    pub preset_id: Option<Uuid>,
+
+    #[schema(value_type = Option<ParseConfig>)]
+    pub parse_config: Option<Json<ParseConfig>>,
+
+    #[schema(value_type = Option<ChunkConfig>)]
+    pub chunk_config: Option<Json<ChunkConfig>>,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct ConfigRuleInsert<'a> {
+    pub id: Uuid,
+    pub name: &'a str,
+    pub priority: i32,
+    pub criteria: &'a RuleCriteria,
+    pub preset_id: Option<Uuid>,
+    pub parse_config: Option<&'a ParseConfig>,
+    pub chunk_config: Option<&'a ChunkConfig>,
+}
+
+impl<'a> ConfigRuleInsert<'a> {
+    pub fn new(name: &'a str, priority: i32, criteria: &'a RuleCriteria) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            priority,
+            criteria,
+            preset_id: None,
+            parse_config: None,
+            chunk_config: None,
+        }
+    }
+
+    pub fn with_preset(mut self, preset_id: Option<Uuid>) -> Self {
+        self.preset_id = preset_id;
+        self
+    }
+
+    pub fn with_configs(
+        mut self,
+        parse_config: Option<&'a ParseConfig>,
+        chunk_config: Option<&'a ChunkConfig>,
+    ) -> Self {
+        self.parse_config = parse_config;
+        self.chunk_config = chunk_config;
+        self
+    }
+}
@@ -1,7 +1,11 @@
 use crate::error::ChonkitError;
 use std::future::Future;
 
+pub mod chunk;
 pub mod document;
+pub mod eval;
+pub mod preset;
+pub mod rule;
 pub mod vector;
 
 /// Bound for repositories that support atomic operations.
@@ -0,0 +1,83 @@
+use crate::{
+    core::model::chunk::{Chunk, ChunkInsert},
+    error::ChonkitError,
+};
+use std::future::Future;
+use uuid::Uuid;
+
+use super::Atomic;
+
+/// Keeps track of the chunks documents are embedded with.
+pub trait ChunkRepo {
+    /// Get a chunk.
+    ///
+    /// * `id`: Chunk ID.
+    fn get_chunk(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<Chunk>, ChonkitError>> + Send;
+
+    /// Get chunks by their IDs. Chunks that do not exist are omitted.
+    ///
+    /// * `ids`: Chunk IDs.
+    fn get_chunks_by_id(
+        &self,
+        ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<Chunk>, ChonkitError>> + Send;
+
+    /// List the chunks of a document in order.
+    ///
+    /// * `document_id`: Document ID.
+    /// * `config_hash`: If given, only list chunks produced by the configuration
+    ///   with this fingerprint. Otherwise list chunks of all configurations.
+    fn list_chunks(
+        &self,
+        document_id: Uuid,
+        config_hash: Option<&str>,
+    ) -> impl Future<Output = Result<Vec<Chunk>, ChonkitError>> + Send;
+
+    /// Insert the chunks of a document produced by a configuration.
+    ///
+    /// * `document_id`: Document ID.
+    /// * `config_hash`: Fingerprint of the configuration producing the chunks.
+    /// * `document_hash`: Hash of the document content the chunks were created from.
+    /// * `chunks`: Chunks to insert.
+    /// * `tx`: Optional transaction to insert in.
+    fn insert_chunks(
+        &self,
+        document_id: Uuid,
+        config_hash: &str,
+        document_hash: &str,
+        chunks: Vec<ChunkInsert<'_>>,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<Vec<Chunk>, ChonkitError>> + Send
+    where
+        Self: Atomic;
+
+    /// Delete the chunks of a document produced by a configuration.
+    /// Return the amount of chunks deleted.
+    ///
+    /// * `document_id`: Document ID.
+    /// * `config_hash`: Fingerprint of the configuration producing the chunks.
+    /// * `tx`: Optional transaction to delete in.
+    fn delete_chunks(
+        &self,
+        document_id: Uuid,
+        config_hash: &str,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<u64, ChonkitError>> + Send
+    where
+        Self: Atomic;
+
+    /// Full text search over stored chunks, best matches first.
+    ///
+    /// * `query`: Search query, in web search syntax.
+    /// * `document_id`: If given, only search the chunks of this document.
+    /// * `limit`: Maximum amount of chunks to return.
+    fn search_chunks(
+        &self,
+        query: &str,
+        document_id: Option<Uuid>,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<Chunk>, ChonkitError>> + Send;
+}
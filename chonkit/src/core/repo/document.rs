@@ -5,7 +5,8 @@ use crate::{
         model::{
             document::{
                 config::{DocumentChunkConfig, DocumentParseConfig},
-                Document, DocumentConfig, DocumentDisplay, DocumentInsert, DocumentUpdate,
+                Document, DocumentConfig, DocumentDisplay, DocumentFilter, DocumentInsert,
+                DocumentUpdate,
             },
             List, PaginationSort,
         },
@@ -54,13 +55,18 @@ pub trait DocumentRepo {
     /// List documents with limit and offset
     ///
     /// * `p`: Pagination params.
+    /// * `filter`: Document metadata filter.
     async fn list(
         &self,
         p: PaginationSort,
-        src: Option<&str>,
-        ready: Option<bool>,
+        filter: &DocumentFilter,
     ) -> Result<List<Document>, ChonkitError>;
 
+    /// List the IDs of all documents matching the filter.
+    ///
+    /// * `filter`: Document metadata filter.
+    async fn list_ids(&self, filter: &DocumentFilter) -> Result<Vec<Uuid>, ChonkitError>;
+
     /// List documents with limit and offset with additional relations for embeddings.
     ///
     /// * `p`: Pagination params.
@@ -78,15 +84,21 @@ pub trait DocumentRepo {
     /// * `document`: Insert payload.
     async fn insert(&self, document: DocumentInsert<'_>) -> Result<Document, ChonkitError>;
 
-    /// Update document metadata.
+    /// Update the metadata of documents. Return the IDs of the updated documents.
     ///
-    /// * `id`: Document ID.
+    /// * `ids`: Document IDs.
     /// * `document`: Update payload.
     async fn update(
         &self,
-        id: uuid::Uuid,
+        ids: &[Uuid],
         document: DocumentUpdate<'_>,
-    ) -> Result<u64, ChonkitError>;
+    ) -> Result<Vec<Uuid>, ChonkitError>;
+
+    /// Update the hash of a document's content, e.g. after its file changed in storage.
+    ///
+    /// * `id`: Document ID.
+    /// * `hash`: Hash of the document's new content.
+    async fn update_hash(&self, id: Uuid, hash: &str) -> Result<(), ChonkitError>;
 
     /// Remove document metadata by id.
     ///
@@ -0,0 +1,91 @@
+use crate::{
+    core::model::eval::{EvalDataset, EvalQuestion, EvalQuestionInsert, EvalRun, EvalRunInsert},
+    error::ChonkitError,
+};
+use std::future::Future;
+use uuid::Uuid;
+
+use super::Atomic;
+
+/// Keeps track of evaluation datasets and the results of evaluating them.
+pub trait EvalRepo {
+    /// List all evaluation datasets.
+    fn list_datasets(&self) -> impl Future<Output = Result<Vec<EvalDataset>, ChonkitError>> + Send;
+
+    /// Get an evaluation dataset.
+    ///
+    /// * `id`: Dataset ID.
+    fn get_dataset(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<EvalDataset>, ChonkitError>> + Send;
+
+    /// Insert an evaluation dataset.
+    ///
+    /// * `name`: Unique dataset name.
+    /// * `tx`: Optional transaction to insert in.
+    fn insert_dataset(
+        &self,
+        name: &str,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<EvalDataset, ChonkitError>> + Send
+    where
+        Self: Atomic;
+
+    /// Delete an evaluation dataset along with its questions and runs.
+    /// Return the amount of entries deleted.
+    ///
+    /// * `id`: Dataset ID.
+    fn delete_dataset(&self, id: Uuid) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
+
+    /// List the questions of a dataset, oldest first.
+    ///
+    /// * `dataset_id`: Dataset ID.
+    fn list_questions(
+        &self,
+        dataset_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<EvalQuestion>, ChonkitError>> + Send;
+
+    /// Insert questions into a dataset.
+    ///
+    /// * `dataset_id`: Dataset ID.
+    /// * `questions`: Questions to insert.
+    /// * `tx`: Optional transaction to insert in.
+    fn insert_questions(
+        &self,
+        dataset_id: Uuid,
+        questions: Vec<EvalQuestionInsert<'_>>,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<Vec<EvalQuestion>, ChonkitError>> + Send
+    where
+        Self: Atomic;
+
+    /// Delete a question. Return the amount of entries deleted.
+    ///
+    /// * `id`: Question ID.
+    fn delete_question(&self, id: Uuid) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
+
+    /// List the runs of a dataset, most recent first.
+    ///
+    /// * `dataset_id`: Dataset ID.
+    fn list_runs(
+        &self,
+        dataset_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<EvalRun>, ChonkitError>> + Send;
+
+    /// Get an evaluation run.
+    ///
+    /// * `id`: Run ID.
+    fn get_run(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<EvalRun>, ChonkitError>> + Send;
+
+    /// Insert the results of an evaluation run.
+    ///
+    /// * `insert`: Run insert DTO.
+    fn insert_run(
+        &self,
+        insert: EvalRunInsert<'_>,
+    ) -> impl Future<Output = Result<EvalRun, ChonkitError>> + Send;
+}
@@ -0,0 +1,104 @@
+use crate::{
+    core::{
+        chunk::ChunkConfig,
+        document::parser::ParseConfig,
+        model::preset::{DocumentSelection, Preset, PresetInsert},
+    },
+    error::ChonkitError,
+};
+use std::future::Future;
+use uuid::Uuid;
+
+use super::Atomic;
+
+/// Keeps track of parsing and chunking presets and the documents linked to them.
+pub trait PresetRepo {
+    /// List all presets.
+    fn list_presets(&self) -> impl Future<Output = Result<Vec<Preset>, ChonkitError>> + Send;
+
+    /// Get a preset.
+    ///
+    /// * `id`: Preset ID.
+    fn get_preset(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<Preset>, ChonkitError>> + Send;
+
+    /// Insert a preset.
+    ///
+    /// * `insert`: Preset name and configurations.
+    fn insert_preset(
+        &self,
+        insert: PresetInsert<'_>,
+    ) -> impl Future<Output = Result<Preset, ChonkitError>> + Send;
+
+    /// Update the name and configurations of a preset.
+    /// Return `None` if the preset does not exist.
+    ///
+    /// * `id`: Preset ID.
+    /// * `name`: Unique preset name.
+    /// * `parse_config`: Parsing configuration.
+    /// * `chunk_config`: Chunking configuration.
+    /// * `tx`: Optional transaction to update in.
+    fn update_preset(
+        &self,
+        id: Uuid,
+        name: &str,
+        parse_config: ParseConfig,
+        chunk_config: ChunkConfig,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<Option<Preset>, ChonkitError>> + Send
+    where
+        Self: Atomic;
+
+    /// Delete a preset. Linked documents keep their current configurations.
+    /// Presets used by configuration rules cannot be deleted.
+    /// Return the amount of entries deleted.
+    ///
+    /// * `id`: Preset ID.
+    fn delete_preset(&self, id: Uuid) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
+
+    /// List the IDs of the documents linked to a preset.
+    ///
+    /// * `preset_id`: Preset ID.
+    fn list_preset_documents(
+        &self,
+        preset_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Uuid>, ChonkitError>> + Send;
+
+    /// Link the selected documents to a preset, replacing their previous presets.
+    /// Return the IDs of the linked documents.
+    ///
+    /// * `preset_id`: Preset ID.
+    /// * `selection`: Documents to link.
+    /// * `tx`: Optional transaction to link in.
+    fn link_documents(
+        &self,
+        preset_id: Uuid,
+        selection: DocumentSelection<'_>,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<Vec<Uuid>, ChonkitError>> + Send
+    where
+        Self: Atomic;
+
+    /// Unlink a document from its preset, if any. Its configurations are kept.
+    ///
+    /// * `document_id`: Document ID.
+    fn unlink_document(
+        &self,
+        document_id: Uuid,
+    ) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
+
+    /// Overwrite the parsing and chunking configurations of the documents linked to
+    /// a preset with the preset's. Return the amount of documents updated.
+    ///
+    /// * `preset_id`: Preset ID.
+    /// * `tx`: Optional transaction to update in.
+    fn sync_preset_configs(
+        &self,
+        preset_id: Uuid,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<u64, ChonkitError>> + Send
+    where
+        Self: Atomic;
+}
@@ -0,0 +1,44 @@
+use crate::{
+    core::model::rule::{ConfigRule, ConfigRuleInsert},
+    error::ChonkitError,
+};
+use std::future::Future;
+use uuid::Uuid;
+
+/// Keeps track of the rules choosing the configurations of new documents.
+pub trait RuleRepo {
+    /// List all rules in the order they are checked in; by descending priority,
+    /// then by creation date.
+    fn list_rules(&self) -> impl Future<Output = Result<Vec<ConfigRule>, ChonkitError>> + Send;
+
+    /// Get a rule.
+    ///
+    /// * `id`: Rule ID.
+    fn get_rule(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<ConfigRule>, ChonkitError>> + Send;
+
+    /// Insert a rule.
+    ///
+    /// * `insert`: Rule name, criteria and configurations.
+    fn insert_rule(
+        &self,
+        insert: ConfigRuleInsert<'_>,
+    ) -> impl Future<Output = Result<ConfigRule, ChonkitError>> + Send;
+
+    /// Update a rule, keeping its ID. Return `None` if the rule does not exist.
+    ///
+    /// * `id`: Rule ID.
+    /// * `update`: New rule name, criteria and configurations. Its ID is ignored.
+    fn update_rule(
+        &self,
+        id: Uuid,
+        update: ConfigRuleInsert<'_>,
+    ) -> impl Future<Output = Result<Option<ConfigRule>, ChonkitError>> + Send;
+
+    /// Delete a rule. Return the amount of entries deleted.
+    ///
+    /// * `id`: Rule ID.
+    fn delete_rule(&self, id: Uuid) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
+}
@@ -1,6 +1,9 @@
 use crate::{
     core::model::{
-        collection::{Collection, CollectionDisplay, CollectionInsert, Embedding, EmbeddingInsert},
+        collection::{
+            Collection, CollectionAlias, CollectionDisplay, CollectionInsert, Embedding,
+            EmbeddingInsert,
+        },
         List, Pagination, PaginationSort,
     },
     error::ChonkitError,
@@ -28,6 +31,11 @@ pub trait VectorRepo {
         p: PaginationSort,
     ) -> impl Future<Output = Result<List<CollectionDisplay>, ChonkitError>> + Send;
 
+    /// List all collections.
+    fn list_all_collections(
+        &self,
+    ) -> impl Future<Output = Result<Vec<Collection>, ChonkitError>> + Send;
+
     /// Insert collection metadata.
     ///
     /// * `insert`: Collection insert DTO.
@@ -90,6 +98,14 @@ pub trait VectorRepo {
         id: Uuid,
     ) -> impl Future<Output = Result<Vec<Embedding>, ChonkitError>> + Send;
 
+    /// Get the embedding information of all documents in the given collection.
+    ///
+    /// * `collection_id`: Collection ID.
+    fn get_collection_embeddings(
+        &self,
+        collection_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Embedding>, ChonkitError>> + Send;
+
     /// Get a document's embedding information for the given collection.
     ///
     /// * `id`: Document ID.
@@ -132,6 +148,14 @@ pub trait VectorRepo {
         collection_id: Uuid,
     ) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
 
+    /// Return the IDs of the given documents that exist in the repository.
+    ///
+    /// * `ids`: Document IDs.
+    fn get_existing_documents(
+        &self,
+        ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<Uuid>, ChonkitError>> + Send;
+
     /// Delete all embedding entries for the given collection.
     ///
     /// * `collection`: Collection name.
@@ -139,4 +163,36 @@ pub trait VectorRepo {
         &self,
         collection_id: Uuid,
     ) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
+
+    /// Get an alias by its name.
+    ///
+    /// * `name`: Alias name.
+    fn get_alias(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<CollectionAlias>, ChonkitError>> + Send;
+
+    /// List all aliases.
+    fn list_aliases(
+        &self,
+    ) -> impl Future<Output = Result<Vec<CollectionAlias>, ChonkitError>> + Send;
+
+    /// Delete an alias. Return the amount of entries deleted.
+    ///
+    /// * `name`: Alias name.
+    fn delete_alias(&self, name: &str) -> impl Future<Output = Result<u64, ChonkitError>> + Send;
+
+    /// Point an alias to the given collection, creating it if it does not exist.
+    ///
+    /// * `name`: Alias name.
+    /// * `collection_id`: Collection ID.
+    /// * `tx`: Optional transaction to upsert in.
+    fn upsert_alias(
+        &self,
+        name: &str,
+        collection_id: Uuid,
+        tx: Option<&mut Self::Tx>,
+    ) -> impl Future<Output = Result<CollectionAlias, ChonkitError>> + Send
+    where
+        Self: Atomic;
 }
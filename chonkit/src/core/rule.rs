@@ -0,0 +1,165 @@
+//! Rules for choosing the initial parsing and chunking configurations of new documents.
+
+use super::model::document::DocumentType;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use validify::{schema_err, schema_validation, Validate, ValidationErrors};
+
+/// Conditions a document must satisfy for a rule to apply to it.
+/// Unset conditions match any document.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[validate(Self::validate)]
+pub struct RuleCriteria {
+    /// Document types, i.e. file extensions such as `md` or `pdf`.
+    #[serde(default)]
+    pub types: Vec<String>,
+
+    /// Minimum file size in bytes.
+    pub min_size: Option<usize>,
+
+    /// Maximum file size in bytes.
+    pub max_size: Option<usize>,
+
+    /// Storage provider the document comes from.
+    pub src: Option<String>,
+
+    /// Document label.
+    pub label: Option<String>,
+
+    /// Pattern the file name must match.
+    #[serde(default, with = "serde_regex")]
+    #[schema(value_type = Option<String>)]
+    pub name_pattern: Option<Regex>,
+}
+
+impl RuleCriteria {
+    /// Check whether the document satisfies all the conditions.
+    pub fn matches(&self, document: &DocumentFacts<'_>) -> bool {
+        let ty = document.ty.to_string();
+
+        (self.types.is_empty() || self.types.iter().any(|t| t.eq_ignore_ascii_case(&ty)))
+            && self.min_size.is_none_or(|min| document.size >= min)
+            && self.max_size.is_none_or(|max| document.size <= max)
+            && self.src.as_ref().is_none_or(|src| src == document.src)
+            && self
+                .label
+                .as_ref()
+                .is_none_or(|label| Some(label.as_str()) == document.label)
+            && self
+                .name_pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(document.name))
+    }
+
+    #[schema_validation]
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        if self
+            .types
+            .iter()
+            .any(|ty| DocumentType::try_from(ty.to_lowercase().as_str()).is_err())
+        {
+            schema_err!(
+                "unsupported_type",
+                "types must be one of md, xml, json, csv, txt, pdf or docx"
+            );
+        }
+        if let (Some(min), Some(max)) = (self.min_size, self.max_size) {
+            if min > max {
+                schema_err!(
+                    "min_size>max_size",
+                    "minSize cannot be greater than maxSize"
+                );
+            }
+        }
+    }
+}
+
+/// What rules know about a document when it is added.
+#[derive(Debug, Clone, Copy)]
+pub struct DocumentFacts<'a> {
+    /// File name.
+    pub name: &'a str,
+
+    pub ty: DocumentType,
+
+    /// File size in bytes.
+    pub size: usize,
+
+    /// Storage provider.
+    pub src: &'a str,
+
+    pub label: Option<&'a str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model::document::TextDocumentType;
+
+    const FACTS: DocumentFacts<'static> = DocumentFacts {
+        name: "2024-report.md",
+        ty: DocumentType::Text(TextDocumentType::Md),
+        size: 2048,
+        src: "fs",
+        label: Some("reports"),
+    };
+
+    #[test]
+    fn matches_any_document_without_conditions() {
+        assert!(RuleCriteria::default().matches(&FACTS));
+    }
+
+    #[test]
+    fn matches_all_conditions() {
+        let criteria = RuleCriteria {
+            types: vec!["MD".to_string(), "txt".to_string()],
+            min_size: Some(1024),
+            max_size: Some(4096),
+            src: Some("fs".to_string()),
+            label: Some("reports".to_string()),
+            name_pattern: Some(Regex::new(r"^\d{4}-").unwrap()),
+        };
+        assert!(criteria.matches(&FACTS));
+
+        let pdf = RuleCriteria {
+            types: vec!["pdf".to_string()],
+            ..Default::default()
+        };
+        assert!(!pdf.matches(&FACTS));
+
+        let small = RuleCriteria {
+            max_size: Some(1024),
+            ..Default::default()
+        };
+        assert!(!small.matches(&FACTS));
+
+        let unlabeled = DocumentFacts {
+            label: None,
+            ..FACTS
+        };
+        assert!(!criteria.matches(&unlabeled));
+
+        let renamed = DocumentFacts {
+            name: "report.md",
+            ..FACTS
+        };
+        assert!(!criteria.matches(&renamed));
+    }
+
+    #[test]
+    fn validates_criteria() {
+        let unsupported = RuleCriteria {
+            types: vec!["exe".to_string()],
+            ..Default::default()
+        };
+        assert!(unsupported.validate().is_err());
+
+        let sizes = RuleCriteria {
+            min_size: Some(10),
+            max_size: Some(5),
+            ..Default::default()
+        };
+        assert!(sizes.validate().is_err());
+    }
+}
@@ -0,0 +1,372 @@
+//! Post-processing of search results, i.e. diversifying results and merging
+//! results from multiple collections.
+
+use super::vector::{QueryVector, SearchHit};
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Constant dampening the impact of top ranks in reciprocal rank fusion.
+pub const RRF_K: f64 = 60.;
+
+/// A search hit attributed to the collection it was found in.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FederatedHit {
+    pub content: String,
+
+    /// ID of the document the hit belongs to, if known.
+    pub document_id: Option<Uuid>,
+
+    /// Position of the chunk in the document, if known.
+    pub chunk_index: Option<usize>,
+
+    /// ID of the stored chunk the hit was created from, if known.
+    pub chunk_id: Option<Uuid>,
+
+    /// Collection the hit was found in.
+    pub collection_id: Uuid,
+
+    pub collection_name: String,
+
+    /// Vector database provider of the collection.
+    pub provider: String,
+
+    /// Score returned by the vector database.
+    pub score: f64,
+
+    /// Score min-max normalized within the results of its collection, in `[0, 1]`.
+    pub normalized_score: f64,
+
+    /// Reciprocal rank fusion score the merged results are ordered by.
+    pub rrf_score: f64,
+}
+
+/// Min-max normalize the scores of hits from a single collection into `[0, 1]`.
+/// If all scores are equal, they are normalized to 1.
+pub fn normalize_scores(hits: &mut [FederatedHit]) {
+    let (min, max) = hits.iter().fold((f64::MAX, f64::MIN), |(min, max), hit| {
+        (min.min(hit.score), max.max(hit.score))
+    });
+
+    for hit in hits.iter_mut() {
+        hit.normalized_score = if max > min {
+            (hit.score - min) / (max - min)
+        } else {
+            1.
+        };
+    }
+}
+
+/// Merge ranked lists of hits with reciprocal rank fusion.
+///
+/// Every list must be ordered best first. A hit at rank `r` (starting from 1) contributes
+/// `1 / (RRF_K + r)` to its fused score. Hits with the same document and content found in
+/// multiple lists are merged into one, attributed to the collection where it scored best.
+///
+/// * `lists`: Ranked hits per collection.
+/// * `limit`: Maximum amount of hits to return.
+pub fn reciprocal_rank_fusion(lists: Vec<Vec<FederatedHit>>, limit: usize) -> Vec<FederatedHit> {
+    let mut fused: Vec<FederatedHit> = vec![];
+
+    for list in lists {
+        for (rank, mut hit) in list.into_iter().enumerate() {
+            let rrf = 1. / (RRF_K + (rank + 1) as f64);
+
+            let existing = fused
+                .iter_mut()
+                .find(|h| h.document_id == hit.document_id && h.content == hit.content);
+
+            match existing {
+                Some(existing) => {
+                    let rrf_score = existing.rrf_score + rrf;
+                    if hit.normalized_score > existing.normalized_score {
+                        *existing = hit;
+                    }
+                    existing.rrf_score = rrf_score;
+                }
+                None => {
+                    hit.rrf_score = rrf;
+                    fused.push(hit);
+                }
+            }
+        }
+    }
+
+    fused.sort_by(|a, b| {
+        b.rrf_score
+            .total_cmp(&a.rrf_score)
+            .then(b.normalized_score.total_cmp(&a.normalized_score))
+    });
+
+    fused.truncate(limit);
+
+    fused
+}
+
+/// Select up to `limit` hits out of candidates ordered best first.
+///
+/// If `lambda` is given, hits are selected with Maximal Marginal Relevance, trading the
+/// similarity of a hit to the query (`lambda = 1`) for its dissimilarity to the hits already
+/// selected (`lambda = 0`). The hits must then contain their vectors; hits without vectors
+/// are treated as dissimilar to everything.
+///
+/// If `max_per_document` is given, at most that many hits of the same document are selected.
+///
+/// * `query`: The vector the candidates were obtained with.
+/// * `candidates`: Search hits, best first.
+/// * `limit`: Maximum amount of hits to select.
+/// * `lambda`: MMR trade-off between relevance and diversity, in `[0, 1]`.
+/// * `max_per_document`: Maximum amount of hits per document.
+pub fn select_hits(
+    query: &QueryVector,
+    candidates: Vec<SearchHit>,
+    limit: usize,
+    lambda: Option<f64>,
+    max_per_document: Option<usize>,
+) -> Vec<SearchHit> {
+    let mut per_document: HashMap<Uuid, usize> = HashMap::new();
+    let below_cap = |per_document: &HashMap<Uuid, usize>, hit: &SearchHit| match (
+        hit.document_id,
+        max_per_document,
+    ) {
+        (Some(document_id), Some(max)) => {
+            per_document.get(&document_id).copied().unwrap_or(0) < max
+        }
+        _ => true,
+    };
+
+    let Some(lambda) = lambda else {
+        let mut selected = vec![];
+        for hit in candidates {
+            if selected.len() == limit {
+                break;
+            }
+            if below_cap(&per_document, &hit) {
+                if let Some(document_id) = hit.document_id {
+                    *per_document.entry(document_id).or_default() += 1;
+                }
+                selected.push(hit);
+            }
+        }
+        return selected;
+    };
+
+    let relevance: Vec<f64> = candidates
+        .iter()
+        .map(|hit| similarity(Some(query), hit.vector.as_ref()))
+        .collect();
+
+    let mut remaining: Vec<(SearchHit, f64)> = candidates.into_iter().zip(relevance).collect();
+    let mut selected: Vec<SearchHit> = vec![];
+
+    while selected.len() < limit {
+        let mut best: Option<(usize, f64)> = None;
+
+        for (i, (hit, relevance)) in remaining.iter().enumerate() {
+            if !below_cap(&per_document, hit) {
+                continue;
+            }
+
+            let redundancy = selected
+                .iter()
+                .map(|s| similarity(s.vector.as_ref(), hit.vector.as_ref()))
+                .fold(0., f64::max);
+
+            let score = lambda * relevance - (1. - lambda) * redundancy;
+
+            // Ties are resolved in favour of the better ranked candidate
+            match best {
+                Some((_, best_score)) if score <= best_score => {}
+                _ => best = Some((i, score)),
+            }
+        }
+
+        let Some((i, _)) = best else {
+            break;
+        };
+
+        let (hit, _) = remaining.remove(i);
+        if let Some(document_id) = hit.document_id {
+            *per_document.entry(document_id).or_default() += 1;
+        }
+        selected.push(hit);
+    }
+
+    selected
+}
+
+/// Cosine similarity of two vectors of the same kind, 0 if either is missing
+/// or they are of different kinds.
+fn similarity(a: Option<&QueryVector>, b: Option<&QueryVector>) -> f64 {
+    let (dot, norm_a, norm_b) = match (a, b) {
+        (Some(QueryVector::Dense(a)), Some(QueryVector::Dense(b))) => (
+            a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>(),
+            a.iter().map(|x| x * x).sum::<f64>().sqrt(),
+            b.iter().map(|x| x * x).sum::<f64>().sqrt(),
+        ),
+        (Some(QueryVector::Sparse(a)), Some(QueryVector::Sparse(b))) => {
+            let b_values: HashMap<u32, f32> = b
+                .indices
+                .iter()
+                .copied()
+                .zip(b.values.iter().copied())
+                .collect();
+            let norm = |values: &[f32]| {
+                values
+                    .iter()
+                    .map(|x| (*x as f64).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            };
+            (
+                a.indices
+                    .iter()
+                    .zip(&a.values)
+                    .filter_map(|(idx, a)| Some(*a as f64 * *b_values.get(idx)? as f64))
+                    .sum::<f64>(),
+                norm(&a.values),
+                norm(&b.values),
+            )
+        }
+        _ => return 0.,
+    };
+
+    if norm_a == 0. || norm_b == 0. {
+        return 0.;
+    }
+
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_hit(content: &str, document_id: Uuid, vector: Vec<f64>) -> SearchHit {
+        SearchHit {
+            content: content.to_string(),
+            score: 0.,
+            document_id: Some(document_id),
+            chunk_index: None,
+            chunk_id: None,
+            vector: Some(QueryVector::Dense(vector)),
+        }
+    }
+
+    fn hit(content: &str, collection: &str, score: f64) -> FederatedHit {
+        FederatedHit {
+            content: content.to_string(),
+            document_id: None,
+            chunk_index: None,
+            chunk_id: None,
+            collection_id: Uuid::nil(),
+            collection_name: collection.to_string(),
+            provider: "test".to_string(),
+            score,
+            normalized_score: 0.,
+            rrf_score: 0.,
+        }
+    }
+
+    #[test]
+    fn normalizes_scores() {
+        let mut hits = vec![hit("a", "A", 10.), hit("b", "A", 5.), hit("c", "A", 0.)];
+        normalize_scores(&mut hits);
+        let normalized: Vec<_> = hits.iter().map(|h| h.normalized_score).collect();
+        assert_eq!(vec![1., 0.5, 0.], normalized);
+
+        let mut hits = vec![hit("a", "A", 0.3), hit("b", "A", 0.3)];
+        normalize_scores(&mut hits);
+        assert!(hits.iter().all(|h| h.normalized_score == 1.));
+    }
+
+    #[test]
+    fn fuses_by_rank() {
+        let a = vec![hit("a1", "A", 0.9), hit("a2", "A", 0.8)];
+        // Scores on a different scale must not matter
+        let b = vec![
+            hit("b1", "B", 100.),
+            hit("b2", "B", 50.),
+            hit("b3", "B", 10.),
+        ];
+
+        let fused = reciprocal_rank_fusion(vec![a, b], 4);
+        let contents: Vec<_> = fused.iter().map(|h| h.content.as_str()).collect();
+
+        assert_eq!(4, fused.len());
+        assert_eq!(vec!["a1", "b1", "a2", "b2"], contents[..4].to_vec());
+        assert_eq!(1. / (RRF_K + 1.), fused[0].rrf_score);
+    }
+
+    #[test]
+    fn merges_duplicates() {
+        let mut a = vec![hit("same", "A", 0.5), hit("a", "A", 0.1)];
+        let mut b = vec![hit("b", "B", 0.9), hit("same", "B", 0.8)];
+        normalize_scores(&mut a);
+        normalize_scores(&mut b);
+
+        let fused = reciprocal_rank_fusion(vec![a, b], 10);
+
+        assert_eq!(3, fused.len());
+        assert_eq!("same", fused[0].content);
+        assert_eq!(1. / (RRF_K + 1.) + 1. / (RRF_K + 2.), fused[0].rrf_score);
+        // Attributed to the collection where it ranked first
+        assert_eq!("A", fused[0].collection_name);
+    }
+
+    #[test]
+    fn mmr_selects_diverse_hits() {
+        let query = QueryVector::Dense(vec![1., 0.]);
+        let (doc_a, doc_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let candidates = vec![
+            search_hit("a", doc_a, vec![1., 0.1]),
+            // Near duplicate of "a"
+            search_hit("a'", doc_a, vec![1., 0.11]),
+            search_hit("b", doc_b, vec![0.8, -0.6]),
+        ];
+
+        let relevant = select_hits(&query, candidates.clone(), 2, Some(1.), None);
+        let contents: Vec<_> = relevant.iter().map(|h| h.content.as_str()).collect();
+        assert_eq!(vec!["a", "a'"], contents);
+
+        let diverse = select_hits(&query, candidates, 2, Some(0.5), None);
+        let contents: Vec<_> = diverse.iter().map(|h| h.content.as_str()).collect();
+        assert_eq!(vec!["a", "b"], contents);
+    }
+
+    #[test]
+    fn caps_hits_per_document() {
+        let query = QueryVector::Dense(vec![1., 0.]);
+        let (doc_a, doc_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let candidates = vec![
+            search_hit("a1", doc_a, vec![1., 0.]),
+            search_hit("a2", doc_a, vec![1., 0.]),
+            search_hit("a3", doc_a, vec![1., 0.]),
+            search_hit("b1", doc_b, vec![0., 1.]),
+        ];
+
+        let capped = select_hits(&query, candidates.clone(), 3, None, Some(2));
+        let contents: Vec<_> = capped.iter().map(|h| h.content.as_str()).collect();
+        assert_eq!(vec!["a1", "a2", "b1"], contents);
+
+        let capped = select_hits(&query, candidates, 3, Some(1.), Some(1));
+        let contents: Vec<_> = capped.iter().map(|h| h.content.as_str()).collect();
+        assert_eq!(vec!["a1", "b1"], contents);
+    }
+
+    #[test]
+    fn sparse_similarity() {
+        let a = QueryVector::Sparse(crate::core::embedder::SparseEmbedding {
+            indices: vec![1, 3],
+            values: vec![1., 1.],
+        });
+        let b = QueryVector::Sparse(crate::core::embedder::SparseEmbedding {
+            indices: vec![3, 5],
+            values: vec![1., 1.],
+        });
+        assert!((similarity(Some(&a), Some(&b)) - 0.5).abs() < 1e-9);
+        assert!((similarity(Some(&a), Some(&a)) - 1.).abs() < 1e-9);
+        assert_eq!(0., similarity(Some(&a), None));
+    }
+}
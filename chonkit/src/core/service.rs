@@ -1,4 +1,7 @@
 //! Business logic.
 
 pub mod document;
+pub mod eval;
+pub mod preset;
+pub mod rule;
 pub mod vector;
@@ -4,28 +4,38 @@ use crate::{
     config::{DEFAULT_DOCUMENT_CONTENT, DEFAULT_DOCUMENT_NAME},
     core::{
         chunk::{
-            ChunkConfig, ChunkedDocument, SemanticEmbedder, SemanticWindowConfig,
+            apply_chunk_edits, config_fingerprint, locate_chunks, ChunkConfig, ChunkEdit,
+            ChunkedDocument, CuratedChunk, SemanticEmbedder, SemanticWindowConfig,
             SnappingWindowConfig,
         },
         document::{
-            parser::{ParseConfig, Parser},
+            parser::{ParseConfig, ParsedContent, Parser},
             sha256,
         },
         model::{
+            chunk::{Chunk, ChunkInsert, ChunkSource, MANUAL_CHUNKS},
             document::{
-                Document, DocumentConfig, DocumentDisplay, DocumentInsert, DocumentType,
-                TextDocumentType,
+                Document, DocumentConfig, DocumentDisplay, DocumentFilter, DocumentInsert,
+                DocumentType, TextDocumentType,
             },
+            preset::DocumentSelection,
+            rule::ConfigRule,
             List, Pagination, PaginationSort,
         },
         provider::ProviderState,
-        repo::{document::DocumentRepo, Atomic},
+        repo::{
+            chunk::ChunkRepo, document::DocumentRepo, preset::PresetRepo, rule::RuleRepo, Atomic,
+        },
+        rule::DocumentFacts,
     },
     err,
     error::ChonkitError,
     map_err, transaction,
 };
-use dto::{ChunkPreviewPayload, DocumentUpload};
+use dto::{
+    ChunkPreviewPayload, ChunkSearch, DocumentBulkUpdatePayload, DocumentUpdatePayload,
+    DocumentUpload, SaveChunksPayload,
+};
 use tracing::info;
 use uuid::Uuid;
 use validify::{Validate, Validify};
@@ -39,7 +49,7 @@ pub struct DocumentService<R> {
 
 impl<R> DocumentService<R>
 where
-    R: DocumentRepo + Atomic + Send + Sync,
+    R: DocumentRepo + ChunkRepo + PresetRepo + RuleRepo + Atomic + Send + Sync,
 {
     pub fn new(repo: R, providers: ProviderState) -> Self {
         Self { repo, providers }
@@ -48,16 +58,77 @@ where
     /// Get a paginated list of documents from the repository.
     ///
     /// * `p`: Pagination and sorting options.
-    /// * `src`: Optional document source to filter by.
-    /// * `ready`: If given and `true`, return only documents that are ready for processing.
+    /// * `filter`: Document metadata filter.
     pub async fn list_documents(
         &self,
         p: PaginationSort,
-        src: Option<&str>,
-        ready: Option<bool>,
+        filter: DocumentFilter,
     ) -> Result<List<Document>, ChonkitError> {
         map_err!(p.validate());
-        self.repo.list(p, src, ready).await
+        map_err!(filter.validate());
+        self.repo.list(p, &filter).await
+    }
+
+    /// List the IDs of all documents matching the filter.
+    ///
+    /// * `filter`: Document metadata filter.
+    pub async fn list_document_ids(
+        &self,
+        filter: DocumentFilter,
+    ) -> Result<Vec<Uuid>, ChonkitError> {
+        map_err!(filter.validate());
+        self.repo.list_ids(&filter).await
+    }
+
+    /// Update the name, label and tags of a document.
+    ///
+    /// * `id`: Document ID.
+    /// * `payload`: Metadata to update.
+    pub async fn update_document(
+        &self,
+        id: Uuid,
+        mut payload: DocumentUpdatePayload,
+    ) -> Result<Document, ChonkitError> {
+        map_err!(payload.validify());
+
+        if self
+            .repo
+            .update(&[id], payload.to_update())
+            .await?
+            .is_empty()
+        {
+            return err!(DoesNotExist, "Document with ID {id}");
+        }
+
+        self.get_document(id).await
+    }
+
+    /// Update the label and tags of many documents.
+    /// Return the IDs of the updated documents.
+    ///
+    /// * `payload`: Documents to update and the metadata to update them with.
+    pub async fn update_documents(
+        &self,
+        mut payload: DocumentBulkUpdatePayload,
+    ) -> Result<Vec<Uuid>, ChonkitError> {
+        map_err!(payload.validate());
+        map_err!(payload.update.validify());
+
+        let DocumentBulkUpdatePayload {
+            mut documents,
+            filter,
+            update,
+        } = payload;
+
+        if let Some(filter) = filter {
+            documents.extend(self.repo.list_ids(&filter).await?);
+        }
+
+        if documents.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.repo.update(&documents, update.to_update()).await
     }
 
     /// Get a paginated list of documents from the repository with additional info for each.
@@ -113,6 +184,25 @@ where
         store.read(&document, &parser).await
     }
 
+    /// Get document text content and its pages using its parsing configuration,
+    /// or the default parser if it has no configuration. Parsing runs on a blocking thread.
+    ///
+    /// * `id`: Document ID.
+    pub async fn get_parsed_content(&self, id: Uuid) -> Result<ParsedContent, ChonkitError> {
+        let Some(document) = self.repo.get_by_id(id).await? else {
+            return err!(DoesNotExist, "Document with ID {id}");
+        };
+
+        let store = self.providers.document.get_provider(&document.src)?;
+
+        let ext = document.ext.as_str().try_into()?;
+        let parser = self.get_parser(id, ext).await?;
+
+        let bytes = store.get_bytes(&document.path).await?;
+
+        map_err!(tokio::task::spawn_blocking(move || parser.parse_with_pages(&bytes)).await)
+    }
+
     /// Get document chunks using its parsing and chunking configuration,
     /// or the default configurations if they have no configuration.
     ///
@@ -139,6 +229,307 @@ where
         self.chunk(config, content).await
     }
 
+    /// Get the chunks a document is embedded with. These are its manually curated chunks
+    /// if it has any, otherwise the stored chunks of its current parsing and chunking
+    /// configuration. If the document was not chunked with the configuration before,
+    /// it is parsed and chunked and the chunks are stored.
+    /// Errors if the manually curated chunks were saved for a previous version of the
+    /// document's content.
+    ///
+    /// * `document_id`: Document ID.
+    pub async fn get_or_create_chunks(
+        &self,
+        document_id: Uuid,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        let config = self.get_config(document_id).await?;
+
+        if config.stale_chunks {
+            return err!(
+                Chunks,
+                "Manual chunks of document with ID {document_id} were saved for a previous version of its content; save or delete them again"
+            );
+        }
+
+        if config.chunk_source == ChunkSource::Manual {
+            return self
+                .repo
+                .list_chunks(document_id, Some(MANUAL_CHUNKS))
+                .await;
+        }
+
+        let Some(chunk_config) = config.chunk_config else {
+            return err!(
+                DoesNotExist,
+                "Chunking config for document with ID {document_id}"
+            );
+        };
+
+        let config_hash =
+            config_fingerprint(&config.hash, config.parse_config.as_ref(), &chunk_config);
+
+        let existing = self
+            .repo
+            .list_chunks(document_id, Some(&config_hash))
+            .await?;
+
+        if !existing.is_empty() {
+            return Ok(existing);
+        }
+
+        let content = self.get_content(document_id).await?;
+        let chunked = self.chunk(chunk_config, &content).await?;
+
+        let chunks = match chunked {
+            ChunkedDocument::Ref(ref r) => r.clone(),
+            ChunkedDocument::Owned(ref o) => o.iter().map(|s| s.as_str()).collect(),
+        };
+
+        let inserts = locate_chunks(&content, &chunks)
+            .into_iter()
+            .zip(chunks)
+            .enumerate()
+            .map(|(i, (offsets, chunk))| ChunkInsert::new(i, chunk, offsets))
+            .collect();
+
+        let mut tx = self.repo.start_tx().await?;
+
+        match self
+            .repo
+            .insert_chunks(
+                document_id,
+                &config_hash,
+                &config.hash,
+                inserts,
+                Some(&mut tx),
+            )
+            .await
+        {
+            Ok(inserted) => {
+                self.repo.commit_tx(tx).await?;
+                Ok(inserted)
+            }
+            Err(e) => {
+                self.repo.abort_tx(tx).await?;
+
+                // The chunks could have been stored concurrently
+                let existing = self
+                    .repo
+                    .list_chunks(document_id, Some(&config_hash))
+                    .await?;
+
+                if existing.is_empty() {
+                    return Err(e);
+                }
+
+                Ok(existing)
+            }
+        }
+    }
+
+    /// Get a stored chunk.
+    ///
+    /// * `id`: Chunk ID.
+    pub async fn get_chunk(&self, id: Uuid) -> Result<Chunk, ChonkitError> {
+        match self.repo.get_chunk(id).await? {
+            Some(chunk) => Ok(chunk),
+            None => err!(DoesNotExist, "Chunk with ID {id}"),
+        }
+    }
+
+    /// Get stored chunks by their IDs. Chunks that do not exist are omitted.
+    ///
+    /// * `ids`: Chunk IDs.
+    pub async fn get_chunks_by_id(&self, ids: &[Uuid]) -> Result<Vec<Chunk>, ChonkitError> {
+        self.repo.get_chunks_by_id(ids).await
+    }
+
+    /// List the stored chunks of a document.
+    ///
+    /// * `document_id`: Document ID.
+    /// * `current`: If `true`, only list the chunks the document is currently embedded with,
+    ///   i.e. its manually curated chunks or the chunks of its current configuration.
+    ///   Manually curated chunks are listed even if they are stale, see
+    ///   [DocumentConfig::stale_chunks].
+    pub async fn list_chunks(
+        &self,
+        document_id: Uuid,
+        current: bool,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        let config = self.get_config(document_id).await?;
+
+        if !current {
+            return self.repo.list_chunks(document_id, None).await;
+        }
+
+        if config.chunk_source == ChunkSource::Manual {
+            return self
+                .repo
+                .list_chunks(document_id, Some(MANUAL_CHUNKS))
+                .await;
+        }
+
+        let Some(ref chunk_config) = config.chunk_config else {
+            return Ok(vec![]);
+        };
+
+        let config_hash =
+            config_fingerprint(&config.hash, config.parse_config.as_ref(), chunk_config);
+
+        self.repo.list_chunks(document_id, Some(&config_hash)).await
+    }
+
+    /// Save a chunk list for the document to use instead of its chunking configuration.
+    /// Replaces any previously saved chunk list.
+    ///
+    /// * `document_id`: Document ID.
+    /// * `payload`: The chunks to save. Defaults to the chunks the document is currently
+    ///   embedded with.
+    pub async fn save_manual_chunks(
+        &self,
+        document_id: Uuid,
+        payload: SaveChunksPayload,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        map_err!(payload.validate());
+
+        let chunks = match payload {
+            SaveChunksPayload {
+                chunks: Some(chunks),
+                ..
+            } => chunks.into_iter().map(CuratedChunk::new).collect(),
+            SaveChunksPayload {
+                preview: Some(preview),
+                ..
+            } => self
+                .chunk_preview(document_id, preview)
+                .await?
+                .into_iter()
+                .map(CuratedChunk::new)
+                .collect(),
+            _ => self
+                .get_or_create_chunks(document_id)
+                .await?
+                .into_iter()
+                .map(|chunk| CuratedChunk::new(chunk.content))
+                .collect(),
+        };
+
+        self.store_manual_chunks(document_id, chunks).await
+    }
+
+    /// Edit the manually curated chunks of a document.
+    /// Either all edits are applied or none are.
+    ///
+    /// * `document_id`: Document ID.
+    /// * `edits`: Edits to apply in order.
+    pub async fn edit_manual_chunks(
+        &self,
+        document_id: Uuid,
+        edits: Vec<ChunkEdit>,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        let existing = self
+            .repo
+            .list_chunks(document_id, Some(MANUAL_CHUNKS))
+            .await?;
+
+        if existing.is_empty() {
+            // Differentiate between missing documents and missing chunks
+            self.get_document(document_id).await?;
+            return err!(
+                DoesNotExist,
+                "Manual chunks for document with ID {document_id}"
+            );
+        }
+
+        let mut chunks = existing
+            .into_iter()
+            .map(|chunk| CuratedChunk {
+                id: Some(chunk.id),
+                content: chunk.content,
+            })
+            .collect();
+
+        if let Err(e) = apply_chunk_edits(&mut chunks, &edits) {
+            return err!(Chunks, "{e}");
+        }
+
+        if chunks.is_empty() {
+            return err!(
+                Chunks,
+                "Edits cannot remove all chunks; delete the manual chunks instead"
+            );
+        }
+
+        self.store_manual_chunks(document_id, chunks).await
+    }
+
+    /// Delete the manually curated chunks of a document so it is chunked with its
+    /// chunking configuration again. Return the amount of chunks deleted.
+    ///
+    /// * `document_id`: Document ID.
+    pub async fn delete_manual_chunks(&self, document_id: Uuid) -> Result<u64, ChonkitError> {
+        self.get_document(document_id).await?;
+        self.repo
+            .delete_chunks(document_id, MANUAL_CHUNKS, None)
+            .await
+    }
+
+    /// Replace the manually curated chunks of a document.
+    /// Chunks are located in the document's current content, and are stored with
+    /// its hash so they can be detected as stale when the content changes.
+    async fn store_manual_chunks(
+        &self,
+        document_id: Uuid,
+        chunks: Vec<CuratedChunk>,
+    ) -> Result<Vec<Chunk>, ChonkitError> {
+        let document = self.get_document(document_id).await?;
+        let content = self.get_content(document_id).await?;
+
+        let contents: Vec<&str> = chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+
+        let inserts: Vec<ChunkInsert<'_>> = locate_chunks(&content, &contents)
+            .into_iter()
+            .zip(chunks.iter())
+            .enumerate()
+            .map(|(i, (offsets, chunk))| {
+                let insert = ChunkInsert::new(i, &chunk.content, offsets);
+                match chunk.id {
+                    Some(id) => insert.with_id(id),
+                    None => insert,
+                }
+            })
+            .collect();
+
+        transaction!(self.repo, |tx| async move {
+            let tx: &mut R::Tx = tx;
+            self.repo
+                .delete_chunks(document_id, MANUAL_CHUNKS, Some(tx))
+                .await?;
+            self.repo
+                .insert_chunks(
+                    document_id,
+                    MANUAL_CHUNKS,
+                    &document.hash,
+                    inserts,
+                    Some(tx),
+                )
+                .await
+        })
+    }
+
+    /// Full text search over the stored chunks of all documents.
+    ///
+    /// * `params`: Search query and limits.
+    pub async fn search_chunks(&self, params: ChunkSearch) -> Result<Vec<Chunk>, ChonkitError> {
+        map_err!(params.validate());
+        self.repo
+            .search_chunks(
+                &params.query,
+                params.document_id,
+                params.limit.unwrap_or(10) as i64,
+            )
+            .await
+    }
+
     /// Insert the document metadata to the repository and persist it
     /// in the underlying storage implementation.
     ///
@@ -151,7 +542,13 @@ where
     ) -> Result<DocumentConfig, ChonkitError> {
         map_err!(params.validify());
 
-        let DocumentUpload { ref name, ty, file } = params;
+        let DocumentUpload {
+            ref name,
+            ty,
+            file,
+            ref label,
+        } = params;
+        let label = label.as_deref().filter(|label| !label.is_empty());
         let hash = sha256(file);
         let store = self.providers.document.get_provider(storage_provider)?;
 
@@ -164,22 +561,104 @@ where
             );
         };
 
+        let rules = self.repo.list_rules().await?;
+        let facts = DocumentFacts {
+            name,
+            ty,
+            size: file.len(),
+            src: store.id(),
+            label,
+        };
+        let initial = self.initial_config(&rules, &facts).await?;
+
         transaction!(self.repo, |tx| async move {
             let path = store.write(name, file).await?;
 
-            let insert = DocumentInsert::new(name, &path, ty, &hash, store.id());
-            let parse_config = ParseConfig::default();
-            let chunk_config = ChunkConfig::snapping_default();
+            let mut insert = DocumentInsert::new(name, &path, ty, &hash, store.id());
+            if let Some(label) = label {
+                insert = insert.with_label(label);
+            }
+
+            self.insert_with_initial_config(insert, initial, tx).await
+        })
+    }
 
-            let document = self
-                .repo
-                .insert_with_configs(insert, parse_config, chunk_config, tx)
-                .await?;
+    /// Choose the configurations of a new document with the first rule matching it.
+    /// Documents not matching any rule get the default configurations.
+    ///
+    /// * `rules`: Rules in the order they are checked in.
+    /// * `facts`: What is known about the document.
+    async fn initial_config(
+        &self,
+        rules: &[ConfigRule],
+        facts: &DocumentFacts<'_>,
+    ) -> Result<InitialConfig, ChonkitError> {
+        let Some(rule) = rules.iter().find(|rule| rule.criteria.matches(facts)) else {
+            return Ok(InitialConfig::default());
+        };
 
-            Ok(document)
+        info!("Rule '{}' applies to document '{}'", rule.name, facts.name);
+
+        if let Some(preset_id) = rule.preset_id {
+            let Some(preset) = self.repo.get_preset(preset_id).await? else {
+                return err!(DoesNotExist, "Preset with ID '{preset_id}'");
+            };
+
+            return Ok(InitialConfig {
+                parse_config: preset.parse_config.0,
+                chunk_config: preset.chunk_config.0,
+                preset_id: Some(preset_id),
+            });
+        }
+
+        Ok(InitialConfig {
+            parse_config: rule
+                .parse_config
+                .as_ref()
+                .map(|config| config.0.clone())
+                .unwrap_or_default(),
+            chunk_config: rule
+                .chunk_config
+                .as_ref()
+                .map(|config| config.0.clone())
+                .unwrap_or_else(ChunkConfig::snapping_default),
+            preset_id: None,
         })
     }
 
+    /// Insert a document along with its configurations, linking it to the preset they
+    /// came from, if any.
+    async fn insert_with_initial_config(
+        &self,
+        insert: DocumentInsert<'_>,
+        initial: InitialConfig,
+        tx: &mut R::Tx,
+    ) -> Result<DocumentConfig, ChonkitError> {
+        let InitialConfig {
+            parse_config,
+            chunk_config,
+            preset_id,
+        } = initial;
+
+        let mut document = self
+            .repo
+            .insert_with_configs(insert, parse_config, chunk_config, &mut *tx)
+            .await?;
+
+        if let Some(preset_id) = preset_id {
+            let selection = DocumentSelection {
+                documents: &[document.id],
+                ..Default::default()
+            };
+            self.repo
+                .link_documents(preset_id, selection, Some(tx))
+                .await?;
+            document.preset_id = Some(preset_id);
+        }
+
+        Ok(document)
+    }
+
     /// Remove the document from the repo and delete it from the storage.
     ///
     /// * `id`: Document ID.
@@ -203,6 +682,7 @@ where
     }
 
     /// Sync storage contents with the repo.
+    /// Updates the hashes of documents whose content changed in storage.
     pub async fn sync(&self, storage_provider: &str) -> Result<(), ChonkitError> {
         let __start = Instant::now();
         let store = self.providers.document.get_provider(storage_provider)?;
@@ -213,8 +693,7 @@ where
             .repo
             .list(
                 PaginationSort::new_default_sort(Pagination::new(10_000, 1)),
-                Some(store.id()),
-                None,
+                &DocumentFilter::src(store.id()),
             )
             .await?;
 
@@ -226,6 +705,7 @@ where
 
         // Store
         let files = store.list_files().await?;
+        let rules = self.repo.list_rules().await?;
 
         for file in files {
             let content = store.get_bytes(&file.path).await?;
@@ -233,15 +713,57 @@ where
 
             let doc = self.repo.get_by_path(&file.path, store.id()).await?;
 
-            if let Some(Document { id, name, .. }) = doc {
-                info!("Document '{name}' already exists ({id})");
+            if let Some(Document {
+                id,
+                name,
+                hash: existing,
+                ..
+            }) = doc
+            {
+                if existing == hash {
+                    info!("Document '{name}' already exists ({id})");
+                    continue;
+                }
+
+                // Content changed, chunks are fingerprinted with the new hash
+                // and manually curated chunks become stale
+                info!("Document '{name}' changed, updating hash ({id})");
+                if let Err(e) = self.repo.update_hash(id, &hash).await {
+                    tracing::error!("{e}");
+                }
                 continue;
             }
 
+            let facts = DocumentFacts {
+                name: &file.name,
+                ty: file.ext,
+                size: content.len(),
+                src: store.id(),
+                label: None,
+            };
+
+            let initial = match self.initial_config(&rules, &facts).await {
+                Ok(initial) => initial,
+                Err(e) => {
+                    tracing::error!("{e}");
+                    continue;
+                }
+            };
+
             let insert = DocumentInsert::new(&file.name, &file.path, file.ext, &hash, store.id());
 
-            match self.repo.insert(insert).await {
-                Ok(Document { id, name, .. }) => info!("Successfully inserted '{name}' ({id})"),
+            // The block keeps the macro from returning early on errors
+            let inserted = async {
+                transaction!(self.repo, |tx| async move {
+                    self.insert_with_initial_config(insert, initial, tx).await
+                })
+            }
+            .await;
+
+            match inserted {
+                Ok(DocumentConfig { id, name, .. }) => {
+                    info!("Successfully inserted '{name}' ({id})")
+                }
                 Err(e) => tracing::error!("{e}"),
             }
         }
@@ -286,7 +808,11 @@ where
         Ok(chunks)
     }
 
-    async fn chunk<'i>(
+    /// Chunk the input with the given configuration.
+    ///
+    /// * `config`: Chunking configuration.
+    /// * `input`: Text to chunk.
+    pub async fn chunk<'i>(
         &self,
         config: ChunkConfig,
         input: &'i str,
@@ -353,6 +879,12 @@ where
 
                 ChunkedDocument::Owned(chunked)
             }
+            ChunkConfig::Markdown(config) => {
+                let chunker = map_err!(chunx::MarkdownWindow::new(config.size));
+                let chunked = map_err!(chunker.chunk(input));
+
+                ChunkedDocument::Ref(chunked)
+            }
         };
 
         if chunks.is_empty() {
@@ -391,6 +923,7 @@ where
     }
 
     /// Update a document's parsing configuration.
+    /// Unlinks the document from its preset, if any.
     ///
     /// * `id`: Document ID.
     /// * `config`: Parsing configuration.
@@ -404,11 +937,13 @@ where
         }
 
         self.repo.upsert_parse_config(id, config).await?;
+        self.repo.unlink_document(id).await?;
 
         Ok(())
     }
 
     /// Update a document's chunking configuration.
+    /// Unlinks the document from its preset, if any.
     ///
     /// * `id`: Document ID.
     /// * `config`: Chunking configuration.
@@ -420,6 +955,7 @@ where
         }
 
         self.repo.upsert_chunk_config(id, config).await?;
+        self.repo.unlink_document(id).await?;
 
         Ok(())
     }
@@ -466,13 +1002,127 @@ where
     }
 }
 
+/// Configurations a new document starts with.
+struct InitialConfig {
+    parse_config: ParseConfig,
+    chunk_config: ChunkConfig,
+    /// The preset the configurations come from.
+    preset_id: Option<Uuid>,
+}
+
+impl Default for InitialConfig {
+    fn default() -> Self {
+        Self {
+            parse_config: ParseConfig::default(),
+            chunk_config: ChunkConfig::snapping_default(),
+            preset_id: None,
+        }
+    }
+}
+
 /// Document service DTOs.
 pub mod dto {
     use crate::core::{
-        chunk::ChunkConfig, document::parser::ParseConfig, model::document::DocumentType,
+        chunk::ChunkConfig,
+        document::parser::ParseConfig,
+        model::document::{DocumentFilter, DocumentType, DocumentUpdate},
     };
     use serde::Deserialize;
-    use validify::{Validate, Validify};
+    use uuid::Uuid;
+    use validify::{schema_err, schema_validation, Validate, ValidationErrors, Validify};
+
+    /// Document metadata to update. Unset fields are left as is.
+    /// Tags are replaced first, then added, then removed.
+    #[derive(Debug, Default, Deserialize, Validify, utoipa::ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct DocumentUpdatePayload {
+        /// New document name.
+        #[modify(trim)]
+        #[validate(length(min = 1))]
+        pub name: Option<String>,
+
+        /// New document label. An empty label removes the current one.
+        #[modify(trim)]
+        pub label: Option<String>,
+
+        /// Replaces the document's tags.
+        pub tags: Option<Vec<String>>,
+
+        /// Tags to add to the document.
+        #[serde(default)]
+        pub add_tags: Vec<String>,
+
+        /// Tags to remove from the document.
+        #[serde(default)]
+        pub remove_tags: Vec<String>,
+    }
+
+    impl DocumentUpdatePayload {
+        pub(super) fn to_update(&self) -> DocumentUpdate<'_> {
+            DocumentUpdate {
+                name: self.name.as_deref(),
+                label: self.label.as_deref(),
+                tags: self.tags.as_deref(),
+                add_tags: &self.add_tags,
+                remove_tags: &self.remove_tags,
+            }
+        }
+
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            if self.name.is_none()
+                && self.label.is_none()
+                && self.tags.is_none()
+                && self.add_tags.is_empty()
+                && self.remove_tags.is_empty()
+            {
+                schema_err!("no_update", "no document metadata to update");
+            }
+            if self
+                .tags
+                .iter()
+                .flatten()
+                .chain(self.add_tags.iter())
+                .any(|tag| tag.trim().is_empty())
+            {
+                schema_err!("empty_tag", "tags cannot be empty");
+            }
+        }
+    }
+
+    /// Updates the label and tags of the given documents and of all documents
+    /// matching the filter.
+    #[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct DocumentBulkUpdatePayload {
+        /// Document IDs.
+        #[serde(default)]
+        pub documents: Vec<Uuid>,
+
+        /// Selects documents by their metadata.
+        #[validate]
+        pub filter: Option<DocumentFilter>,
+
+        /// Metadata to update. Documents cannot be renamed in bulk.
+        pub update: DocumentUpdatePayload,
+    }
+
+    impl DocumentBulkUpdatePayload {
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            if self.documents.is_empty() && self.filter.is_none() {
+                schema_err!(
+                    "no_documents",
+                    "either `documents` or `filter` must be given"
+                );
+            }
+            if self.update.name.is_some() {
+                schema_err!("bulk_rename", "documents cannot be renamed in bulk");
+            }
+        }
+    }
 
     #[derive(Debug, Validify)]
     pub struct DocumentUpload<'a> {
@@ -486,11 +1136,75 @@ pub mod dto {
 
         /// Document file.
         pub file: &'a [u8],
+
+        /// Document label.
+        #[modify(trim)]
+        pub label: Option<String>,
     }
 
     impl<'a> DocumentUpload<'a> {
         pub fn new(name: String, ty: DocumentType, file: &'a [u8]) -> Self {
-            Self { name, ty, file }
+            Self {
+                name,
+                ty,
+                file,
+                label: None,
+            }
+        }
+
+        pub fn with_label(mut self, label: Option<String>) -> Self {
+            self.label = label;
+            self
+        }
+    }
+
+    /// Full text search over stored chunks.
+    #[derive(Debug, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ChunkSearch {
+        /// Search query. Supports quoted phrases, `or` and `-` for exclusion.
+        #[validate(length(min = 1, message = "Search query cannot be empty."))]
+        pub query: String,
+
+        /// Only search the chunks of this document.
+        pub document_id: Option<uuid::Uuid>,
+
+        /// Maximum amount of chunks to return. Defaults to 10.
+        #[validate(range(min = 1., max = 100.))]
+        pub limit: Option<u32>,
+    }
+
+    /// Chunks to save as a document's manually curated chunk list. At most one of
+    /// `chunks` and `preview` can be given. If neither is, the chunks the document is
+    /// currently embedded with are saved.
+    #[derive(Debug, Default, Deserialize, Validate, utoipa::ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct SaveChunksPayload {
+        /// Explicit chunk list.
+        pub chunks: Option<Vec<String>>,
+
+        /// Save the chunks produced by this configuration, as in chunk previews.
+        pub preview: Option<ChunkPreviewPayload>,
+    }
+
+    impl SaveChunksPayload {
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            if self.chunks.is_some() && self.preview.is_some() {
+                schema_err!(
+                    "ambiguous_chunks",
+                    "only one of `chunks` and `preview` can be given"
+                );
+            }
+            if let Some(ref chunks) = self.chunks {
+                if chunks.is_empty() {
+                    schema_err!("no_chunks", "`chunks` cannot be empty");
+                }
+                if chunks.iter().any(|chunk| chunk.trim().is_empty()) {
+                    schema_err!("empty_chunk", "`chunks` cannot contain empty chunks");
+                }
+            }
         }
     }
 
@@ -0,0 +1,367 @@
+use crate::core::model::eval::{
+    EvalDataset, EvalDatasetDisplay, EvalQuestion, EvalRun, EvalRunInsert,
+};
+use crate::core::repo::{eval::EvalRepo, Atomic};
+use crate::error::ChonkitError;
+use crate::{err, map_err, transaction};
+use dto::{CreateEvalDatasetPayload, EvalQuestionPayload};
+use uuid::Uuid;
+use validify::Validify;
+
+/// High level operations for managing retrieval evaluation datasets and their results.
+#[derive(Clone)]
+pub struct EvalService<R> {
+    repo: R,
+}
+
+impl<R> EvalService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R> EvalService<R>
+where
+    R: EvalRepo + Atomic + Send + Sync,
+    R::Tx: Send + Sync,
+{
+    pub async fn list_datasets(&self) -> Result<Vec<EvalDataset>, ChonkitError> {
+        self.repo.list_datasets().await
+    }
+
+    /// Get a dataset along with its questions.
+    ///
+    /// * `id`: Dataset ID.
+    pub async fn get_dataset(&self, id: Uuid) -> Result<EvalDatasetDisplay, ChonkitError> {
+        let Some(dataset) = self.repo.get_dataset(id).await? else {
+            return err!(DoesNotExist, "Evaluation dataset with ID '{id}'");
+        };
+
+        let questions = self.repo.list_questions(id).await?;
+
+        Ok(EvalDatasetDisplay { dataset, questions })
+    }
+
+    /// Create a dataset along with its initial questions.
+    ///
+    /// * `payload`: Dataset name and questions.
+    pub async fn create_dataset(
+        &self,
+        mut payload: CreateEvalDatasetPayload,
+    ) -> Result<EvalDatasetDisplay, ChonkitError> {
+        map_err!(payload.validify());
+
+        let CreateEvalDatasetPayload { name, questions } = payload;
+
+        transaction!(self.repo, |tx| async move {
+            let tx: &mut R::Tx = tx;
+
+            let dataset = self.repo.insert_dataset(&name, Some(&mut *tx)).await?;
+
+            let inserts = questions
+                .iter()
+                .map(EvalQuestionPayload::to_insert)
+                .collect();
+
+            let questions = self
+                .repo
+                .insert_questions(dataset.id, inserts, Some(tx))
+                .await?;
+
+            Ok(EvalDatasetDisplay { dataset, questions })
+        })
+    }
+
+    /// Delete a dataset along with its questions and runs.
+    ///
+    /// * `id`: Dataset ID.
+    pub async fn delete_dataset(&self, id: Uuid) -> Result<(), ChonkitError> {
+        if self.repo.delete_dataset(id).await? == 0 {
+            return err!(DoesNotExist, "Evaluation dataset with ID '{id}'");
+        }
+        Ok(())
+    }
+
+    /// Add questions to an existing dataset.
+    ///
+    /// * `dataset_id`: Dataset ID.
+    /// * `questions`: Questions to add.
+    pub async fn add_questions(
+        &self,
+        dataset_id: Uuid,
+        mut questions: Vec<EvalQuestionPayload>,
+    ) -> Result<Vec<EvalQuestion>, ChonkitError> {
+        for question in questions.iter_mut() {
+            map_err!(question.validify());
+        }
+
+        if self.repo.get_dataset(dataset_id).await?.is_none() {
+            return err!(DoesNotExist, "Evaluation dataset with ID '{dataset_id}'");
+        }
+
+        let inserts = questions
+            .iter()
+            .map(EvalQuestionPayload::to_insert)
+            .collect();
+
+        self.repo.insert_questions(dataset_id, inserts, None).await
+    }
+
+    /// Delete a question from its dataset.
+    ///
+    /// * `id`: Question ID.
+    pub async fn delete_question(&self, id: Uuid) -> Result<(), ChonkitError> {
+        if self.repo.delete_question(id).await? == 0 {
+            return err!(DoesNotExist, "Evaluation question with ID '{id}'");
+        }
+        Ok(())
+    }
+
+    /// List the questions of a dataset. Errors if the dataset does not exist.
+    ///
+    /// * `dataset_id`: Dataset ID.
+    pub async fn list_questions(
+        &self,
+        dataset_id: Uuid,
+    ) -> Result<Vec<EvalQuestion>, ChonkitError> {
+        Ok(self.get_dataset(dataset_id).await?.questions)
+    }
+
+    /// List the runs of a dataset, most recent first.
+    ///
+    /// * `dataset_id`: Dataset ID.
+    pub async fn list_runs(&self, dataset_id: Uuid) -> Result<Vec<EvalRun>, ChonkitError> {
+        self.repo.list_runs(dataset_id).await
+    }
+
+    /// Get an evaluation run.
+    ///
+    /// * `id`: Run ID.
+    pub async fn get_run(&self, id: Uuid) -> Result<EvalRun, ChonkitError> {
+        match self.repo.get_run(id).await? {
+            Some(run) => Ok(run),
+            None => err!(DoesNotExist, "Evaluation run with ID '{id}'"),
+        }
+    }
+
+    /// Store the results of an evaluation run.
+    ///
+    /// * `insert`: Run results.
+    pub async fn insert_run(&self, insert: EvalRunInsert<'_>) -> Result<EvalRun, ChonkitError> {
+        self.repo.insert_run(insert).await
+    }
+}
+
+/// Evaluation service DTOs.
+pub mod dto {
+    use crate::core::{
+        eval::{EvalMetric, SweepChunker, SweepGrid},
+        model::eval::EvalQuestionInsert,
+    };
+    use serde::Deserialize;
+    use utoipa::ToSchema;
+    use uuid::Uuid;
+    use validify::{schema_err, schema_validation, ValidationErrors, Validify};
+
+    #[derive(Debug, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CreateEvalDatasetPayload {
+        /// Unique dataset name.
+        #[validate(length(min = 1))]
+        #[modify(trim)]
+        pub name: String,
+
+        /// Initial questions of the dataset.
+        #[validate]
+        #[serde(default)]
+        pub questions: Vec<EvalQuestionPayload>,
+    }
+
+    /// A question and what it is expected to retrieve. At least one
+    /// document or span must be given.
+    #[derive(Debug, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct EvalQuestionPayload {
+        #[validate(length(min = 1))]
+        #[modify(trim)]
+        pub question: String,
+
+        /// Documents whose chunks are relevant to the question.
+        #[serde(default)]
+        pub documents: Vec<Uuid>,
+
+        /// Text spans whose chunks are relevant to the question. A chunk is
+        /// relevant if it contains a span or is contained in one.
+        #[serde(default)]
+        pub spans: Vec<String>,
+    }
+
+    impl EvalQuestionPayload {
+        pub(super) fn to_insert(&self) -> EvalQuestionInsert<'_> {
+            EvalQuestionInsert::new(&self.question, &self.documents, &self.spans)
+        }
+
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            if self.documents.is_empty() && self.spans.is_empty() {
+                schema_err!(
+                    "no_expectations",
+                    "either `documents` or `spans` must be given"
+                );
+            }
+            if self.spans.iter().any(|span| span.trim().is_empty()) {
+                schema_err!("empty_span", "`spans` cannot contain empty spans");
+            }
+        }
+    }
+
+    /// Params for evaluating a dataset against a collection.
+    #[derive(Debug, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RunEvaluationPayload {
+        /// The collection to evaluate.
+        pub collection_id: Uuid,
+
+        /// Amount of chunks to retrieve per question. Defaults to 10.
+        #[validate(range(min = 1., max = 100.))]
+        pub k: Option<u32>,
+    }
+
+    /// Params for generating an evaluation dataset from document chunks with a chat model.
+    #[derive(Debug, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct GenerateQuestionsPayload {
+        /// Name of the dataset to create.
+        #[validate(length(min = 1))]
+        #[modify(trim)]
+        pub name: String,
+
+        /// Documents to sample chunks from. Documents are chunked with
+        /// their current configuration.
+        #[validate(length(min = 1))]
+        pub documents: Vec<Uuid>,
+
+        /// Amount of chunks sampled per document. Chunks are sampled evenly
+        /// across the document. Defaults to 5.
+        #[validate(range(min = 1., max = 100.))]
+        pub chunks_per_document: Option<usize>,
+
+        /// Amount of questions generated per chunk. Defaults to 1.
+        #[validate(range(min = 1., max = 10.))]
+        pub questions_per_chunk: Option<usize>,
+
+        /// Chat model to use. Defaults to the configured model.
+        pub model: Option<String>,
+
+        /// System prompt. Defaults to
+        /// [DEFAULT_QUESTION_SYSTEM_PROMPT](crate::core::generator::DEFAULT_QUESTION_SYSTEM_PROMPT).
+        pub system_prompt: Option<String>,
+
+        /// Prompt template containing the `{chunk}` placeholder and optionally
+        /// `{document}` (the document name) and `{count}` (the amount of questions).
+        /// Defaults to [DEFAULT_QUESTION_PROMPT](crate::core::generator::DEFAULT_QUESTION_PROMPT).
+        pub prompt: Option<String>,
+    }
+
+    impl GenerateQuestionsPayload {
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            if self
+                .prompt
+                .as_ref()
+                .is_some_and(|prompt| !prompt.contains("{chunk}"))
+            {
+                schema_err!(
+                    "missing_chunk_placeholder",
+                    "`prompt` must contain the `{chunk}` placeholder"
+                );
+            }
+        }
+    }
+
+    /// Maximum amount of candidates a sweep grid can expand to.
+    pub const MAX_SWEEP_CANDIDATES: usize = 100;
+
+    /// Params for sweeping chunking configurations of documents against a dataset.
+    #[derive(Debug, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct SweepPayload {
+        /// Collection whose providers, models and index configuration are used
+        /// for the collections of the candidates.
+        pub collection_id: Uuid,
+
+        /// Documents to chunk with every candidate. Defaults to the documents
+        /// expected by the dataset's questions.
+        #[serde(default)]
+        pub documents: Vec<Uuid>,
+
+        /// Chunking configurations to evaluate.
+        pub grid: SweepGrid,
+
+        /// Amount of chunks to retrieve per question. Defaults to 10.
+        #[validate(range(min = 1., max = 100.))]
+        pub k: Option<u32>,
+
+        /// Metric the best candidate is chosen by. Defaults to nDCG.
+        #[serde(default)]
+        pub metric: EvalMetric,
+
+        /// Maximum amount of chunks embedded during the sweep. Candidates that
+        /// would exceed it are skipped.
+        #[validate(range(min = 1.))]
+        pub max_chunks: Option<usize>,
+
+        /// Maximum duration of the sweep in seconds. Checked before every candidate,
+        /// the remaining candidates are skipped once exceeded.
+        #[validate(range(min = 1.))]
+        pub max_duration: Option<u64>,
+
+        /// If true, the best configuration is set as the chunking configuration
+        /// of the documents.
+        #[serde(default)]
+        pub apply: bool,
+    }
+
+    impl SweepPayload {
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            let grid = &self.grid;
+
+            if grid.chunkers.is_empty() {
+                schema_err!("no_chunkers", "`grid.chunkers` cannot be empty");
+            }
+            if grid.sizes.is_empty() || grid.sizes.contains(&0) {
+                schema_err!(
+                    "invalid_sizes",
+                    "`grid.sizes` must contain at least one non-zero size"
+                );
+            }
+            if grid
+                .chunkers
+                .iter()
+                .any(|c| matches!(c, SweepChunker::Sliding | SweepChunker::Snapping))
+                && grid.overlaps.is_empty()
+            {
+                schema_err!(
+                    "no_overlaps",
+                    "`grid.overlaps` must be given for sliding and snapping chunkers"
+                );
+            }
+            if grid.chunkers.contains(&SweepChunker::Semantic) && grid.thresholds.is_empty() {
+                schema_err!(
+                    "no_thresholds",
+                    "`grid.thresholds` must be given for semantic chunkers"
+                );
+            }
+            if grid.candidates(Some(("", ""))).len() > MAX_SWEEP_CANDIDATES {
+                schema_err!(
+                    "too_many_candidates",
+                    "`grid` cannot expand to more than 100 candidates"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::core::model::preset::{DocumentSelection, Preset, PresetDisplay, PresetInsert};
+use crate::core::repo::{preset::PresetRepo, Atomic};
+use crate::error::ChonkitError;
+use crate::{err, map_err, transaction};
+use dto::{AssignPresetPayload, PresetPayload};
+use uuid::Uuid;
+use validify::Validify;
+
+/// High level operations for managing parsing and chunking presets.
+///
+/// Linked documents reference their preset and have their configurations overwritten
+/// whenever the preset is assigned or updated.
+#[derive(Clone)]
+pub struct PresetService<R> {
+    repo: R,
+}
+
+impl<R> PresetService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R> PresetService<R>
+where
+    R: PresetRepo + Atomic + Send + Sync,
+    R::Tx: Send + Sync,
+{
+    pub async fn list_presets(&self) -> Result<Vec<Preset>, ChonkitError> {
+        self.repo.list_presets().await
+    }
+
+    /// Get a preset along with its linked documents.
+    ///
+    /// * `id`: Preset ID.
+    pub async fn get_preset(&self, id: Uuid) -> Result<PresetDisplay, ChonkitError> {
+        let Some(preset) = self.repo.get_preset(id).await? else {
+            return err!(DoesNotExist, "Preset with ID '{id}'");
+        };
+
+        let documents = self.repo.list_preset_documents(id).await?;
+
+        Ok(PresetDisplay { preset, documents })
+    }
+
+    /// Create a preset.
+    ///
+    /// * `payload`: Preset name and configurations.
+    pub async fn create_preset(&self, mut payload: PresetPayload) -> Result<Preset, ChonkitError> {
+        map_err!(payload.validify());
+
+        let PresetPayload {
+            name,
+            parse_config,
+            chunk_config,
+        } = payload;
+
+        let insert = PresetInsert::new(&name, parse_config, chunk_config);
+
+        self.repo.insert_preset(insert).await
+    }
+
+    /// Update a preset and the configurations of all documents linked to it.
+    ///
+    /// * `id`: Preset ID.
+    /// * `payload`: New preset name and configurations.
+    pub async fn update_preset(
+        &self,
+        id: Uuid,
+        mut payload: PresetPayload,
+    ) -> Result<PresetDisplay, ChonkitError> {
+        map_err!(payload.validify());
+
+        let PresetPayload {
+            name,
+            parse_config,
+            chunk_config,
+        } = payload;
+
+        let preset = transaction!(self.repo, |tx| async move {
+            let tx: &mut R::Tx = tx;
+
+            let Some(preset) = self
+                .repo
+                .update_preset(id, &name, parse_config, chunk_config, Some(&mut *tx))
+                .await?
+            else {
+                return err!(DoesNotExist, "Preset with ID '{id}'");
+            };
+
+            self.repo.sync_preset_configs(id, Some(tx)).await?;
+
+            Ok(preset)
+        })?;
+
+        let documents = self.repo.list_preset_documents(id).await?;
+
+        Ok(PresetDisplay { preset, documents })
+    }
+
+    /// Delete a preset. Linked documents keep their current configurations.
+    /// Presets used by configuration rules cannot be deleted.
+    ///
+    /// * `id`: Preset ID.
+    pub async fn delete_preset(&self, id: Uuid) -> Result<(), ChonkitError> {
+        if self.repo.delete_preset(id).await? == 0 {
+            return err!(DoesNotExist, "Preset with ID '{id}'");
+        }
+        Ok(())
+    }
+
+    /// Link the selected documents to a preset and overwrite their configurations
+    /// with the preset's. Return the IDs of the newly linked documents.
+    ///
+    /// * `id`: Preset ID.
+    /// * `payload`: Documents to link, by ID, label or tags.
+    pub async fn assign_preset(
+        &self,
+        id: Uuid,
+        mut payload: AssignPresetPayload,
+    ) -> Result<Vec<Uuid>, ChonkitError> {
+        map_err!(payload.validify());
+
+        if self.repo.get_preset(id).await?.is_none() {
+            return err!(DoesNotExist, "Preset with ID '{id}'");
+        }
+
+        let AssignPresetPayload {
+            documents,
+            label,
+            tags,
+        } = payload;
+
+        transaction!(self.repo, |tx| async move {
+            let tx: &mut R::Tx = tx;
+
+            let selection = DocumentSelection {
+                documents: &documents,
+                label: label.as_deref(),
+                tags: &tags,
+            };
+
+            let linked = self
+                .repo
+                .link_documents(id, selection, Some(&mut *tx))
+                .await?;
+
+            self.repo.sync_preset_configs(id, Some(tx)).await?;
+
+            Ok(linked)
+        })
+    }
+}
+
+pub mod dto {
+    use crate::core::{chunk::ChunkConfig, document::parser::ParseConfig};
+    use serde::Deserialize;
+    use utoipa::ToSchema;
+    use uuid::Uuid;
+    use validify::{schema_err, schema_validation, ValidationErrors, Validify};
+
+    /// Params for creating and updating presets.
+    #[derive(Debug, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PresetPayload {
+        /// Unique preset name.
+        #[validate(length(min = 1))]
+        #[modify(trim)]
+        pub name: String,
+
+        /// Parsing configuration of linked documents.
+        #[validate]
+        pub parse_config: ParseConfig,
+
+        /// Chunking configuration of linked documents.
+        pub chunk_config: ChunkConfig,
+    }
+
+    /// Selects the documents to link to a preset. A document is selected if it
+    /// matches any of the criteria. At least one criterion must be given.
+    #[derive(Debug, Default, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct AssignPresetPayload {
+        /// Document IDs.
+        #[serde(default)]
+        pub documents: Vec<Uuid>,
+
+        /// Documents with the label.
+        #[modify(trim)]
+        pub label: Option<String>,
+
+        /// Documents with any of the tags.
+        #[serde(default)]
+        pub tags: Vec<String>,
+    }
+
+    impl AssignPresetPayload {
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            if self.documents.is_empty()
+                && self.label.as_ref().is_none_or(|label| label.is_empty())
+                && self.tags.is_empty()
+            {
+                schema_err!(
+                    "no_selection",
+                    "either `documents`, `label` or `tags` must be given"
+                );
+            }
+        }
+    }
+}
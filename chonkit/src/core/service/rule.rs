@@ -0,0 +1,160 @@
+use crate::core::model::rule::ConfigRule;
+use crate::core::repo::{preset::PresetRepo, rule::RuleRepo};
+use crate::error::ChonkitError;
+use crate::{err, map_err};
+use dto::RulePayload;
+use uuid::Uuid;
+use validify::Validify;
+
+/// High level operations for managing the rules choosing the initial parsing and
+/// chunking configurations of uploaded and synced documents.
+#[derive(Clone)]
+pub struct RuleService<R> {
+    repo: R,
+}
+
+impl<R> RuleService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R> RuleService<R>
+where
+    R: RuleRepo + PresetRepo + Send + Sync,
+{
+    /// List rules in the order they are checked in.
+    pub async fn list_rules(&self) -> Result<Vec<ConfigRule>, ChonkitError> {
+        self.repo.list_rules().await
+    }
+
+    /// Get a rule.
+    ///
+    /// * `id`: Rule ID.
+    pub async fn get_rule(&self, id: Uuid) -> Result<ConfigRule, ChonkitError> {
+        let Some(rule) = self.repo.get_rule(id).await? else {
+            return err!(DoesNotExist, "Rule with ID '{id}'");
+        };
+        Ok(rule)
+    }
+
+    /// Create a rule.
+    ///
+    /// * `payload`: Rule name, criteria and configurations.
+    pub async fn create_rule(&self, mut payload: RulePayload) -> Result<ConfigRule, ChonkitError> {
+        map_err!(payload.validify());
+
+        self.check_preset(&payload).await?;
+
+        self.repo.insert_rule(payload.to_insert()).await
+    }
+
+    /// Update a rule. Only affects documents added after the update.
+    ///
+    /// * `id`: Rule ID.
+    /// * `payload`: New rule name, criteria and configurations.
+    pub async fn update_rule(
+        &self,
+        id: Uuid,
+        mut payload: RulePayload,
+    ) -> Result<ConfigRule, ChonkitError> {
+        map_err!(payload.validify());
+
+        self.check_preset(&payload).await?;
+
+        let Some(rule) = self.repo.update_rule(id, payload.to_insert()).await? else {
+            return err!(DoesNotExist, "Rule with ID '{id}'");
+        };
+
+        Ok(rule)
+    }
+
+    /// Delete a rule. Documents keep the configurations it assigned.
+    ///
+    /// * `id`: Rule ID.
+    pub async fn delete_rule(&self, id: Uuid) -> Result<(), ChonkitError> {
+        if self.repo.delete_rule(id).await? == 0 {
+            return err!(DoesNotExist, "Rule with ID '{id}'");
+        }
+        Ok(())
+    }
+
+    async fn check_preset(&self, payload: &RulePayload) -> Result<(), ChonkitError> {
+        if let Some(preset_id) = payload.preset_id {
+            if self.repo.get_preset(preset_id).await?.is_none() {
+                return err!(DoesNotExist, "Preset with ID '{preset_id}'");
+            }
+        }
+        Ok(())
+    }
+}
+
+pub mod dto {
+    use crate::core::{
+        chunk::ChunkConfig, document::parser::ParseConfig, model::rule::ConfigRuleInsert,
+        rule::RuleCriteria,
+    };
+    use serde::Deserialize;
+    use utoipa::ToSchema;
+    use uuid::Uuid;
+    use validify::{schema_err, schema_validation, ValidationErrors, Validify};
+
+    /// Params for creating and updating rules. Either a preset or at least one of
+    /// the configurations must be given, but not both.
+    #[derive(Debug, Deserialize, Validify, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
+    pub struct RulePayload {
+        /// Unique rule name.
+        #[validate(length(min = 1))]
+        #[modify(trim)]
+        pub name: String,
+
+        /// Rules with higher priorities are checked first. Defaults to 0.
+        #[serde(default)]
+        pub priority: i32,
+
+        /// Conditions documents must satisfy for the rule to apply.
+        #[validate]
+        #[serde(default)]
+        pub criteria: RuleCriteria,
+
+        /// Preset to link matching documents to.
+        pub preset_id: Option<Uuid>,
+
+        /// Parsing configuration of matching documents. Defaults to the default
+        /// parsing configuration.
+        #[validate]
+        pub parse_config: Option<ParseConfig>,
+
+        /// Chunking configuration of matching documents. Defaults to the default
+        /// `SnappingWindow` configuration.
+        pub chunk_config: Option<ChunkConfig>,
+    }
+
+    impl RulePayload {
+        pub(super) fn to_insert(&self) -> ConfigRuleInsert<'_> {
+            ConfigRuleInsert::new(&self.name, self.priority, &self.criteria)
+                .with_preset(self.preset_id)
+                .with_configs(self.parse_config.as_ref(), self.chunk_config.as_ref())
+        }
+
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            let configs = self.parse_config.is_some() || self.chunk_config.is_some();
+
+            if self.preset_id.is_some() && configs {
+                schema_err!(
+                    "preset_and_configs",
+                    "`presetId` cannot be given along with `parseConfig` or `chunkConfig`"
+                );
+            }
+            if self.preset_id.is_none() && !configs {
+                schema_err!(
+                    "no_target",
+                    "either `presetId`, `parseConfig` or `chunkConfig` must be given"
+                );
+            }
+        }
+    }
+}
@@ -1,19 +1,35 @@
 use crate::config::DEFAULT_COLLECTION_NAME;
+use crate::core::embedder::InputKind;
 use crate::core::model::collection::{
-    Collection, CollectionDisplay, CollectionInsert, Embedding, EmbeddingInsert,
+    Collection, CollectionAlias, CollectionDisplay, CollectionImport, CollectionInsert,
+    CollectionShort, Embedding, EmbeddingInsert, OrphanedCollection, OrphanedVectors,
+    ReconciliationReport,
 };
 use crate::core::model::{List, Pagination, PaginationSort};
 use crate::core::provider::ProviderState;
 use crate::core::repo::vector::VectorRepo;
 use crate::core::repo::Atomic;
-use crate::core::vector::CreateVectorCollection;
+use crate::core::search::{self, FederatedHit};
+use crate::core::vector::snapshot::{
+    CollectionSnapshot, SnapshotCollection, SnapshotFormat, SnapshotWriter,
+};
+use crate::core::vector::{
+    CreateVectorCollection, QuantizationSearch, QueryVector, StoredChunk, StoredPoint, VectorDb,
+    VectorKind, Vectors,
+};
 use crate::error::{ChonkitErr, ChonkitError};
 use crate::{err, map_err, transaction};
-use dto::{CreateCollectionPayload, CreateEmbeddings, SearchPayload};
-use tracing::{error, info};
+use dto::{CloneCollectionPayload, CreateCollectionPayload, CreateEmbeddings, SearchPayload};
+use futures_util::{future, stream, Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 use validify::{Validate, Validify};
 
+/// Amount of points fetched per page when exporting collections.
+const EXPORT_PAGE_SIZE: u32 = 256;
+
 /// High level operations related to embeddings (vectors) and their storage.
 #[derive(Clone)]
 pub struct VectorService<Repo> {
@@ -103,6 +119,14 @@ where
         embedder.list_embedding_models().await
     }
 
+    /// Return a list of sparse models supported by the embedder.
+    ///
+    /// * `embedder`: The embedder to use.
+    pub async fn list_sparse_models(&self, embedder: &str) -> Result<Vec<String>, ChonkitError> {
+        let embedder = self.providers.embedding.get_provider(embedder)?;
+        embedder.list_sparse_models().await
+    }
+
     /// Create the default vector collection if it doesn't already exist.
     pub async fn create_default_collection(&self, vector_db: &str, embedder: &str) {
         let vector_db = self
@@ -124,7 +148,8 @@ where
 
             let db_insert = CollectionInsert::new(
                 &collection_name,
-                &model,
+                Some(&model),
+                None,
                 embedder.id(),
                 vector_db.id(),
             );
@@ -148,8 +173,14 @@ where
                 },
             };
 
-            let vector_db_insert =
-                CreateVectorCollection::new(collection.id, &collection_name, size, embedder.id(), &model);
+            let vector_db_insert = CreateVectorCollection::new(
+                collection.id,
+                &collection_name,
+                Some(size),
+                embedder.id(),
+                Some(&model),
+                None,
+            );
 
             vector_db.create_default_collection(vector_db_insert).await?;
 
@@ -169,25 +200,50 @@ where
         let CreateCollectionPayload {
             name,
             model,
+            sparse_model,
             vector_provider,
             embedding_provider,
+            index_config,
         } = data;
 
         let vector_db = self.providers.vector.get_provider(&vector_provider)?;
         let embedder = self.providers.embedding.get_provider(&embedding_provider)?;
 
-        let Some(size) = embedder.size(&model).await? else {
-            let embedder_id = embedder.id();
-            return err!(
-                InvalidEmbeddingModel,
-                "Model {model} not supported by embedder '{embedder_id}'"
-            );
+        let size = match model {
+            Some(ref model) => {
+                let Some(size) = embedder.size(model).await? else {
+                    let embedder_id = embedder.id();
+                    return err!(
+                        InvalidEmbeddingModel,
+                        "Model {model} not supported by embedder '{embedder_id}'"
+                    );
+                };
+                Some(size)
+            }
+            None => None,
         };
 
-        info!("Creating collection '{name}' of size '{size}'",);
+        if let Some(ref sparse_model) = sparse_model {
+            if !embedder.list_sparse_models().await?.contains(sparse_model) {
+                let embedder_id = embedder.id();
+                return err!(
+                    InvalidEmbeddingModel,
+                    "Sparse model {sparse_model} not supported by embedder '{embedder_id}'"
+                );
+            }
+        }
+
+        info!("Creating collection '{name}' of size '{size:?}' (sparse model: {sparse_model:?})");
 
         transaction!(self.repo, |tx| async move {
-            let insert = CollectionInsert::new(&name, &model, embedder.id(), vector_db.id());
+            let insert = CollectionInsert::new(
+                &name,
+                model.as_deref(),
+                sparse_model.as_deref(),
+                embedder.id(),
+                vector_db.id(),
+            )
+            .with_index_config(index_config);
             let collection = self.repo.insert_collection(insert, Some(tx)).await?;
 
             let data = CreateVectorCollection::new(
@@ -195,8 +251,10 @@ where
                 &name,
                 size,
                 &embedding_provider,
-                &model,
-            );
+                model.as_deref(),
+                sparse_model.as_deref(),
+            )
+            .with_index_config(index_config);
 
             vector_db.create_vector_collection(data).await?;
 
@@ -233,6 +291,7 @@ where
             document_id,
             collection_id,
             chunks,
+            chunk_ids,
         }: CreateEmbeddings<'_>,
     ) -> Result<Embedding, ChonkitError> {
         // Make sure the collection exists.
@@ -240,6 +299,13 @@ where
             return err!(DoesNotExist, "Collection with ID '{collection_id}'");
         };
 
+        if chunk_ids.is_some_and(|ids| ids.len() != chunks.len()) {
+            return err!(
+                Chunks,
+                "Amount of chunk IDs does not match the amount of chunks"
+            );
+        }
+
         let existing = self.repo.get_embeddings(document_id, collection.id).await?;
         if existing.is_some() {
             let name = collection.name;
@@ -257,28 +323,44 @@ where
 
         let v_collection = vector_db.get_collection(&collection.name).await?;
 
-        let Some(size) = embedder.size(&collection.model).await? else {
-            let (model, embedder) = (collection.model, embedder.id());
-            return err!(
-                InvalidEmbeddingModel,
-                "Model '{model}' not supported for embedder {embedder}"
-            );
-        };
+        let mut vectors = Vectors::default();
 
-        if size != v_collection.size {
-            let v_size = v_collection.size;
-            return err!(
-                InvalidEmbeddingModel,
-                "Model size ({size}) not compatible with collection ({v_size})"
-            );
+        if let Some(ref model) = collection.model {
+            let Some(size) = embedder.size(model).await? else {
+                let embedder = embedder.id();
+                return err!(
+                    InvalidEmbeddingModel,
+                    "Model '{model}' not supported for embedder {embedder}"
+                );
+            };
+
+            if Some(size) != v_collection.size {
+                let v_size = v_collection.size;
+                return err!(
+                    InvalidEmbeddingModel,
+                    "Model size ({size}) not compatible with collection ({v_size:?})"
+                );
+            }
+
+            let embeddings = embedder
+                .embed_as(chunks, model, InputKind::Document)
+                .await?;
+
+            debug_assert_eq!(chunks.len(), embeddings.len());
+
+            vectors.dense = Some(embeddings);
         }
 
-        let embeddings = embedder.embed(chunks, &collection.model).await?;
+        if let Some(ref sparse_model) = collection.sparse_model {
+            let embeddings = embedder.embed_sparse(chunks, sparse_model).await?;
 
-        debug_assert_eq!(chunks.len(), embeddings.len());
+            debug_assert_eq!(chunks.len(), embeddings.len());
+
+            vectors.sparse = Some(embeddings);
+        }
 
         vector_db
-            .insert_embeddings(document_id, &collection.name, chunks, embeddings)
+            .insert_embeddings(document_id, &collection.name, chunks, chunk_ids, vectors)
             .await?;
 
         let embeddings = self
@@ -298,6 +380,8 @@ where
 
         let collection = if let Some(collection_id) = search.collection_id {
             self.get_collection(collection_id).await?
+        } else if let Some(ref alias) = search.alias {
+            self.get_collection_by_alias(alias).await?
         } else {
             let (Some(name), Some(provider)) = (&search.collection_name, &search.provider) else {
                 // Cannot happen because of above validify
@@ -308,23 +392,204 @@ where
         };
 
         let vector_db = self.providers.vector.get_provider(&collection.provider)?;
-        let embedder = self
-            .providers
-            .embedding
-            .get_provider(&collection.embedder)?;
+        let (kind, model) = query_model(&collection, search.vector)?;
+        let query = self
+            .embed_query(&collection.embedder, kind, model, &search.query)
+            .await?;
 
-        let mut embeddings = embedder.embed(&[&search.query], &collection.model).await?;
+        let quantization = QuantizationSearch {
+            rescore: search.rescore,
+            oversampling: search.oversampling,
+        };
 
-        debug_assert!(!embeddings.is_empty());
-        debug_assert_eq!(1, embeddings.len());
+        let limit = search.limit.unwrap_or(5);
 
-        vector_db
+        let hits = vector_db
             .query(
-                std::mem::take(&mut embeddings[0]),
+                query.clone(),
                 &collection.name,
-                search.limit.unwrap_or(5),
+                search.candidate_limit(limit),
+                quantization,
+                search.mmr_lambda.is_some(),
             )
-            .await
+            .await?;
+
+        let hits = search::select_hits(
+            &query,
+            hits,
+            limit as usize,
+            search.mmr_lambda,
+            search.max_per_document.map(|max| max as usize),
+        );
+
+        Ok(hits.into_iter().map(|hit| hit.content).collect())
+    }
+
+    /// Search multiple collections at once, possibly across different providers and
+    /// embedding models, and merge the results with reciprocal rank fusion.
+    ///
+    /// The query is embedded once per distinct embedder and model and the collections
+    /// are queried concurrently. Each collection returns up to `limit` hits, diversified
+    /// with MMR if requested, whose scores are normalized within the collection before
+    /// the results are fused. The per document cap is applied to the fused results.
+    /// Limit defaults to 5.
+    ///
+    /// * `search`: Search params. All of `collectionId`, `alias`, `collections`, `aliases`
+    ///   and the name and provider combo are searched.
+    pub async fn federated_search(
+        &self,
+        mut search: SearchPayload,
+    ) -> Result<Vec<FederatedHit>, ChonkitError> {
+        map_err!(search.validify());
+
+        let mut collections: Vec<Collection> = vec![];
+
+        let mut ids = search.collections.clone();
+        ids.extend(search.collection_id);
+        for id in ids {
+            collections.push(self.get_collection(id).await?);
+        }
+
+        let mut aliases = search.aliases.clone();
+        aliases.extend(search.alias.clone());
+        for alias in aliases {
+            collections.push(self.get_collection_by_alias(&alias).await?);
+        }
+
+        if let (Some(name), Some(provider)) = (&search.collection_name, &search.provider) {
+            collections.push(self.get_collection_by_name(name, provider).await?);
+        }
+
+        let mut seen = HashSet::new();
+        collections.retain(|collection| seen.insert(collection.id));
+
+        // Embed the query once per distinct embedder and model
+        let mut queries: HashMap<(String, VectorKind, String), QueryVector> = HashMap::new();
+        let mut targets = vec![];
+
+        for collection in collections {
+            let (kind, model) = query_model(&collection, search.vector)?;
+            let key = (collection.embedder.clone(), kind, model.to_string());
+
+            if !queries.contains_key(&key) {
+                let query = self
+                    .embed_query(&collection.embedder, kind, model, &search.query)
+                    .await?;
+                queries.insert(key.clone(), query);
+            }
+
+            targets.push((key, collection));
+        }
+
+        let limit = search.limit.unwrap_or(5);
+        let quantization = QuantizationSearch {
+            rescore: search.rescore,
+            oversampling: search.oversampling,
+        };
+
+        let candidates = search.candidate_limit(limit);
+        let mmr_lambda = search.mmr_lambda;
+        let max_per_document = search.max_per_document.map(|max| max as usize);
+
+        // Documents can be in multiple collections, so with a cap each collection contributes
+        // all of its candidates to still have `limit` hits after capping the fused results
+        let selected = if max_per_document.is_some() {
+            candidates
+        } else {
+            limit
+        };
+
+        let searches = targets.into_iter().map(|(key, collection)| {
+            let query = queries[&key].clone();
+            async move {
+                let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+                let hits = vector_db
+                    .query(
+                        query.clone(),
+                        &collection.name,
+                        candidates,
+                        quantization,
+                        mmr_lambda.is_some(),
+                    )
+                    .await?;
+
+                let hits = search::select_hits(
+                    &query,
+                    hits,
+                    selected as usize,
+                    mmr_lambda,
+                    max_per_document,
+                );
+
+                let mut hits: Vec<FederatedHit> = hits
+                    .into_iter()
+                    .map(|hit| FederatedHit {
+                        content: hit.content,
+                        document_id: hit.document_id,
+                        chunk_index: hit.chunk_index,
+                        chunk_id: hit.chunk_id,
+                        collection_id: collection.id,
+                        collection_name: collection.name.clone(),
+                        provider: collection.provider.clone(),
+                        score: hit.score,
+                        normalized_score: 0.,
+                        rrf_score: 0.,
+                    })
+                    .collect();
+
+                search::normalize_scores(&mut hits);
+
+                Result::<_, ChonkitError>::Ok(hits)
+            }
+        });
+
+        let lists = futures_util::future::try_join_all(searches).await?;
+
+        let mut hits = search::reciprocal_rank_fusion(lists, usize::MAX);
+
+        if let Some(max) = max_per_document {
+            let mut per_document: HashMap<Uuid, usize> = HashMap::new();
+            hits.retain(|hit| {
+                let Some(document_id) = hit.document_id else {
+                    return true;
+                };
+                let count = per_document.entry(document_id).or_default();
+                *count += 1;
+                *count <= max
+            });
+        }
+
+        hits.truncate(limit as usize);
+
+        Ok(hits)
+    }
+
+    /// Embed a search query with the given embedder and model.
+    async fn embed_query(
+        &self,
+        embedder: &str,
+        kind: VectorKind,
+        model: &str,
+        query: &str,
+    ) -> Result<QueryVector, ChonkitError> {
+        let embedder = self.providers.embedding.get_provider(embedder)?;
+
+        match kind {
+            VectorKind::Dense => {
+                let mut embeddings = embedder.embed_as(&[query], model, InputKind::Query).await?;
+
+                debug_assert_eq!(1, embeddings.len());
+
+                Ok(QueryVector::Dense(std::mem::take(&mut embeddings[0])))
+            }
+            VectorKind::Sparse => {
+                let mut embeddings = embedder.embed_sparse(&[query], model).await?;
+
+                debug_assert_eq!(1, embeddings.len());
+
+                Ok(QueryVector::Sparse(std::mem::take(&mut embeddings[0])))
+            }
+        }
     }
 
     pub async fn get_embeddings(
@@ -378,10 +643,583 @@ where
         let vector_db = self.providers.vector.get_provider(&collection.provider)?;
         vector_db.count_vectors(&collection.name, document_id).await
     }
+
+    /// Compare the embeddings tracked in the repository with the vectors stored in the
+    /// vector databases. Only vector collections returned by the providers' listing are
+    /// considered, so collections not managed by chonkit are never reported.
+    pub async fn reconcile(&self) -> Result<ReconciliationReport, ChonkitError> {
+        let mut report = ReconciliationReport::default();
+
+        let collections = self.repo.list_all_collections().await?;
+
+        // Names of existing vector collections, keyed by provider.
+        let mut vector_collections = HashMap::new();
+
+        for provider in self.providers.vector.list_provider_ids() {
+            let vector_db = self.providers.vector.get_provider(provider)?;
+            let names: HashSet<String> = vector_db
+                .list_collection_names()
+                .await?
+                .into_iter()
+                .collect();
+
+            for name in names.iter() {
+                let tracked = collections
+                    .iter()
+                    .any(|c| &c.name == name && c.provider == provider);
+                if !tracked {
+                    report.orphaned_collections.push(OrphanedCollection {
+                        name: name.clone(),
+                        provider: provider.to_string(),
+                    });
+                }
+            }
+
+            vector_collections.insert(provider, names);
+        }
+
+        for collection in collections {
+            // Collections of disabled providers cannot be checked
+            let Some(names) = vector_collections.get(collection.provider.as_str()) else {
+                warn!(
+                    "Skipping reconciliation of '{}', provider '{}' is not enabled",
+                    collection.name, collection.provider
+                );
+                continue;
+            };
+
+            let embeddings = self.repo.get_collection_embeddings(collection.id).await?;
+
+            if !names.contains(&collection.name) {
+                report.missing_vectors.extend(embeddings);
+                report.missing_collections.push(CollectionShort::new(
+                    collection.id,
+                    collection.name,
+                    collection.model,
+                    collection.sparse_model,
+                    collection.embedder,
+                    collection.provider,
+                ));
+                continue;
+            }
+
+            let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+            let mut counts = vector_db
+                .count_vectors_by_document(&collection.name)
+                .await?;
+
+            for embedding in embeddings {
+                if counts.remove(&embedding.document_id).is_none() {
+                    report.missing_vectors.push(embedding);
+                }
+            }
+
+            // Whatever remains has no embedding entry
+            let document_ids: Vec<Uuid> = counts.keys().copied().collect();
+            let existing: HashSet<Uuid> = self
+                .repo
+                .get_existing_documents(&document_ids)
+                .await?
+                .into_iter()
+                .collect();
+
+            for (document_id, count) in counts {
+                report.orphaned_vectors.push(OrphanedVectors {
+                    collection_id: collection.id,
+                    document_id,
+                    count,
+                    document_exists: existing.contains(&document_id),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Delete the orphaned vectors of deleted documents found in the report.
+    /// Vectors of existing documents are only reported since their embedding entry
+    /// may not be committed yet, e.g. while embedding or cloning. Orphaned collections
+    /// are never deleted here, see [delete_orphaned_collection][Self::delete_orphaned_collection].
+    /// Errors are collected in the report's `repair_errors`.
+    ///
+    /// * `report`: The report obtained from [reconcile][Self::reconcile].
+    pub async fn delete_orphans(&self, report: &mut ReconciliationReport) {
+        for orphan in report.orphaned_vectors.iter() {
+            let OrphanedVectors {
+                collection_id,
+                document_id,
+                document_exists,
+                ..
+            } = orphan;
+
+            if *document_exists {
+                continue;
+            }
+
+            let result = async {
+                let collection = self.get_collection(*collection_id).await?;
+                let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+                vector_db
+                    .delete_embeddings(&collection.name, *document_id)
+                    .await
+            }
+            .await;
+
+            match result {
+                Ok(_) => info!(
+                    "Deleted orphaned vectors of '{document_id}' in collection '{collection_id}'"
+                ),
+                Err(e) => report.repair_errors.push(format!(
+                    "Deleting vectors of '{document_id}' in collection '{collection_id}': {e}"
+                )),
+            }
+        }
+    }
+
+    /// Delete a vector collection not tracked by the repository. Only collections reported as
+    /// orphaned by [reconcile][Self::reconcile] can be deleted.
+    ///
+    /// * `provider`: Vector database provider.
+    /// * `name`: Vector collection name.
+    pub async fn delete_orphaned_collection(
+        &self,
+        provider: &str,
+        name: &str,
+    ) -> Result<(), ChonkitError> {
+        if self
+            .repo
+            .get_collection_by_name(name, provider)
+            .await?
+            .is_some()
+        {
+            return err!(
+                AlreadyExists,
+                "Collection '{name}' ({provider}) is tracked and can only be deleted by its ID"
+            );
+        }
+
+        let vector_db = self.providers.vector.get_provider(provider)?;
+
+        let names = vector_db.list_collection_names().await?;
+        if !names.iter().any(|n| n == name) {
+            return err!(DoesNotExist, "Orphaned collection '{name}' ({provider})");
+        }
+
+        vector_db.delete_vector_collection(name).await?;
+
+        info!("Deleted orphaned collection '{name}' ({provider})");
+
+        Ok(())
+    }
+
+    /// List the chunks of a document stored in a collection.
+    ///
+    /// * `collection_id`: Collection ID.
+    /// * `document_id`: Document ID.
+    /// * `pagination`: Pagination parameters.
+    pub async fn list_chunks(
+        &self,
+        collection_id: Uuid,
+        document_id: Uuid,
+        pagination: Pagination,
+    ) -> Result<List<StoredChunk>, ChonkitError> {
+        map_err!(pagination.validate());
+
+        let Some(collection) = self.repo.get_collection(collection_id).await? else {
+            return err!(DoesNotExist, "Collection with ID '{collection_id}'");
+        };
+        let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+        vector_db
+            .list_chunks(&collection.name, document_id, pagination)
+            .await
+    }
+
+    /// Create the target collection for cloning `source_id` and return it along with
+    /// the IDs of the documents embedded in the source collection.
+    /// Model, embedder, provider and index configuration default to the source's.
+    ///
+    /// * `source_id`: Source collection ID.
+    /// * `payload`: Target collection parameters.
+    pub async fn create_clone_target(
+        &self,
+        source_id: Uuid,
+        payload: CloneCollectionPayload,
+    ) -> Result<(Collection, Vec<Uuid>), ChonkitError> {
+        let Some(source) = self.repo.get_collection(source_id).await? else {
+            return err!(DoesNotExist, "Collection with ID '{source_id}'");
+        };
+
+        let CloneCollectionPayload {
+            name,
+            mut model,
+            mut sparse_model,
+            embedding_provider,
+            vector_provider,
+            index_config,
+            alias: _,
+        } = payload;
+
+        if model.is_none() && sparse_model.is_none() {
+            model = source.model;
+            sparse_model = source.sparse_model;
+        }
+
+        let create = CreateCollectionPayload {
+            name,
+            model,
+            sparse_model,
+            vector_provider: vector_provider.unwrap_or(source.provider),
+            embedding_provider: embedding_provider.unwrap_or(source.embedder),
+            index_config: index_config.unwrap_or(source.index_config.0),
+        };
+
+        let target = self.create_collection(create).await?;
+
+        let documents = self
+            .repo
+            .get_collection_embeddings(source_id)
+            .await?
+            .into_iter()
+            .map(|embedding| embedding.document_id)
+            .collect();
+
+        Ok((target, documents))
+    }
+
+    /// List all collection aliases.
+    pub async fn list_aliases(&self) -> Result<Vec<CollectionAlias>, ChonkitError> {
+        self.repo.list_aliases().await
+    }
+
+    /// Get the collection an alias points to.
+    ///
+    /// * `name`: Alias name.
+    pub async fn get_collection_by_alias(&self, name: &str) -> Result<Collection, ChonkitError> {
+        let Some(alias) = self.repo.get_alias(name).await? else {
+            return err!(DoesNotExist, "Alias '{name}'");
+        };
+        self.get_collection(alias.collection_id).await
+    }
+
+    /// Atomically point an alias to a collection, creating the alias if it does not exist.
+    /// The provider's native alias is updated as well where available.
+    ///
+    /// * `name`: Alias name.
+    /// * `collection_id`: Collection ID.
+    pub async fn swap_alias(
+        &self,
+        name: &str,
+        collection_id: Uuid,
+    ) -> Result<CollectionAlias, ChonkitError> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return err!(
+                InvalidAlias,
+                "alias name must be non-empty and alphanumeric with underscores or dashes"
+            );
+        }
+
+        let Some(collection) = self.repo.get_collection(collection_id).await? else {
+            return err!(DoesNotExist, "Collection with ID '{collection_id}'");
+        };
+
+        // Native aliases in another provider would keep pointing to the old collection.
+        let previous = match self.repo.get_alias(name).await? {
+            Some(alias) if alias.collection_id != collection.id => {
+                self.repo.get_collection(alias.collection_id).await?
+            }
+            _ => None,
+        };
+
+        let previous_db = match previous {
+            Some(previous) if previous.provider != collection.provider => {
+                Some(self.providers.vector.get_provider(&previous.provider)?)
+            }
+            _ => None,
+        };
+
+        let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+
+        transaction!(self.repo, |tx| async move {
+            let alias = self
+                .repo
+                .upsert_alias(name, collection.id, Some(tx))
+                .await?;
+
+            vector_db.set_alias(name, &collection.name).await?;
+
+            // Only removed once the new alias is set so that a failure leaves the alias
+            // pointing to the previous collection in both the repo and the provider.
+            if let Some(previous_db) = previous_db {
+                if let Err(e) = previous_db.delete_alias(name).await {
+                    warn!(
+                        "Failed to delete alias '{name}' from provider '{}': {e}",
+                        previous_db.id()
+                    );
+                }
+            }
+
+            info!(
+                "Alias '{name}' now points to collection '{}'",
+                collection.name
+            );
+
+            Ok(alias)
+        })
+    }
+
+    /// Delete an alias and the provider's native alias.
+    ///
+    /// * `name`: Alias name.
+    pub async fn delete_alias(&self, name: &str) -> Result<(), ChonkitError> {
+        let collection = self.get_collection_by_alias(name).await?;
+        let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+        vector_db.delete_alias(name).await?;
+        self.repo.delete_alias(name).await?;
+        Ok(())
+    }
+
+    /// Export a collection and all of its points to a snapshot. The snapshot is
+    /// streamed as the points are scrolled, one page at a time.
+    ///
+    /// * `id`: Collection ID.
+    /// * `format`: Snapshot file format.
+    pub async fn export_collection(
+        &self,
+        id: Uuid,
+        format: SnapshotFormat,
+    ) -> Result<impl Stream<Item = Result<Vec<u8>, ChonkitError>> + Send + 'static, ChonkitError>
+    {
+        let Some(collection) = self.repo.get_collection(id).await? else {
+            return err!(DoesNotExist, "Collection with ID '{id}'");
+        };
+
+        let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+        let v_collection = vector_db.get_collection(&collection.name).await?;
+
+        let snapshot = SnapshotCollection {
+            name: collection.name,
+            size: v_collection.size,
+            model: collection.model,
+            sparse_model: collection.sparse_model,
+            embedder: collection.embedder,
+            provider: collection.provider,
+            index_config: collection.index_config.0,
+        };
+
+        let (writer, header) = SnapshotWriter::new(&snapshot, format)?;
+
+        info!("Exporting collection '{}'", snapshot.name);
+
+        let export = Export {
+            vector_db,
+            collection: snapshot.name,
+            writer,
+            offset: None,
+        };
+
+        let pages = stream::try_unfold(Some(export), |export| async move {
+            let Some(mut export) = export else {
+                return Ok(None);
+            };
+
+            let page = export
+                .vector_db
+                .scroll(&export.collection, export.offset.take(), EXPORT_PAGE_SIZE)
+                .await?;
+
+            let mut bytes = export.writer.write(page.points)?;
+
+            if page.next_offset.is_none() {
+                bytes.extend(export.writer.finish()?);
+                return Ok(Some((bytes, None)));
+            }
+
+            export.offset = page.next_offset;
+
+            Ok(Some((bytes, Some(export))))
+        });
+
+        Ok(stream::once(future::ready(Ok(header))).chain(pages))
+    }
+
+    /// Create a collection from a snapshot and insert the points of the documents
+    /// that exist in the repository, creating their embedding entries. Points of unknown
+    /// documents are skipped and their document IDs returned.
+    ///
+    /// * `bytes`: Snapshot contents.
+    /// * `format`: Snapshot file format.
+    /// * `name`: Collection name, defaults to the one in the snapshot.
+    /// * `provider`: Vector database provider, defaults to the one in the snapshot.
+    pub async fn import_collection(
+        &self,
+        bytes: Vec<u8>,
+        format: SnapshotFormat,
+        name: Option<String>,
+        provider: Option<String>,
+    ) -> Result<CollectionImport, ChonkitError> {
+        let CollectionSnapshot {
+            collection: snapshot,
+            points,
+        } = CollectionSnapshot::decode(bytes, format)?;
+
+        let mut documents: HashMap<Uuid, Vec<StoredPoint>> = HashMap::new();
+        for point in points {
+            if snapshot.model.is_some() && point.dense.is_none() {
+                return err!(InvalidSnapshot, "point '{}' has no dense vector", point.id);
+            }
+            if snapshot.sparse_model.is_some() && point.sparse.is_none() {
+                return err!(InvalidSnapshot, "point '{}' has no sparse vector", point.id);
+            }
+            documents.entry(point.document_id).or_default().push(point);
+        }
+
+        let payload = CreateCollectionPayload {
+            name: name.unwrap_or(snapshot.name),
+            model: snapshot.model,
+            sparse_model: snapshot.sparse_model,
+            vector_provider: provider.unwrap_or(snapshot.provider),
+            embedding_provider: snapshot.embedder,
+            index_config: snapshot.index_config,
+        };
+
+        let collection = self.create_collection(payload).await?;
+
+        let v_collection_size = {
+            let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+            vector_db.get_collection(&collection.name).await?.size
+        };
+
+        if snapshot.size.is_some() && snapshot.size != v_collection_size {
+            self.delete_collection(collection.id).await?;
+            return err!(
+                InvalidSnapshot,
+                "vector size ({:?}) not compatible with collection ({v_collection_size:?})",
+                snapshot.size
+            );
+        }
+
+        let skipped_documents = match self.import_points(&collection, documents).await {
+            Ok(skipped) => skipped,
+            Err(e) => {
+                self.delete_collection(collection.id).await?;
+                return Err(e);
+            }
+        };
+
+        Ok(CollectionImport {
+            collection,
+            skipped_documents,
+        })
+    }
+
+    /// Insert snapshot points into a collection, grouped by document.
+    /// Returns the IDs of the documents whose points were skipped since
+    /// they do not exist in the repository.
+    async fn import_points(
+        &self,
+        collection: &Collection,
+        documents: HashMap<Uuid, Vec<StoredPoint>>,
+    ) -> Result<Vec<Uuid>, ChonkitError> {
+        let vector_db = self.providers.vector.get_provider(&collection.provider)?;
+
+        let document_ids = documents.keys().copied().collect::<Vec<_>>();
+        let existing = self.repo.get_existing_documents(&document_ids).await?;
+
+        let mut skipped = vec![];
+
+        for (document_id, mut points) in documents {
+            if !existing.contains(&document_id) {
+                warn!(
+                    "Document '{document_id}' does not exist; skipping its points in '{}'",
+                    collection.name
+                );
+                skipped.push(document_id);
+                continue;
+            }
+
+            points.sort_by(|a, b| {
+                (a.chunk_index.is_none(), a.chunk_index)
+                    .cmp(&(b.chunk_index.is_none(), b.chunk_index))
+            });
+
+            let mut content = Vec::with_capacity(points.len());
+            let mut vectors = Vectors::default();
+
+            for point in points {
+                content.push(point.content);
+                if let Some(dense) = point.dense {
+                    vectors.dense.get_or_insert_with(Vec::new).push(dense);
+                }
+                if let Some(sparse) = point.sparse {
+                    vectors.sparse.get_or_insert_with(Vec::new).push(sparse);
+                }
+            }
+
+            if collection.model.is_none() {
+                vectors.dense = None;
+            }
+            if collection.sparse_model.is_none() {
+                vectors.sparse = None;
+            }
+
+            let content = content.iter().map(String::as_str).collect::<Vec<_>>();
+
+            vector_db
+                .insert_embeddings(document_id, &collection.name, &content, None, vectors)
+                .await?;
+
+            self.repo
+                .insert_embeddings(EmbeddingInsert::new(document_id, collection.id))
+                .await?;
+        }
+
+        Ok(skipped)
+    }
+}
+
+/// Get the kind of vectors and the model to search a collection by.
+/// Collections with dense vectors are searched by them unless specified otherwise.
+///
+/// * `collection`: The collection to search.
+/// * `kind`: Requested kind of vectors.
+fn query_model(
+    collection: &Collection,
+    kind: Option<VectorKind>,
+) -> Result<(VectorKind, &str), ChonkitError> {
+    let kind = kind.unwrap_or(if collection.model.is_some() {
+        VectorKind::Dense
+    } else {
+        VectorKind::Sparse
+    });
+
+    match (kind, &collection.model, &collection.sparse_model) {
+        (VectorKind::Dense, Some(model), _) => Ok((kind, model)),
+        (VectorKind::Sparse, _, Some(sparse_model)) => Ok((kind, sparse_model)),
+        (kind, _, _) => {
+            let name = &collection.name;
+            err!(
+                InvalidEmbeddingModel,
+                "Collection '{name}' does not contain {kind:?} vectors"
+            )
+        }
+    }
+}
+
+/// State of a collection export in progress.
+struct Export {
+    vector_db: Arc<dyn VectorDb + Send + Sync>,
+    collection: String,
+    writer: SnapshotWriter,
+    offset: Option<String>,
 }
 
 /// Vector service DTOs.
 pub mod dto {
+    use crate::core::vector::{VectorIndexConfig, VectorKind};
     use serde::Deserialize;
     use utoipa::ToSchema;
     use uuid::Uuid;
@@ -413,8 +1251,35 @@ pub mod dto {
         Ok(())
     }
 
+    #[derive(Debug, Deserialize, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CloneCollectionPayload {
+        /// Name of the new collection. Cannot contain special characters.
+        pub name: String,
+
+        /// Embedding model for dense vectors of the new collection.
+        /// If neither `model` nor `sparseModel` are set, the source collection's models are used.
+        pub model: Option<String>,
+
+        /// Embedding model for sparse vectors of the new collection.
+        pub sparse_model: Option<String>,
+
+        /// Embeddings provider. Defaults to the source collection's.
+        pub embedding_provider: Option<String>,
+
+        /// Vector database provider. Defaults to the source collection's.
+        pub vector_provider: Option<String>,
+
+        /// Index configuration. Defaults to the source collection's.
+        pub index_config: Option<VectorIndexConfig>,
+
+        /// Alias to point to the new collection once all documents are embedded.
+        pub alias: Option<String>,
+    }
+
     #[derive(Debug, Deserialize, Validify, ToSchema)]
     #[serde(rename_all = "camelCase")]
+    #[validate(Self::validate_schema)]
     pub struct CreateCollectionPayload {
         /// Collection name. Cannot contain special characters.
         #[validate(custom(ascii_alphanumeric_underscored))]
@@ -423,14 +1288,36 @@ pub mod dto {
         #[modify(trim)]
         pub name: String,
 
-        /// Collection embedding model.
-        pub model: String,
+        /// Collection embedding model for dense vectors.
+        /// At least one of `model` and `sparseModel` must be set.
+        pub model: Option<String>,
+
+        /// Collection embedding model for sparse vectors.
+        pub sparse_model: Option<String>,
 
         /// Vector database provider.
         pub vector_provider: String,
 
         /// Embeddings provider.
         pub embedding_provider: String,
+
+        /// Distance metric, HNSW parameters and on-disk storage of the collection.
+        /// Defaults to cosine distance with the vector database's defaults.
+        #[validate]
+        #[serde(default)]
+        pub index_config: VectorIndexConfig,
+    }
+
+    impl CreateCollectionPayload {
+        #[schema_validation]
+        fn validate_schema(&self) -> Result<(), ValidationErrors> {
+            if self.model.is_none() && self.sparse_model.is_none() {
+                schema_err!(
+                    "model_or_sparse_model",
+                    "at least one of `model` and `sparse_model` must be set"
+                );
+            }
+        }
     }
 
     #[derive(Debug, Clone, Validify)]
@@ -443,6 +1330,9 @@ pub mod dto {
 
         /// The chunked document.
         pub chunks: &'a [&'a str],
+
+        /// IDs of the stored chunks, 1:1 with `chunks`. `None` if the chunks are not stored.
+        pub chunk_ids: Option<&'a [Uuid]>,
     }
 
     /// Params for semantic search.
@@ -458,6 +1348,12 @@ pub mod dto {
         /// everything else.
         pub collection_id: Option<Uuid>,
 
+        /// If given search in the collection the alias points to.
+        /// Has priority over the name and provider combo.
+        #[validate(length(min = 1))]
+        #[modify(trim)]
+        pub alias: Option<String>,
+
         /// If given search via the name and provider combo.
         #[validate(length(min = 1))]
         #[modify(trim)]
@@ -466,30 +1362,88 @@ pub mod dto {
         /// Vector provider.
         pub provider: Option<String>,
 
+        /// Additional collections to search. If any collections or aliases are given,
+        /// the search is federated across all of the given collections and the results
+        /// are merged with reciprocal rank fusion.
+        #[serde(default)]
+        pub collections: Vec<Uuid>,
+
+        /// Additional aliases of collections to search. See `collections`.
+        #[serde(default)]
+        pub aliases: Vec<String>,
+
         /// Amount of results to return.
+        #[validate(range(max = 1000.))]
         pub limit: Option<u32>,
+
+        /// The kind of vectors to search by. Defaults to dense vectors
+        /// if the collection has them, sparse otherwise.
+        pub vector: Option<VectorKind>,
+
+        /// Whether to rescore results with the original vectors when
+        /// searching collections with quantized vectors.
+        pub rescore: Option<bool>,
+
+        /// Fetch `limit * oversampling` results with the quantized vectors
+        /// before rescoring them.
+        #[validate(range(min = 1.))]
+        pub oversampling: Option<f64>,
+
+        /// Trade-off between the relevance (1) and the diversity (0) of the results.
+        /// If given, the results are selected from the candidates with Maximal
+        /// Marginal Relevance to avoid near-duplicate results.
+        #[validate(range(min = 0., max = 1.))]
+        pub mmr_lambda: Option<f64>,
+
+        /// Maximum amount of results from the same document.
+        #[validate(range(min = 1.))]
+        pub max_per_document: Option<u32>,
+
+        /// Amount of candidates to select the results from when using `mmrLambda`
+        /// or `maxPerDocument`. Defaults to 4 times the limit.
+        #[validate(range(min = 1., max = 1000.))]
+        pub candidates: Option<u32>,
     }
 
     impl SearchPayload {
+        /// Whether the search spans multiple collections.
+        pub fn is_federated(&self) -> bool {
+            !self.collections.is_empty() || !self.aliases.is_empty()
+        }
+
+        /// Amount of hits to fetch from the vector database to select `limit` results from.
+        pub fn candidate_limit(&self, limit: u32) -> u32 {
+            if self.mmr_lambda.is_none() && self.max_per_document.is_none() {
+                return limit;
+            }
+            self.candidates
+                .unwrap_or(limit.saturating_mul(4))
+                .max(limit)
+        }
+
         #[schema_validation]
         fn validate_schema(&self) -> Result<(), ValidationErrors> {
             let SearchPayload {
                 collection_id,
+                alias,
                 collection_name,
                 provider,
                 ..
             } = self;
-            match (collection_id, collection_name, provider) {
-                (None, None, None) => {
+            if collection_id.is_some() || alias.is_some() || self.is_federated() {
+                return Ok(());
+            }
+            match (collection_name, provider) {
+                (None, None) => {
                     schema_err!(
                         "either_id_or_name_and_provider",
-                        "one of either `collection_id`, or `provider` and `collection_name` combination must be set"
+                        "one of either `collection_id`, `alias`, or `provider` and `collection_name` combination must be set"
                     );
                 }
-                (None, Some(_), None) | (None, None, Some(_)) => {
+                (Some(_), None) | (None, Some(_)) => {
                     schema_err!(
                     "name_and_provider",
-                    "both 'collection_name'and 'provider' must be set if `collection_id` is not set"
+                    "both 'collection_name' and 'provider' must be set if neither `collection_id` nor `alias` is set"
                 );
                 }
                 _ => {}
@@ -1,6 +1,12 @@
+use super::embedder::SparseEmbedding;
+use super::model::{List, Pagination};
 use crate::error::ChonkitError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
+use validify::{schema_err, schema_validation, Validate, ValidationErrors};
+
+pub mod snapshot;
 
 // Collection properties.
 
@@ -9,11 +15,14 @@ pub const COLLECTION_NAME_PROPERTY: &str = "name";
 pub const COLLECTION_SIZE_PROPERTY: &str = "size";
 pub const COLLECTION_EMBEDDING_PROVIDER_PROPERTY: &str = "embedding_provider";
 pub const COLLECTION_EMBEDDING_MODEL_PROPERTY: &str = "embedding_model";
+pub const COLLECTION_SPARSE_MODEL_PROPERTY: &str = "sparse_model";
 
 // Vector payload properties.
 
 pub const CONTENT_PROPERTY: &str = "content";
 pub const DOCUMENT_ID_PROPERTY: &str = "document_id";
+pub const CHUNK_INDEX_PROPERTY: &str = "chunk_index";
+pub const CHUNK_ID_PROPERTY: &str = "chunk_id";
 
 /// Used by vector databases.
 #[derive(Debug, Serialize, Deserialize, Default, utoipa::ToSchema)]
@@ -23,19 +32,23 @@ pub struct VectorCollection {
     pub id: Uuid,
     /// Unique collection name.
     pub name: String,
-    /// Collection vector size
-    pub size: usize,
+    /// Collection dense vector size. `None` if the collection only holds sparse vectors.
+    pub size: Option<usize>,
     pub embedding_provider: String,
-    pub embedding_model: String,
+    /// Model used for dense vectors.
+    pub embedding_model: Option<String>,
+    /// Model used for sparse vectors.
+    pub sparse_model: Option<String>,
 }
 
 impl VectorCollection {
     pub fn new(
         id: Uuid,
         name: String,
-        size: usize,
+        size: Option<usize>,
         embedding_provider: String,
-        embedding_model: String,
+        embedding_model: Option<String>,
+        sparse_model: Option<String>,
     ) -> Self {
         Self {
             id,
@@ -43,6 +56,7 @@ impl VectorCollection {
             size,
             embedding_provider,
             embedding_model,
+            sparse_model,
         }
     }
 
@@ -57,7 +71,7 @@ impl VectorCollection {
     }
 
     pub fn with_size(mut self, size: usize) -> Self {
-        self.size = size;
+        self.size = Some(size);
         self
     }
 
@@ -67,7 +81,12 @@ impl VectorCollection {
     }
 
     pub fn with_embedding_model(mut self, model: String) -> Self {
-        self.embedding_model = model;
+        self.embedding_model = Some(model);
+        self
+    }
+
+    pub fn with_sparse_model(mut self, model: String) -> Self {
+        self.sparse_model = Some(model);
         self
     }
 }
@@ -80,22 +99,140 @@ pub struct VectorCollectionInfo {
     pub embedding_model: String,
 }
 
+/// Distance metric used to compare dense vectors in a collection.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Distance {
+    #[default]
+    Cosine,
+    Dot,
+    Euclidean,
+    Manhattan,
+}
+
+/// Index parameters of a vector collection. Unset HNSW parameters
+/// use the defaults of the vector database.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, Validate, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase", default)]
+#[validate(Self::validate_schema)]
+pub struct VectorIndexConfig {
+    /// Distance metric for dense vectors.
+    pub distance: Distance,
+
+    /// Amount of edges per node in the HNSW graph.
+    #[validate(range(min = 4., max = 128.))]
+    pub hnsw_m: Option<u64>,
+
+    /// Amount of neighbours considered when building the HNSW graph.
+    #[validate(range(min = 4., max = 1024.))]
+    pub hnsw_ef_construct: Option<u64>,
+
+    /// Store vectors and the index on disk instead of in memory.
+    pub on_disk: bool,
+
+    /// Quantization of the collection's dense vectors.
+    pub quantization: Option<Quantization>,
+}
+
+impl VectorIndexConfig {
+    #[schema_validation]
+    fn validate_schema(&self) -> Result<(), ValidationErrors> {
+        if let Some(Quantization::Scalar {
+            quantile: Some(quantile),
+            ..
+        }) = self.quantization
+        {
+            if !(0.5..=1.).contains(&quantile) {
+                schema_err!(
+                    "quantile",
+                    "scalar quantization quantile must be between 0.5 and 1"
+                );
+            }
+        }
+    }
+}
+
+/// Dense vector quantization. Quantized vectors are kept in memory and are used
+/// for the initial search, while the original vectors can be used for rescoring.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum Quantization {
+    /// Converts each dimension to an `int8`.
+    #[serde(rename_all = "camelCase")]
+    Scalar {
+        /// Quantile used to determine the bounds of the quantized values.
+        /// Must be between 0.5 and 1.
+        quantile: Option<f32>,
+
+        /// Keep the quantized vectors in memory even if the collection is on disk.
+        #[serde(default)]
+        always_ram: bool,
+    },
+
+    /// Splits vectors into chunks and stores only the chunk centroids.
+    #[serde(rename_all = "camelCase")]
+    Product {
+        compression: ProductCompression,
+
+        /// Keep the quantized vectors in memory even if the collection is on disk.
+        #[serde(default)]
+        always_ram: bool,
+    },
+
+    /// Converts each dimension to a single bit.
+    #[serde(rename_all = "camelCase")]
+    Binary {
+        /// Keep the quantized vectors in memory even if the collection is on disk.
+        #[serde(default)]
+        always_ram: bool,
+    },
+}
+
+/// Compression ratio of product quantization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProductCompression {
+    X4,
+    X8,
+    X16,
+    X32,
+    X64,
+}
+
+/// Search parameters for collections with quantized vectors. Unset
+/// parameters use the defaults of the vector database.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct QuantizationSearch {
+    /// Whether to rescore the results with the original vectors.
+    pub rescore: Option<bool>,
+
+    /// Fetch `limit * oversampling` results with the quantized vectors before rescoring.
+    pub oversampling: Option<f64>,
+}
+
+/// Collections hold dense vectors, sparse vectors or both. Dense vectors are
+/// present if `size` and `embedding_model` are set, sparse if `sparse_model` is set.
 #[derive(Debug, Serialize)]
 pub struct CreateVectorCollection<'a> {
     pub collection_id: Uuid,
     pub name: &'a str,
-    pub size: usize,
+    pub size: Option<usize>,
     pub embedding_provider: &'a str,
-    pub embedding_model: &'a str,
+    pub embedding_model: Option<&'a str>,
+    pub sparse_model: Option<&'a str>,
+    pub index_config: VectorIndexConfig,
 }
 
 impl<'a> CreateVectorCollection<'a> {
     pub fn new(
         id: Uuid,
         name: &'a str,
-        size: usize,
+        size: Option<usize>,
         embedding_provider: &'a str,
-        embedding_model: &'a str,
+        embedding_model: Option<&'a str>,
+        sparse_model: Option<&'a str>,
     ) -> Self {
         Self {
             collection_id: id,
@@ -103,8 +240,92 @@ impl<'a> CreateVectorCollection<'a> {
             size,
             embedding_provider,
             embedding_model,
+            sparse_model,
+            index_config: VectorIndexConfig::default(),
         }
     }
+
+    pub fn with_index_config(mut self, index_config: VectorIndexConfig) -> Self {
+        self.index_config = index_config;
+        self
+    }
+}
+
+/// The kind of vectors to search by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorKind {
+    Dense,
+    Sparse,
+}
+
+/// A vector used for searching a collection.
+#[derive(Debug, Clone)]
+pub enum QueryVector {
+    Dense(Vec<f64>),
+    Sparse(SparseEmbedding),
+}
+
+/// Vectors of the contents being inserted. Each set of vectors, if present,
+/// must be 1:1 with the contents. Collections holding both dense and sparse
+/// vectors expect both to be present.
+#[derive(Debug, Default)]
+pub struct Vectors {
+    pub dense: Option<Vec<Vec<f64>>>,
+    pub sparse: Option<Vec<SparseEmbedding>>,
+}
+
+/// A point returned from semantic search.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub content: String,
+    /// Similarity score, higher is better. Scores depend on the provider
+    /// and distance metric and are not comparable across collections.
+    pub score: f64,
+    /// ID of the document the point belongs to. `None` if the point was not tagged.
+    pub document_id: Option<Uuid>,
+    /// Position of the chunk in the document. `None` for chunks
+    /// stored before chunk indices were tracked.
+    pub chunk_index: Option<usize>,
+    /// ID of the stored chunk the point was created from. `None` for points
+    /// stored before chunks were persisted, or inserted without them.
+    pub chunk_id: Option<Uuid>,
+    /// The vector the point was matched by. Only present if requested.
+    pub vector: Option<QueryVector>,
+}
+
+/// A chunk stored in a vector collection.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredChunk {
+    /// Point ID in the vector database.
+    pub id: String,
+    /// Position of the chunk in the document. `None` for chunks
+    /// stored before chunk indices were tracked.
+    pub index: Option<usize>,
+    /// Chunk content.
+    pub content: String,
+}
+
+/// A point stored in a vector collection, i.e. a chunk and its vectors.
+#[derive(Debug, Clone, Default)]
+pub struct StoredPoint {
+    /// Point ID in the vector database.
+    pub id: String,
+    pub document_id: Uuid,
+    /// Position of the chunk in the document.
+    pub chunk_index: Option<usize>,
+    pub content: String,
+    pub dense: Option<Vec<f64>>,
+    pub sparse: Option<SparseEmbedding>,
+}
+
+/// A page of points obtained with [VectorDb::scroll].
+#[derive(Debug, Default)]
+pub struct PointPage {
+    pub points: Vec<StoredPoint>,
+    /// Offset of the next page, `None` if there are no more points.
+    pub next_offset: Option<String>,
 }
 
 /// Vector database operations.
@@ -115,6 +336,18 @@ pub trait VectorDb {
     /// List available vector collections.
     async fn list_vector_collections(&self) -> Result<Vec<VectorCollection>, ChonkitError>;
 
+    /// List the names of the collections holding chonkit's vectors, including the ones
+    /// [VectorDb::list_vector_collections] skips due to missing collection information.
+    /// Used to find orphaned collections when reconciling.
+    async fn list_collection_names(&self) -> Result<Vec<String>, ChonkitError> {
+        Ok(self
+            .list_vector_collections()
+            .await?
+            .into_iter()
+            .map(|collection| collection.name)
+            .collect())
+    }
+
     /// Create a vector collection.
     ///
     /// * `name`: The name of the collection.
@@ -148,17 +381,24 @@ pub trait VectorDb {
         data: CreateVectorCollection<'_>,
     ) -> Result<(), ChonkitError>;
 
-    /// Perform semantic search.
+    /// Perform semantic search. Dense vectors are compared with the distance
+    /// metric of the collection and sparse vectors by their dot product.
+    /// Hits are ordered by their score, best first.
     ///
-    /// * `search`: The query to use as the search vector.
+    /// * `search`: The query vector.
     /// * `collection`: The collection to search in.
     /// * `limit`: Amount of results to return.
+    /// * `quantization`: Search parameters for quantized collections.
+    ///                   Ignored by providers that do not support quantization.
+    /// * `with_vectors`: Whether to return the vectors of the hits, of the same kind as `search`.
     async fn query(
         &self,
-        search: Vec<f64>,
+        search: QueryVector,
         collection: &str,
         limit: u32,
-    ) -> Result<Vec<String>, ChonkitError>;
+        quantization: QuantizationSearch,
+        with_vectors: bool,
+    ) -> Result<Vec<SearchHit>, ChonkitError>;
 
     /// Store the contents and their vectors to the vector storage.
     /// The `contents` and `vectors` inputs are expected to
@@ -166,14 +406,16 @@ pub trait VectorDb {
     /// yield the contents and their respectful embeddings.
     ///
     /// * `content`: The contents to append to the vectors.
-    /// * `vectors`: The vectors to store.
+    /// * `chunk_ids`: IDs of the stored chunks the contents come from, 1:1 with `content`.
+    /// * `vectors`: The dense and/or sparse vectors to store.
     /// * `collection`: The vector collection to store in.
     async fn insert_embeddings(
         &self,
         document_id: Uuid,
         collection: &str,
         content: &[&str],
-        vectors: Vec<Vec<f64>>,
+        chunk_ids: Option<&[Uuid]>,
+        vectors: Vectors,
     ) -> Result<(), ChonkitError>;
 
     /// Delete the vectors tagged with the given `document_id`.
@@ -195,4 +437,50 @@ pub trait VectorDb {
         collection: &str,
         document_id: Uuid,
     ) -> Result<usize, ChonkitError>;
+
+    /// Returns the amount of vectors stored for each document in the collection.
+    ///
+    /// * `collection`: The collection to count in.
+    async fn count_vectors_by_document(
+        &self,
+        collection: &str,
+    ) -> Result<HashMap<Uuid, usize>, ChonkitError>;
+
+    /// Iterate over all points in the collection, including their vectors.
+    ///
+    /// * `collection`: The collection to scroll.
+    /// * `offset`: The `next_offset` of the previous page, `None` for the first page.
+    /// * `limit`: Maximum amount of points in the page.
+    async fn scroll(
+        &self,
+        collection: &str,
+        offset: Option<String>,
+        limit: u32,
+    ) -> Result<PointPage, ChonkitError>;
+
+    /// List the chunks tagged with the given `document_id`, ordered by their
+    /// index in the document.
+    ///
+    /// * `collection`: The collection to list from.
+    /// * `document_id`: The id of the document whose chunks to list.
+    /// * `pagination`: Pagination parameters.
+    async fn list_chunks(
+        &self,
+        collection: &str,
+        document_id: Uuid,
+        pagination: Pagination,
+    ) -> Result<List<StoredChunk>, ChonkitError>;
+
+    /// Point the provider's native alias `alias` to `collection`, creating it if it
+    /// does not exist. The switch must be atomic. Providers without native aliases
+    /// do nothing, in which case aliases are only resolved by chonkit.
+    ///
+    /// * `alias`: Alias name.
+    /// * `collection`: The collection to point the alias to.
+    async fn set_alias(&self, alias: &str, collection: &str) -> Result<(), ChonkitError>;
+
+    /// Delete the provider's native alias. Providers without native aliases do nothing.
+    ///
+    /// * `alias`: Alias name.
+    async fn delete_alias(&self, alias: &str) -> Result<(), ChonkitError>;
 }
@@ -0,0 +1,585 @@
+//! Portable collection snapshots used to export and import collections
+//! between vector providers and chonkit instances.
+
+use super::{StoredPoint, VectorIndexConfig};
+use crate::core::embedder::SparseEmbedding;
+use crate::error::ChonkitError;
+use crate::{err, map_err};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// File format of collection snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotFormat {
+    /// The first line holds the collection metadata, every following line holds a point.
+    #[default]
+    Jsonl,
+
+    /// Points are stored as rows, the collection metadata is stored in the file metadata
+    /// under [PARQUET_COLLECTION_KEY]. Requires the `parquet` feature.
+    Parquet,
+}
+
+impl SnapshotFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "application/jsonl",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+/// Key of the collection metadata in parquet snapshots.
+pub const PARQUET_COLLECTION_KEY: &str = "chonkit.collection";
+
+/// Collection metadata stored in snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotCollection {
+    pub name: String,
+    /// Dense vector size.
+    pub size: Option<usize>,
+    /// Model used for dense vectors.
+    pub model: Option<String>,
+    /// Model used for sparse vectors.
+    pub sparse_model: Option<String>,
+    /// Embedding provider ID.
+    pub embedder: String,
+    /// Vector database provider ID the collection was exported from.
+    pub provider: String,
+    #[serde(default)]
+    pub index_config: VectorIndexConfig,
+}
+
+/// A point as written to snapshots.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotPoint {
+    id: String,
+    document_id: Uuid,
+    chunk_index: Option<usize>,
+    content: String,
+    dense: Option<Vec<f64>>,
+    sparse_indices: Option<Vec<u32>>,
+    sparse_values: Option<Vec<f32>>,
+}
+
+impl From<StoredPoint> for SnapshotPoint {
+    fn from(point: StoredPoint) -> Self {
+        let (sparse_indices, sparse_values) = match point.sparse {
+            Some(SparseEmbedding { indices, values }) => (Some(indices), Some(values)),
+            None => (None, None),
+        };
+        Self {
+            id: point.id,
+            document_id: point.document_id,
+            chunk_index: point.chunk_index,
+            content: point.content,
+            dense: point.dense,
+            sparse_indices,
+            sparse_values,
+        }
+    }
+}
+
+impl From<SnapshotPoint> for StoredPoint {
+    fn from(point: SnapshotPoint) -> Self {
+        let sparse = match (point.sparse_indices, point.sparse_values) {
+            (Some(indices), Some(values)) => Some(SparseEmbedding { indices, values }),
+            _ => None,
+        };
+        Self {
+            id: point.id,
+            document_id: point.document_id,
+            chunk_index: point.chunk_index,
+            content: point.content,
+            dense: point.dense,
+            sparse,
+        }
+    }
+}
+
+/// A collection and all of its points.
+#[derive(Debug)]
+pub struct CollectionSnapshot {
+    pub collection: SnapshotCollection,
+    pub points: Vec<StoredPoint>,
+}
+
+/// Writes snapshots page by page so collections can be exported without
+/// holding all of their points in memory.
+pub struct SnapshotWriter {
+    inner: WriterInner,
+}
+
+enum WriterInner {
+    Jsonl,
+    #[cfg(feature = "parquet")]
+    Parquet(Box<columnar::Writer>),
+}
+
+impl SnapshotWriter {
+    /// Start a snapshot of the collection in the given format.
+    /// Returns the writer and the first bytes of the snapshot.
+    pub fn new(
+        collection: &SnapshotCollection,
+        format: SnapshotFormat,
+    ) -> Result<(Self, Vec<u8>), ChonkitError> {
+        match format {
+            SnapshotFormat::Jsonl => {
+                let mut out = map_err!(serde_json::to_vec(collection));
+                out.push(b'\n');
+                let inner = WriterInner::Jsonl;
+                Ok((Self { inner }, out))
+            }
+            #[cfg(feature = "parquet")]
+            SnapshotFormat::Parquet => {
+                let (writer, out) = columnar::Writer::new(collection)?;
+                let inner = WriterInner::Parquet(Box::new(writer));
+                Ok((Self { inner }, out))
+            }
+            #[cfg(not(feature = "parquet"))]
+            SnapshotFormat::Parquet => err!(
+                UnsupportedFileType,
+                "parquet snapshots require the `parquet` feature"
+            ),
+        }
+    }
+
+    /// Write a page of points. Returns the bytes of the snapshot written since the last call.
+    pub fn write(&mut self, points: Vec<StoredPoint>) -> Result<Vec<u8>, ChonkitError> {
+        match self.inner {
+            WriterInner::Jsonl => {
+                let mut out = vec![];
+                for point in points {
+                    map_err!(serde_json::to_writer(&mut out, &SnapshotPoint::from(point)));
+                    out.push(b'\n');
+                }
+                Ok(out)
+            }
+            #[cfg(feature = "parquet")]
+            WriterInner::Parquet(ref mut writer) => writer.write(&points),
+        }
+    }
+
+    /// Finish the snapshot. Returns its remaining bytes.
+    pub fn finish(self) -> Result<Vec<u8>, ChonkitError> {
+        match self.inner {
+            WriterInner::Jsonl => Ok(vec![]),
+            #[cfg(feature = "parquet")]
+            WriterInner::Parquet(writer) => writer.finish(),
+        }
+    }
+}
+
+impl CollectionSnapshot {
+    /// Read a snapshot in the given format.
+    pub fn decode(bytes: Vec<u8>, format: SnapshotFormat) -> Result<Self, ChonkitError> {
+        match format {
+            SnapshotFormat::Jsonl => Self::decode_jsonl(&bytes),
+            #[cfg(feature = "parquet")]
+            SnapshotFormat::Parquet => columnar::decode(bytes),
+            #[cfg(not(feature = "parquet"))]
+            SnapshotFormat::Parquet => err!(
+                UnsupportedFileType,
+                "parquet snapshots require the `parquet` feature"
+            ),
+        }
+    }
+
+    fn decode_jsonl(bytes: &[u8]) -> Result<Self, ChonkitError> {
+        let mut lines = bytes
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.iter().all(u8::is_ascii_whitespace));
+
+        let Some(collection) = lines.next() else {
+            return err!(InvalidSnapshot, "missing collection metadata");
+        };
+
+        let collection: SnapshotCollection = match serde_json::from_slice(collection) {
+            Ok(collection) => collection,
+            Err(e) => return err!(InvalidSnapshot, "invalid collection metadata: {e}"),
+        };
+
+        let mut points = vec![];
+        for (i, line) in lines.enumerate() {
+            match serde_json::from_slice::<SnapshotPoint>(line) {
+                Ok(point) => points.push(point.into()),
+                // Line 1 is the collection
+                Err(e) => return err!(InvalidSnapshot, "invalid point on line {}: {e}", i + 2),
+            }
+        }
+
+        Ok(Self { collection, points })
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod columnar {
+    use super::{CollectionSnapshot, SnapshotCollection, PARQUET_COLLECTION_KEY};
+    use crate::core::{embedder::SparseEmbedding, vector::StoredPoint};
+    use crate::error::ChonkitError;
+    use crate::{err, map_err};
+    use arrow_array::{
+        cast::AsArray,
+        types::{Float32Type, Float64Type, UInt32Type, UInt64Type},
+        Array, ArrayRef, ListArray, RecordBatch, StringArray, UInt64Array,
+    };
+    use arrow_schema::{DataType, Field, Schema, SchemaRef};
+    use parquet::{
+        arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+        file::{metadata::KeyValue, properties::WriterProperties},
+    };
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    fn schema() -> Schema {
+        let list = |item: DataType| DataType::List(Arc::new(Field::new("item", item, true)));
+        Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("document_id", DataType::Utf8, false),
+            Field::new("chunk_index", DataType::UInt64, true),
+            Field::new("content", DataType::Utf8, false),
+            Field::new("dense", list(DataType::Float64), true),
+            Field::new("sparse_indices", list(DataType::UInt32), true),
+            Field::new("sparse_values", list(DataType::Float32), true),
+        ])
+    }
+
+    /// Writes every page of points as a row group.
+    pub struct Writer {
+        writer: ArrowWriter<Vec<u8>>,
+        schema: SchemaRef,
+    }
+
+    impl Writer {
+        pub fn new(collection: &SnapshotCollection) -> Result<(Self, Vec<u8>), ChonkitError> {
+            let schema = Arc::new(schema());
+
+            let properties = WriterProperties::builder()
+                .set_key_value_metadata(Some(vec![KeyValue::new(
+                    PARQUET_COLLECTION_KEY.to_string(),
+                    map_err!(serde_json::to_string(collection)),
+                )]))
+                .build();
+
+            let writer = map_err!(ArrowWriter::try_new(
+                vec![],
+                schema.clone(),
+                Some(properties)
+            ));
+
+            let mut writer = Self { writer, schema };
+            let out = writer.take();
+
+            Ok((writer, out))
+        }
+
+        pub fn write(&mut self, points: &[StoredPoint]) -> Result<Vec<u8>, ChonkitError> {
+            if points.is_empty() {
+                return Ok(vec![]);
+            }
+
+            let batch = record_batch(self.schema.clone(), points)?;
+
+            map_err!(self.writer.write(&batch));
+            map_err!(self.writer.flush());
+
+            Ok(self.take())
+        }
+
+        pub fn finish(mut self) -> Result<Vec<u8>, ChonkitError> {
+            map_err!(self.writer.finish());
+            Ok(self.take())
+        }
+
+        /// Take the bytes written to the underlying buffer so far.
+        fn take(&mut self) -> Vec<u8> {
+            std::mem::take(self.writer.inner_mut())
+        }
+    }
+
+    fn record_batch(
+        schema: SchemaRef,
+        points: &[StoredPoint],
+    ) -> Result<RecordBatch, ChonkitError> {
+        let ids = StringArray::from_iter_values(points.iter().map(|p| p.id.as_str()));
+        let document_ids =
+            StringArray::from_iter_values(points.iter().map(|p| p.document_id.to_string()));
+        let chunk_indices =
+            UInt64Array::from_iter(points.iter().map(|p| p.chunk_index.map(|i| i as u64)));
+        let contents = StringArray::from_iter_values(points.iter().map(|p| p.content.as_str()));
+        let dense = ListArray::from_iter_primitive::<Float64Type, _, _>(
+            points
+                .iter()
+                .map(|p| p.dense.as_ref().map(|d| d.iter().copied().map(Some))),
+        );
+        let sparse_indices =
+            ListArray::from_iter_primitive::<UInt32Type, _, _>(points.iter().map(|p| {
+                p.sparse
+                    .as_ref()
+                    .map(|s| s.indices.iter().copied().map(Some))
+            }));
+        let sparse_values =
+            ListArray::from_iter_primitive::<Float32Type, _, _>(points.iter().map(|p| {
+                p.sparse
+                    .as_ref()
+                    .map(|s| s.values.iter().copied().map(Some))
+            }));
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(ids),
+            Arc::new(document_ids),
+            Arc::new(chunk_indices),
+            Arc::new(contents),
+            Arc::new(dense),
+            Arc::new(sparse_indices),
+            Arc::new(sparse_values),
+        ];
+
+        Ok(map_err!(RecordBatch::try_new(schema, columns)))
+    }
+
+    pub fn decode(bytes: Vec<u8>) -> Result<CollectionSnapshot, ChonkitError> {
+        let builder = match ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes)) {
+            Ok(builder) => builder,
+            Err(e) => return err!(InvalidSnapshot, "{e}"),
+        };
+
+        let collection = builder
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .and_then(|kv| kv.iter().find(|kv| kv.key == PARQUET_COLLECTION_KEY))
+            .and_then(|kv| kv.value.as_deref());
+
+        let Some(collection) = collection else {
+            return err!(InvalidSnapshot, "missing collection metadata");
+        };
+
+        let collection: SnapshotCollection = match serde_json::from_str(collection) {
+            Ok(collection) => collection,
+            Err(e) => return err!(InvalidSnapshot, "invalid collection metadata: {e}"),
+        };
+
+        let reader = match builder.build() {
+            Ok(reader) => reader,
+            Err(e) => return err!(InvalidSnapshot, "{e}"),
+        };
+
+        let mut points = vec![];
+
+        for batch in reader {
+            let batch = match batch {
+                Ok(batch) => batch,
+                Err(e) => return err!(InvalidSnapshot, "{e}"),
+            };
+            read_batch(&batch, &mut points)?;
+        }
+
+        Ok(CollectionSnapshot { collection, points })
+    }
+
+    fn read_batch(batch: &RecordBatch, points: &mut Vec<StoredPoint>) -> Result<(), ChonkitError> {
+        let ids = column(batch, "id", |c| c.as_string_opt::<i32>())?;
+        let document_ids = column(batch, "document_id", |c| c.as_string_opt::<i32>())?;
+        let chunk_indices = column(batch, "chunk_index", |c| c.as_primitive_opt::<UInt64Type>())?;
+        let contents = column(batch, "content", |c| c.as_string_opt::<i32>())?;
+        let dense = column(batch, "dense", |c| c.as_list_opt::<i32>())?;
+        let sparse_indices = column(batch, "sparse_indices", |c| c.as_list_opt::<i32>())?;
+        let sparse_values = column(batch, "sparse_values", |c| c.as_list_opt::<i32>())?;
+
+        for i in 0..batch.num_rows() {
+            let document_id = match document_ids
+                .is_valid(i)
+                .then(|| document_ids.value(i).parse::<Uuid>())
+            {
+                Some(Ok(id)) => id,
+                _ => return err!(InvalidSnapshot, "invalid document ID in row {i}"),
+            };
+
+            let chunk_index = chunk_indices
+                .is_valid(i)
+                .then(|| chunk_indices.value(i) as usize);
+
+            let dense = dense.is_valid(i).then(|| {
+                dense
+                    .value(i)
+                    .as_primitive::<Float64Type>()
+                    .values()
+                    .to_vec()
+            });
+
+            let sparse = (sparse_indices.is_valid(i) && sparse_values.is_valid(i)).then(|| {
+                SparseEmbedding {
+                    indices: sparse_indices
+                        .value(i)
+                        .as_primitive::<UInt32Type>()
+                        .values()
+                        .to_vec(),
+                    values: sparse_values
+                        .value(i)
+                        .as_primitive::<Float32Type>()
+                        .values()
+                        .to_vec(),
+                }
+            });
+
+            points.push(StoredPoint {
+                id: ids.value(i).to_string(),
+                document_id,
+                chunk_index,
+                content: contents.value(i).to_string(),
+                dense,
+                sparse,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get a column from the batch and cast it to its expected type.
+    fn column<'a, T: ?Sized>(
+        batch: &'a RecordBatch,
+        name: &str,
+        cast: impl FnOnce(&'a ArrayRef) -> Option<&'a T>,
+    ) -> Result<&'a T, ChonkitError> {
+        match batch.column_by_name(name).and_then(cast) {
+            Some(column) => Ok(column),
+            None => err!(InvalidSnapshot, "missing or invalid column '{name}'"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> CollectionSnapshot {
+        let document_id = Uuid::new_v4();
+        CollectionSnapshot {
+            collection: SnapshotCollection {
+                name: "My_collection".to_string(),
+                size: Some(2),
+                model: Some("model".to_string()),
+                sparse_model: Some("sparse".to_string()),
+                embedder: "fembed".to_string(),
+                provider: "qdrant".to_string(),
+                index_config: VectorIndexConfig::default(),
+            },
+            points: vec![
+                StoredPoint {
+                    id: Uuid::new_v4().to_string(),
+                    document_id,
+                    chunk_index: Some(0),
+                    content: "first".to_string(),
+                    dense: Some(vec![0.1, 0.2]),
+                    sparse: Some(SparseEmbedding {
+                        indices: vec![1, 5],
+                        values: vec![0.5, 0.25],
+                    }),
+                },
+                StoredPoint {
+                    id: Uuid::new_v4().to_string(),
+                    document_id,
+                    chunk_index: None,
+                    content: "second\nline".to_string(),
+                    dense: Some(vec![0.3, 0.4]),
+                    sparse: None,
+                },
+            ],
+        }
+    }
+
+    /// Write the snapshot in pages of one point.
+    fn encode(snapshot: CollectionSnapshot, format: SnapshotFormat) -> Vec<u8> {
+        let (mut writer, mut bytes) = SnapshotWriter::new(&snapshot.collection, format).unwrap();
+        for point in snapshot.points {
+            bytes.extend(writer.write(vec![point]).unwrap());
+        }
+        bytes.extend(writer.finish().unwrap());
+        bytes
+    }
+
+    fn assert_round_trip(format: SnapshotFormat) {
+        let original = snapshot();
+        let points = original.points.clone();
+
+        let bytes = encode(original, format);
+        let decoded = CollectionSnapshot::decode(bytes, format).unwrap();
+
+        assert_eq!("My_collection", decoded.collection.name);
+        assert_eq!(Some(2), decoded.collection.size);
+        assert_eq!(Some("sparse"), decoded.collection.sparse_model.as_deref());
+        assert_eq!(points.len(), decoded.points.len());
+
+        for (expected, actual) in points.iter().zip(decoded.points.iter()) {
+            assert_eq!(expected.id, actual.id);
+            assert_eq!(expected.document_id, actual.document_id);
+            assert_eq!(expected.chunk_index, actual.chunk_index);
+            assert_eq!(expected.content, actual.content);
+            assert_eq!(expected.dense, actual.dense);
+            assert_eq!(
+                expected.sparse.as_ref().map(|s| (&s.indices, &s.values)),
+                actual.sparse.as_ref().map(|s| (&s.indices, &s.values))
+            );
+        }
+    }
+
+    #[test]
+    fn jsonl_round_trip() {
+        assert_round_trip(SnapshotFormat::Jsonl);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_round_trip() {
+        assert_round_trip(SnapshotFormat::Parquet);
+    }
+
+    fn assert_empty_round_trip(format: SnapshotFormat) {
+        let mut empty = snapshot();
+        empty.points.clear();
+
+        let decoded = CollectionSnapshot::decode(encode(empty, format), format).unwrap();
+
+        assert_eq!("My_collection", decoded.collection.name);
+        assert!(decoded.points.is_empty());
+    }
+
+    #[test]
+    fn jsonl_empty_round_trip() {
+        assert_empty_round_trip(SnapshotFormat::Jsonl);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_empty_round_trip() {
+        assert_empty_round_trip(SnapshotFormat::Parquet);
+    }
+
+    #[test]
+    fn rejects_invalid_jsonl() {
+        let err = CollectionSnapshot::decode(vec![], SnapshotFormat::Jsonl).unwrap_err();
+        assert!(matches!(
+            err.error,
+            crate::error::ChonkitErr::InvalidSnapshot(_)
+        ));
+
+        let mut bytes = encode(snapshot(), SnapshotFormat::Jsonl);
+        bytes.extend_from_slice(b"{\"id\": 1}\n");
+        let err = CollectionSnapshot::decode(bytes, SnapshotFormat::Jsonl).unwrap_err();
+        assert!(matches!(
+            err.error,
+            crate::error::ChonkitErr::InvalidSnapshot(_)
+        ));
+    }
+}
@@ -95,6 +95,29 @@ pub enum ChonkitErr {
 
     #[error("encoding: {0}")]
     Encoding(#[from] base64::DecodeError),
+
+    #[error("Invalid alias; {0}")]
+    InvalidAlias(String),
+
+    #[error("Invalid snapshot; {0}")]
+    InvalidSnapshot(String),
+
+    #[error("Reqwest; {0}")]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("Generation; {0}")]
+    Generation(String),
+
+    #[error("Task; {0}")]
+    Task(#[from] tokio::task::JoinError),
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet; {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[cfg(feature = "parquet")]
+    #[error("Arrow; {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
 }
 
 #[derive(Debug, Error)]
@@ -26,6 +26,7 @@ impl ChonkitError {
             | E::Embedding(_)
             | E::Utf8(_)
             | E::Batch
+            | E::Task(_)
             | E::SerdeJson(_) => SC::INTERNAL_SERVER_ERROR,
             E::Axum(_) => SC::INTERNAL_SERVER_ERROR,
 
@@ -39,6 +40,12 @@ impl ChonkitError {
             E::ParseConfig(_) => SC::UNPROCESSABLE_ENTITY,
             E::Unauthorized => SC::UNAUTHORIZED,
             E::Encoding(_) => SC::UNPROCESSABLE_ENTITY,
+            E::InvalidSnapshot(_) => SC::UNPROCESSABLE_ENTITY,
+            E::InvalidAlias(_) => SC::UNPROCESSABLE_ENTITY,
+            E::Reqwest(_) | E::Generation(_) => SC::BAD_GATEWAY,
+
+            #[cfg(feature = "parquet")]
+            E::Parquet(_) | E::Arrow(_) => SC::INTERNAL_SERVER_ERROR,
         }
     }
 }
@@ -113,6 +120,7 @@ impl IntoResponse for ChonkitError {
             | CE::Utf8(_)
             | CE::Sqlx(_)
             | CE::InvalidFileName(_)
+            | CE::Task(_)
             | CE::Http(_) => (status, "Internal".to_string()).into_response(),
             CE::ParsePdf(_) => todo!(),
             CE::DocxRead(_) => todo!(),
@@ -138,6 +146,15 @@ impl IntoResponse for ChonkitError {
             CE::ParseConfig(e) => (status, e.to_string()).into_response(),
             CE::Unauthorized => (status, "Unauthorized".to_string()).into_response(),
             CE::Encoding(e) => (status, e.to_string()).into_response(),
+            CE::InvalidSnapshot(e) => (status, ResponseError::new(ET::Api, e)).into_response(),
+            CE::InvalidAlias(e) => (status, ResponseError::new(ET::Api, e)).into_response(),
+            CE::Reqwest(e) => {
+                (status, ResponseError::new(ET::Internal, e.to_string())).into_response()
+            }
+            CE::Generation(e) => (status, ResponseError::new(ET::Internal, e)).into_response(),
+
+            #[cfg(feature = "parquet")]
+            CE::Parquet(_) | CE::Arrow(_) => (status, "Internal".to_string()).into_response(),
         }
     }
 }
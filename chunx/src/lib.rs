@@ -1,10 +1,12 @@
 use std::str::Utf8Error;
 
 mod cursor;
+pub mod markdown;
 pub mod semantic;
 pub mod sliding;
 pub mod snapping;
 
+pub use markdown::MarkdownWindow;
 pub use semantic::SemanticWindow;
 pub use sliding::SlidingWindow;
 pub use snapping::SnappingWindow;
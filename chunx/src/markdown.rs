@@ -0,0 +1,214 @@
+use super::ChunkerError;
+use tracing::debug;
+
+const MARKDOWN_DEFAULT_SIZE: usize = 1000;
+
+/// Heading aware chunker for Markdown documents.
+///
+/// Every ATX heading (`#` to `######`) outside of fenced code blocks starts
+/// a new chunk, so chunks never span multiple sections.
+/// Sections larger than `size` are split between paragraphs, packing as many
+/// consecutive paragraphs into a chunk as fit in `size`. Paragraphs larger than
+/// `size` are never split.
+#[derive(Debug, Clone)]
+pub struct MarkdownWindow {
+    pub size: usize,
+}
+
+impl MarkdownWindow {
+    /// Create a new `MarkdownWindow` chunker.
+    /// Errors if `size` is 0.
+    pub fn new(size: usize) -> Result<Self, ChunkerError> {
+        if size == 0 {
+            return Err(ChunkerError::Config(
+                "size must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self { size })
+    }
+}
+
+impl Default for MarkdownWindow {
+    fn default() -> Self {
+        Self::new(MARKDOWN_DEFAULT_SIZE).expect("size is 0")
+    }
+}
+
+impl MarkdownWindow {
+    pub fn chunk<'a>(&self, input: &'a str) -> Result<Vec<&'a str>, ChunkerError> {
+        if input.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut chunks = vec![];
+
+        for (start, end) in sections(input) {
+            let section = input[start..end].trim();
+
+            if section.is_empty() {
+                continue;
+            }
+
+            if section.len() <= self.size {
+                chunks.push(section);
+                continue;
+            }
+
+            // Pack paragraphs into chunks of at most `size`
+            let mut chunk: Option<(usize, usize)> = None;
+
+            for (p_start, p_end) in paragraphs(input, start, end) {
+                chunk = match chunk {
+                    Some((c_start, _)) if p_end - c_start <= self.size => Some((c_start, p_end)),
+                    Some((c_start, c_end)) => {
+                        chunks.push(input[c_start..c_end].trim());
+                        Some((p_start, p_end))
+                    }
+                    None => Some((p_start, p_end)),
+                };
+            }
+
+            if let Some((c_start, c_end)) = chunk {
+                chunks.push(input[c_start..c_end].trim());
+            }
+        }
+
+        if !chunks.is_empty() {
+            debug!(
+                "Chunked {} chunks, avg chunk size: {}",
+                chunks.len(),
+                chunks.iter().fold(0, |acc, el| acc + el.len()) / chunks.len()
+            );
+        }
+
+        Ok(chunks)
+    }
+}
+
+/// Byte ranges of the input's sections. Each section except the first starts at a heading.
+fn sections(input: &str) -> Vec<(usize, usize)> {
+    let mut starts = vec![0];
+    let mut fenced = false;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if is_fence(line) {
+            fenced = !fenced;
+        } else if !fenced && offset > 0 && is_heading(line) {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+
+    let ends = starts.iter().skip(1).copied().chain([input.len()]);
+
+    starts.iter().copied().zip(ends).collect()
+}
+
+/// Byte ranges of the paragraphs in `input[start..end]`, i.e. runs of non blank lines.
+/// Blank lines in fenced code blocks do not end paragraphs.
+fn paragraphs(input: &str, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let mut paragraphs = vec![];
+    let mut paragraph: Option<(usize, usize)> = None;
+    let mut fenced = false;
+    let mut offset = start;
+
+    for line in input[start..end].split_inclusive('\n') {
+        if is_fence(line) {
+            fenced = !fenced;
+        }
+
+        if line.trim().is_empty() && !fenced {
+            paragraphs.extend(paragraph.take());
+        } else {
+            let line_end = offset + line.trim_end().len();
+            paragraph = Some((paragraph.map_or(offset, |(p_start, _)| p_start), line_end));
+        }
+
+        offset += line.len();
+    }
+
+    paragraphs.extend(paragraph);
+
+    paragraphs
+}
+
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+fn is_heading(line: &str) -> bool {
+    let line = line.trim_end();
+    let content = line.trim_start_matches(' ');
+
+    // Headings can be indented by at most 3 spaces
+    if line.len() - content.len() > 3 {
+        return false;
+    }
+
+    let text = content.trim_start_matches('#');
+    let level = content.len() - text.len();
+
+    (1..=6).contains(&level) && (text.is_empty() || text.starts_with([' ', '\t']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_window_splits_at_headings() {
+        let input = "Intro text.\n\n# Title\n\nSome text.\n\n## Section\nMore text.\n#hashtag\n";
+        let window = MarkdownWindow::new(1000).unwrap();
+        let chunks = window.chunk(input).unwrap();
+
+        assert_eq!(
+            vec![
+                "Intro text.",
+                "# Title\n\nSome text.",
+                "## Section\nMore text.\n#hashtag"
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn markdown_window_ignores_headings_in_code() {
+        let input = "# Shell\n\n```sh\n# comment\n\necho hi\n```\n\n# Next\nText";
+        let window = MarkdownWindow::new(1000).unwrap();
+        let chunks = window.chunk(input).unwrap();
+
+        assert_eq!(
+            vec![
+                "# Shell\n\n```sh\n# comment\n\necho hi\n```",
+                "# Next\nText"
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn markdown_window_packs_large_sections() {
+        let input =
+            "# Title\n\nFirst paragraph.\n\nSecond one.\n\n```\ncode\n\nblock\n```\n\nLast.";
+        let window = MarkdownWindow::new(30).unwrap();
+        let chunks = window.chunk(input).unwrap();
+
+        assert_eq!(
+            vec![
+                "# Title\n\nFirst paragraph.",
+                "Second one.",
+                "```\ncode\n\nblock\n```\n\nLast."
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn markdown_window_empty() {
+        let window = MarkdownWindow::default();
+        assert!(window.chunk(" \n\n").unwrap().is_empty());
+        assert!(MarkdownWindow::new(0).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use thiserror::Error;
 
+#[cfg(feature = "openai")]
 use crate::openai::OpenAIError;
 
 #[derive(Debug, Error)]
@@ -7,3 +7,14 @@ pub mod local;
 /// to a machine running feserver.
 #[cfg(feature = "fe-remote")]
 pub mod remote;
+
+/// Output of sparse models such as SPLADE. Only the non-zero
+/// dimensions of the vector are stored.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SparseEmbedding {
+    /// Dimensions with non-zero values.
+    pub indices: Vec<u32>,
+
+    /// Values of the dimensions in `indices`.
+    pub values: Vec<f32>,
+}
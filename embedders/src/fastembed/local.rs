@@ -1,16 +1,173 @@
+use super::SparseEmbedding;
 use crate::error::EmbeddingError;
-use fastembed::{EmbeddingModel, ModelInfo};
+use fastembed::{
+    EmbeddingModel, InitOptions, InitOptionsUserDefined, Pooling, SparseInitOptions,
+    SparseTextEmbedding, TextEmbedding, TokenizerFiles, UserDefinedEmbeddingModel,
+};
 use ort::execution_providers::CPUExecutionProvider;
 #[cfg(feature = "cuda")]
 use ort::execution_providers::CUDAExecutionProvider;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
 
+/// Configuration for [LocalFastEmbedder]. Can be deserialized from a JSON file
+/// with [LocalFastEmbedderConfig::from_file].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LocalFastEmbedderConfig {
+    /// Names of the enabled models. Can be any fastembed supported model code, or
+    /// the name of a model in `model_dir`.
+    /// If empty, all supported models and all models in `model_dir` are enabled.
+    pub models: Vec<String>,
+
+    /// Names of the enabled sparse models. Can be any fastembed supported sparse model code.
+    /// If empty, all supported sparse models are enabled.
+    pub sparse_models: Vec<String>,
+
+    /// Directory where fastembed caches downloaded models.
+    /// Models already present in the cache are loaded without network access.
+    /// Defaults to fastembed's `.fastembed_cache`.
+    pub cache_dir: Option<PathBuf>,
+
+    /// Directory containing user provided models, one directory per model.
+    /// The directory name is used as the model name and it must contain `model.onnx`,
+    /// `tokenizer.json`, `config.json`, `special_tokens_map.json` and `tokenizer_config.json`.
+    /// Models from this directory are always loaded offline.
+    pub model_dir: Option<PathBuf>,
+
+    /// Unload models that have not been used for this amount of seconds.
+    /// If not set, models stay loaded once used.
+    pub idle_timeout: Option<u64>,
+}
+
+impl LocalFastEmbedderConfig {
+    /// Read the configuration from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path).unwrap_or_else(|e| {
+            panic!(
+                "error while reading fastembed config {}: {e}",
+                path.display()
+            )
+        });
+        serde_json::from_str(&config).unwrap_or_else(|e| {
+            panic!(
+                "error while parsing fastembed config {}: {e}",
+                path.display()
+            )
+        })
+    }
+
+    /// Parse a comma separated list of model names, skipping empty entries.
+    pub fn parse_models(models: &str) -> Vec<String> {
+        models
+            .split(',')
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(String::from)
+            .collect()
+    }
+}
+
+/// Fastembed running on the local machine. Models are loaded on first use and,
+/// if configured, unloaded after being idle. Clones share the same models.
+#[derive(Clone)]
 pub struct LocalFastEmbedder {
-    pub models: std::collections::HashMap<String, fastembed::TextEmbedding>,
+    models: Arc<Models>,
+    cache_dir: Option<PathBuf>,
+}
+
+/// The enabled dense and sparse models.
+struct Models {
+    text: HashMap<String, ModelSlot>,
+    sparse: HashMap<String, SparseModelSlot>,
+}
+
+/// An enabled model which may or may not be loaded.
+struct ModelSlot {
+    source: ModelSource,
+    dim: usize,
+    embedding: Lazy<TextEmbedding>,
+}
+
+/// An enabled sparse model which may or may not be loaded.
+/// The options are only completed with the cache directory and
+/// execution providers when loading.
+struct SparseModelSlot {
+    options: SparseInitOptions,
+    embedding: Lazy<SparseTextEmbedding>,
+}
+
+/// A model loaded on first use which keeps track of when it was last used.
+struct Lazy<M> {
+    model: Mutex<Option<Arc<M>>>,
+    last_used: Mutex<Instant>,
+}
+
+impl<M> Lazy<M> {
+    fn new() -> Self {
+        Self {
+            model: Mutex::new(None),
+            last_used: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Get the model, loading it with `load` if necessary. Concurrent calls
+    /// wait for the first one to finish loading.
+    fn get_or_load(
+        &self,
+        load: impl FnOnce() -> Result<M, EmbeddingError>,
+    ) -> Result<Arc<M>, EmbeddingError> {
+        *self.last_used.lock().unwrap() = Instant::now();
+
+        let mut model = self.model.lock().unwrap();
+
+        if let Some(ref model) = *model {
+            return Ok(model.clone());
+        }
+
+        let loaded = Arc::new(load()?);
+
+        *model = Some(loaded.clone());
+
+        Ok(loaded)
+    }
+
+    /// Unload the model if it has not been used for `idle_timeout`.
+    /// Returns `true` if the model was unloaded.
+    fn unload_idle(&self, idle_timeout: Duration) -> bool {
+        if self.last_used.lock().unwrap().elapsed() < idle_timeout {
+            return false;
+        }
+
+        // Requests in progress hold their own reference to the model
+        // so it gets dropped once they finish.
+        self.model.lock().unwrap().take().is_some()
+    }
+}
+
+enum ModelSource {
+    /// Downloaded from, or loaded from the cache of, the fastembed model registry.
+    Fastembed(EmbeddingModel),
+
+    /// Loaded from a directory on the local file system.
+    Local(PathBuf),
 }
 
 impl LocalFastEmbedder {
-    /// Initialise the FastEmbedder locally.
+    /// Initialise the FastEmbedder locally with all supported models enabled.
     pub fn new() -> Self {
+        Self::with_config(LocalFastEmbedderConfig::default())
+    }
+
+    /// Initialise the FastEmbedder locally.
+    /// Panics if any of the configured models is not available.
+    pub fn with_config(config: LocalFastEmbedderConfig) -> Self {
         tracing::info!("Initializing local Fastembed");
         #[cfg(feature = "cuda")]
         {
@@ -21,64 +178,112 @@ impl LocalFastEmbedder {
             );
         }
 
-        let mut models = std::collections::HashMap::new();
+        let mut available = HashMap::new();
 
-        for model in list_models() {
-            tracing::info!("Setting up text embedding model: {}", model.model_code);
-            let embedding = fastembed::TextEmbedding::try_new(
-                fastembed::InitOptions::new(model.model)
-                    .with_execution_providers(vec![
-                        #[cfg(feature = "cuda")]
-                        CUDAExecutionProvider::default().into(),
-                        CPUExecutionProvider::default().into(),
-                    ])
-                    .with_show_download_progress(true),
-            )
-            .unwrap_or_else(|e| panic!("error while instantiating text embedding model: {e}"));
+        for model in TextEmbedding::list_supported_models() {
+            available.insert(
+                model.model_code,
+                (ModelSource::Fastembed(model.model), model.dim),
+            );
+        }
 
-            models.insert(model.model_code.to_string(), embedding);
+        if let Some(ref model_dir) = config.model_dir {
+            for (name, path, dim) in read_model_dir(model_dir) {
+                available.insert(name, (ModelSource::Local(path), dim));
+            }
         }
-        Self { models }
-    }
 
-    /// Initialize the FastEmbedder with a specific model.
-    /// Useful for tests. If `model` is `None`, the default model will be used.
-    #[doc(hidden)]
-    pub fn new_with_model(model: &str) -> Self {
-        let mut models = std::collections::HashMap::new();
+        if !config.models.is_empty() {
+            for model in config.models.iter() {
+                if !available.contains_key(model) {
+                    panic!("model '{model}' not supported by fastembed and not found in model directory");
+                }
+            }
+            available.retain(|name, _| config.models.contains(name));
+        }
 
-        for m in fastembed::TextEmbedding::list_supported_models() {
-            if m.model_code != model {
-                continue;
+        let text = available
+            .into_iter()
+            .map(|(name, (source, dim))| {
+                tracing::debug!("Enabling text embedding model: {name}");
+                let slot = ModelSlot {
+                    source,
+                    dim,
+                    embedding: Lazy::new(),
+                };
+                (name, slot)
+            })
+            .collect();
+
+        let mut available_sparse: HashMap<_, _> = SparseTextEmbedding::list_supported_models()
+            .into_iter()
+            .map(|model| (model.model_code, SparseInitOptions::new(model.model)))
+            .collect();
+
+        if !config.sparse_models.is_empty() {
+            for model in config.sparse_models.iter() {
+                if !available_sparse.contains_key(model) {
+                    panic!("sparse model '{model}' not supported by fastembed");
+                }
             }
+            available_sparse.retain(|name, _| config.sparse_models.contains(name));
+        }
+
+        let sparse = available_sparse
+            .into_iter()
+            .map(|(name, options)| {
+                tracing::debug!("Enabling sparse text embedding model: {name}");
+                let slot = SparseModelSlot {
+                    options,
+                    embedding: Lazy::new(),
+                };
+                (name, slot)
+            })
+            .collect();
 
-            tracing::info!("Setting up text embedding model: {}", m.model_code);
+        let models = Arc::new(Models { text, sparse });
 
-            let embedding = fastembed::TextEmbedding::try_new(
-                fastembed::InitOptions::new(m.model)
-                    .with_execution_providers(vec![
-                        #[cfg(feature = "cuda")]
-                        CUDAExecutionProvider::default().into(),
-                        CPUExecutionProvider::default().into(),
-                    ])
-                    .with_show_download_progress(true),
-            )
-            .unwrap_or_else(|e| panic!("error while instantiating text embedding model: {e}"));
+        if let Some(idle_timeout) = config.idle_timeout {
+            spawn_reaper(Arc::downgrade(&models), Duration::from_secs(idle_timeout));
+        }
 
-            models.insert(m.model_code.to_string(), embedding);
+        Self {
+            models,
+            cache_dir: config.cache_dir,
         }
+    }
+
+    /// Initialize the FastEmbedder with a specific model.
+    /// Useful for tests.
+    #[doc(hidden)]
+    pub fn new_with_model(model: &str) -> Self {
+        Self::with_config(LocalFastEmbedderConfig {
+            models: vec![model.to_string()],
+            ..Default::default()
+        })
+    }
 
-        LocalFastEmbedder { models }
+    /// List the enabled models and their sizes.
+    pub fn list_models(&self) -> Vec<(String, usize)> {
+        let mut models: Vec<_> = self
+            .models
+            .text
+            .iter()
+            .map(|(name, slot)| (name.clone(), slot.dim))
+            .collect();
+        models.sort();
+        models
     }
 
-    pub fn list_models(&self) -> Vec<ModelInfo<EmbeddingModel>> {
-        list_models()
+    /// List the enabled sparse models.
+    pub fn list_sparse_models(&self) -> Vec<String> {
+        let mut models: Vec<_> = self.models.sparse.keys().cloned().collect();
+        models.sort();
+        models
     }
 
     pub fn embed(&self, content: &[&str], model: &str) -> Result<Vec<Vec<f64>>, EmbeddingError> {
-        let embedder = self.models.get(model).ok_or_else(|| {
-            EmbeddingError::InvalidModel(format!("model '{model}' not supported by fastembed",))
-        })?;
+        let embedder = self.load(model)?;
 
         let embeddings = embedder.embed(content.to_vec(), None)?;
 
@@ -93,21 +298,217 @@ impl LocalFastEmbedder {
             .map(|e| e.into_iter().map(|e| e as f64).collect())
             .collect())
     }
+
+    pub fn embed_sparse(
+        &self,
+        content: &[&str],
+        model: &str,
+    ) -> Result<Vec<SparseEmbedding>, EmbeddingError> {
+        let embedder = self.load_sparse(model)?;
+
+        let embeddings = embedder.embed(content.to_vec(), None)?;
+
+        debug_assert_eq!(
+            embeddings.len(),
+            content.len(),
+            "Content length is different from embeddings!"
+        );
+
+        Ok(embeddings
+            .into_iter()
+            .map(|e| SparseEmbedding {
+                indices: e.indices.into_iter().map(|i| i as u32).collect(),
+                values: e.values,
+            })
+            .collect())
+    }
+
+    /// Count the tokens the model's tokenizer produces for the content,
+    /// including special tokens and excluding padding.
+    pub fn count_tokens(&self, content: &[&str], model: &str) -> Result<usize, EmbeddingError> {
+        let embedder = self.load(model)?;
+
+        let encodings = embedder
+            .tokenizer
+            .encode_batch(content.to_vec(), true)
+            .map_err(|e| EmbeddingError::InvalidInput(e.to_string()))?;
+
+        Ok(encodings
+            .iter()
+            .map(|e| e.get_attention_mask().iter().filter(|m| **m == 1).count())
+            .sum())
+    }
+
+    /// Get the model, loading it if necessary.
+    fn load(&self, model: &str) -> Result<Arc<TextEmbedding>, EmbeddingError> {
+        let slot = self.models.text.get(model).ok_or_else(|| {
+            EmbeddingError::InvalidModel(format!("model '{model}' not supported by fastembed",))
+        })?;
+
+        slot.embedding.get_or_load(|| {
+            tracing::info!("Loading text embedding model: {model}");
+
+            let embedding = match slot.source {
+                ModelSource::Fastembed(ref m) => {
+                    let mut options = InitOptions::new(m.clone())
+                        .with_execution_providers(vec![
+                            #[cfg(feature = "cuda")]
+                            CUDAExecutionProvider::default().into(),
+                            CPUExecutionProvider::default().into(),
+                        ])
+                        .with_show_download_progress(true);
+                    if let Some(ref cache_dir) = self.cache_dir {
+                        options = options.with_cache_dir(cache_dir.clone());
+                    }
+                    TextEmbedding::try_new(options)?
+                }
+                ModelSource::Local(ref path) => TextEmbedding::try_new_from_user_defined(
+                    read_user_defined(model, path)?,
+                    InitOptionsUserDefined::new().with_execution_providers(vec![
+                        #[cfg(feature = "cuda")]
+                        CUDAExecutionProvider::default().into(),
+                        CPUExecutionProvider::default().into(),
+                    ]),
+                )?,
+            };
+
+            Ok(embedding)
+        })
+    }
+
+    /// Get the sparse model, loading it if necessary.
+    fn load_sparse(&self, model: &str) -> Result<Arc<SparseTextEmbedding>, EmbeddingError> {
+        let slot = self.models.sparse.get(model).ok_or_else(|| {
+            EmbeddingError::InvalidModel(format!(
+                "sparse model '{model}' not supported by fastembed",
+            ))
+        })?;
+
+        slot.embedding.get_or_load(|| {
+            tracing::info!("Loading sparse text embedding model: {model}");
+
+            let mut options = slot
+                .options
+                .clone()
+                .with_execution_providers(vec![
+                    #[cfg(feature = "cuda")]
+                    CUDAExecutionProvider::default().into(),
+                    CPUExecutionProvider::default().into(),
+                ])
+                .with_show_download_progress(true);
+            if let Some(ref cache_dir) = self.cache_dir {
+                options = options.with_cache_dir(cache_dir.clone());
+            }
+
+            Ok(SparseTextEmbedding::try_new(options)?)
+        })
+    }
+}
+
+/// Periodically unload models idle for longer than `idle_timeout`.
+/// Stops once the embedder is dropped.
+fn spawn_reaper(models: Weak<Models>, idle_timeout: Duration) {
+    let interval = (idle_timeout / 2).clamp(Duration::from_secs(1), Duration::from_secs(60));
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(interval);
+
+        let Some(models) = models.upgrade() else {
+            break;
+        };
+
+        for (name, slot) in models.text.iter() {
+            if slot.embedding.unload_idle(idle_timeout) {
+                tracing::info!("Unloading idle text embedding model: {name}");
+            }
+        }
+
+        for (name, slot) in models.sparse.iter() {
+            if slot.embedding.unload_idle(idle_timeout) {
+                tracing::info!("Unloading idle sparse text embedding model: {name}");
+            }
+        }
+    });
+}
+
+/// Return the name, path and size of every model in `model_dir`.
+fn read_model_dir(model_dir: &Path) -> Vec<(String, PathBuf, usize)> {
+    let entries = std::fs::read_dir(model_dir).unwrap_or_else(|e| {
+        panic!(
+            "error while reading model directory {}: {e}",
+            model_dir.display()
+        )
+    });
+
+    let mut models = vec![];
+
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(e) => {
+                tracing::warn!("Skipping model directory entry: {e}");
+                continue;
+            }
+        };
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(String::from) else {
+            continue;
+        };
+
+        match read_model_dim(&path) {
+            Some(dim) => models.push((name, path, dim)),
+            None => tracing::warn!(
+                "Skipping model '{name}'; config.json is missing or does not contain `hidden_size`"
+            ),
+        }
+    }
+
+    models
+}
+
+/// Read the embedding size from the model's `config.json`.
+fn read_model_dim(path: &Path) -> Option<usize> {
+    let config = std::fs::read_to_string(path.join("config.json")).ok()?;
+    let config: serde_json::Value = serde_json::from_str(&config).ok()?;
+    config.get("hidden_size")?.as_u64().map(|dim| dim as usize)
 }
 
-fn list_models() -> Vec<ModelInfo<EmbeddingModel>> {
-    const MODEL_LIST: &[EmbeddingModel] = &[
-        EmbeddingModel::BGESmallENV15,
-        EmbeddingModel::BGELargeENV15,
-        EmbeddingModel::BGEBaseENV15,
-        EmbeddingModel::AllMiniLML6V2,
-        EmbeddingModel::AllMiniLML12V2,
-    ];
+/// Read the files of a user defined model. Uses mean pooling if the model directory
+/// contains a sentence transformers pooling config specifying it, CLS otherwise.
+fn read_user_defined(
+    model: &str,
+    path: &Path,
+) -> Result<UserDefinedEmbeddingModel, EmbeddingError> {
+    let read = |file: &str| {
+        std::fs::read(path.join(file)).map_err(|e| {
+            EmbeddingError::InvalidModel(format!("model '{model}': cannot read {file}: {e}"))
+        })
+    };
+
+    let tokenizer_files = TokenizerFiles {
+        tokenizer_file: read("tokenizer.json")?,
+        config_file: read("config.json")?,
+        special_tokens_map_file: read("special_tokens_map.json")?,
+        tokenizer_config_file: read("tokenizer_config.json")?,
+    };
+
+    let mean_pooling = std::fs::read_to_string(path.join("1_Pooling/config.json"))
+        .ok()
+        .and_then(|config| serde_json::from_str::<serde_json::Value>(&config).ok())
+        .and_then(|config| config.get("pooling_mode_mean_tokens")?.as_bool())
+        .unwrap_or(false);
 
-    fastembed::TextEmbedding::list_supported_models()
-        .into_iter()
-        .filter(|model| MODEL_LIST.contains(&model.model))
-        .collect()
+    let pooling = if mean_pooling {
+        Pooling::Mean
+    } else {
+        Pooling::Cls
+    };
+
+    Ok(UserDefinedEmbeddingModel::new(read("model.onnx")?, tokenizer_files).with_pooling(pooling))
 }
 
 impl Default for LocalFastEmbedder {
@@ -121,3 +522,17 @@ impl std::fmt::Debug for LocalFastEmbedder {
         f.debug_struct("FastEmbedder").finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LocalFastEmbedderConfig;
+
+    #[test]
+    fn parses_models() {
+        assert_eq!(
+            vec!["a", "b"],
+            LocalFastEmbedderConfig::parse_models(" a, ,b, ")
+        );
+        assert!(LocalFastEmbedderConfig::parse_models(",").is_empty());
+    }
+}
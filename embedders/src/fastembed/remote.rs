@@ -1,4 +1,8 @@
-use crate::error::EmbeddingError;
+use super::SparseEmbedding;
+use crate::{
+    error::EmbeddingError,
+    request::{estimate_tokens, RateLimiter, RequestConfig},
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -7,47 +11,125 @@ use std::collections::HashMap;
 pub struct RemoteFastEmbedder {
     pub client: reqwest::Client,
     pub url: String,
+    config: RequestConfig,
+    limiter: RateLimiter,
 }
 
 impl RemoteFastEmbedder {
-    /// Initialise the FastEmbedder remote client.
+    /// Initialise the FastEmbedder remote client with the default [RequestConfig].
     pub fn new(url: String) -> RemoteFastEmbedder {
+        Self::with_config(url, RequestConfig::default())
+    }
+
+    /// Initialise the FastEmbedder remote client.
+    pub fn with_config(url: String, config: RequestConfig) -> RemoteFastEmbedder {
         tracing::info!("Initializing remote Fastembed at {url}");
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(5000))
-            .build()
-            .expect("error while building http client");
-        RemoteFastEmbedder { client, url }
+        RemoteFastEmbedder {
+            client: config.client(),
+            limiter: config.limiter(),
+            config,
+            url,
+        }
     }
+
     pub async fn list_models(&self) -> Result<Vec<(String, usize)>, EmbeddingError> {
         let url = self.url("list");
-        let response: HashMap<String, usize> = self.client.get(&url).send().await?.json().await?;
+        let response: HashMap<String, usize> = self
+            .config
+            .send(|| self.client.get(&url))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
         Ok(response.into_iter().collect())
     }
 
+    pub async fn list_sparse_models(&self) -> Result<Vec<String>, EmbeddingError> {
+        let url = self.url("list/sparse");
+        let response: Vec<String> = self
+            .config
+            .send(|| self.client.get(&url))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response)
+    }
+
+    /// Embed the content, splitting it into multiple requests according
+    /// to the [RequestConfig].
     pub async fn embed(
         &self,
         content: &[&str],
         model: &str,
     ) -> Result<Vec<Vec<f64>>, EmbeddingError> {
         let url = self.url("embed");
-        let request = EmbedRequest {
-            model: model.to_string(),
-            input: content.iter().map(|s| s.to_string()).collect(),
-        };
-
-        let response: EmbedResponse = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?
-            .json()
-            .await?;
+        let mut embeddings = Vec::with_capacity(content.len());
+
+        for batch in self.config.batches(content) {
+            let batch = &content[batch];
+
+            let request = EmbedRequest {
+                model: model.to_string(),
+                input: batch.iter().map(|s| s.to_string()).collect(),
+            };
 
-        debug_assert_eq!(content.len(), response.embeddings.len());
+            self.limiter
+                .acquire(batch.iter().map(|s| estimate_tokens(s)).sum())
+                .await;
 
-        Ok(response.embeddings)
+            let response: EmbedResponse = self
+                .config
+                .send(|| self.client.post(&url).json(&request))
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            debug_assert_eq!(batch.len(), response.embeddings.len());
+
+            embeddings.extend(response.embeddings);
+        }
+
+        Ok(embeddings)
+    }
+
+    /// Embed the content with a sparse model, splitting it into multiple requests
+    /// according to the [RequestConfig].
+    pub async fn embed_sparse(
+        &self,
+        content: &[&str],
+        model: &str,
+    ) -> Result<Vec<SparseEmbedding>, EmbeddingError> {
+        let url = self.url("embed/sparse");
+        let mut embeddings = Vec::with_capacity(content.len());
+
+        for batch in self.config.batches(content) {
+            let batch = &content[batch];
+
+            let request = EmbedRequest {
+                model: model.to_string(),
+                input: batch.iter().map(|s| s.to_string()).collect(),
+            };
+
+            self.limiter
+                .acquire(batch.iter().map(|s| estimate_tokens(s)).sum())
+                .await;
+
+            let response: SparseEmbedResponse = self
+                .config
+                .send(|| self.client.post(&url).json(&request))
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            debug_assert_eq!(batch.len(), response.embeddings.len());
+
+            embeddings.extend(response.embeddings);
+        }
+
+        Ok(embeddings)
     }
 
     fn url(&self, path: &str) -> String {
@@ -66,6 +148,11 @@ pub struct EmbedResponse {
     embeddings: Vec<Vec<f64>>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SparseEmbedResponse {
+    embeddings: Vec<SparseEmbedding>,
+}
+
 impl std::fmt::Debug for RemoteFastEmbedder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FastEmbedder")
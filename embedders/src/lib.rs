@@ -5,3 +5,5 @@ pub mod fastembed;
 #[cfg(feature = "openai")]
 pub mod openai;
 
+#[cfg(any(feature = "openai", feature = "fe-remote"))]
+pub mod request;
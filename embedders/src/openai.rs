@@ -1,6 +1,9 @@
 use std::error::Error;
 
-use crate::error::EmbeddingError;
+use crate::{
+    error::EmbeddingError,
+    request::{estimate_tokens, RateLimiter, RequestConfig},
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::debug;
@@ -11,35 +14,71 @@ pub struct OpenAiEmbeddings {
     endpoint: String,
     key: String,
     client: reqwest::Client,
+    config: RequestConfig,
+    limiter: RateLimiter,
 }
 
 impl OpenAiEmbeddings {
+    /// Initialise the OpenAI client with the default [RequestConfig].
     pub fn new(api_key: &str) -> Self {
+        Self::with_config(api_key, RequestConfig::default())
+    }
+
+    /// Initialise the OpenAI client.
+    pub fn with_config(api_key: &str, config: RequestConfig) -> Self {
         Self {
             endpoint: DEFAULT_OPENAI_ENDPOINT.to_string(),
             key: api_key.to_string(),
-            client: reqwest::Client::new(),
+            client: config.client(),
+            limiter: config.limiter(),
+            config,
         }
     }
 
-    pub fn list_embedding_models(&self) -> Vec<(String, usize)> {
-        vec![
-            (String::from(TEXT_EMBEDDING_3_LARGE), 3072),
-            (String::from(TEXT_EMBEDDING_3_SMALL), 1536),
-            (String::from(TEXT_EMBEDDING_ADA_002), 1536),
-        ]
+    /// Use an OpenAI compatible API at `endpoint` instead of OpenAI's.
+    /// The endpoint should not contain the `/v1` path.
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_string();
+        self
     }
 
+    /// List the available models and their sizes.
+    ///
+    /// OpenAI's `/v1/models` does not report embedding sizes, so the known OpenAI
+    /// models are returned when using the default endpoint. Custom endpoints are queried
+    /// and only the models reporting their `dimensions` are returned.
+    pub async fn list_embedding_models(&self) -> Result<Vec<(String, usize)>, EmbeddingError> {
+        if self.endpoint == DEFAULT_OPENAI_ENDPOINT {
+            return Ok(vec![
+                (String::from(TEXT_EMBEDDING_3_LARGE), 3072),
+                (String::from(TEXT_EMBEDDING_3_SMALL), 1536),
+                (String::from(TEXT_EMBEDDING_ADA_002), 1536),
+            ]);
+        }
+
+        let url = format!("{}/v1/models", self.endpoint);
+        let response: ModelList = self
+            .config
+            .send(|| self.client.get(&url).bearer_auth(&self.key))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .filter_map(|model| Some((model.id, model.dimensions?)))
+            .collect())
+    }
+
+    /// Embed the input, splitting it into multiple requests according
+    /// to the [RequestConfig].
     pub async fn embed(
         &self,
         input: &[&str],
         model: &str,
     ) -> Result<Vec<Vec<f64>>, EmbeddingError> {
-        let request = EmbeddingRequest {
-            model: model.to_string(),
-            input: input.iter().map(|s| s.to_string()).collect(),
-        };
-
         if input.is_empty() {
             return Err(EmbeddingError::InvalidInput(format!(
                 "cannot be empty (len = {})",
@@ -47,18 +86,43 @@ impl OpenAiEmbeddings {
             )));
         }
 
+        let mut embeddings = Vec::with_capacity(input.len());
+
+        for batch in self.config.batches(input) {
+            embeddings.extend(self.embed_batch(&input[batch], model).await?);
+        }
+
+        Ok(embeddings)
+    }
+
+    async fn embed_batch(
+        &self,
+        input: &[&str],
+        model: &str,
+    ) -> Result<Vec<Vec<f64>>, EmbeddingError> {
+        let request = EmbeddingRequest {
+            model: model.to_string(),
+            input: input.iter().map(|s| s.to_string()).collect(),
+        };
+
+        self.limiter
+            .acquire(input.iter().map(|s| estimate_tokens(s)).sum())
+            .await;
+
         let response = match self
-            .client
-            .post(format!("{}/v1/embeddings", self.endpoint))
-            .bearer_auth(&self.key)
-            .json(&request)
-            .send()
+            .config
+            .send(|| {
+                self.client
+                    .post(format!("{}/v1/embeddings", self.endpoint))
+                    .bearer_auth(&self.key)
+                    .json(&request)
+            })
             .await
         {
             Ok(res) => res,
             Err(e) => {
                 tracing::error!("Error in OpenAI request: {e}");
-                return Err(EmbeddingError::Reqwest(e));
+                return Err(e);
             }
         };
 
@@ -97,7 +161,10 @@ impl OpenAiEmbeddings {
             response.usage.total_tokens
         );
 
-        Ok(response.data.into_iter().map(|o| o.embedding).collect())
+        let mut data = response.data;
+        data.sort_by_key(|o| o.index);
+
+        Ok(data.into_iter().map(|o| o.embedding).collect())
     }
 }
 
@@ -130,13 +197,26 @@ struct Usage {
     total_tokens: usize,
 }
 
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    data: Vec<Model>,
+}
+
+/// Model object returned by `/v1/models`. `dimensions` is not part of the OpenAI
+/// API, but is returned by compatible servers such as feserver.
+#[derive(Debug, Deserialize)]
+struct Model {
+    id: String,
+    dimensions: Option<usize>,
+}
+
 #[derive(Debug, Deserialize, Error)]
 #[error("{message}, type: {r#type}, param: {param:?}, code: {code:?}")]
 pub struct OpenAIErrorParams {
     pub message: String,
     pub r#type: String,
     pub param: Option<String>,
-    pub code: Option<usize>,
+    pub code: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Error)]
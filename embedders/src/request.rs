@@ -0,0 +1,386 @@
+//! Request handling shared by the HTTP based embedders.
+//!
+//! Contains the batching, retry and rate limiting logic used by the
+//! [OpenAiEmbeddings][crate::openai::OpenAiEmbeddings] and
+//! [RemoteFastEmbedder][crate::fastembed::remote::RemoteFastEmbedder] clients.
+
+use crate::error::EmbeddingError;
+use reqwest::{header::HeaderMap, RequestBuilder, Response, StatusCode};
+use std::{collections::VecDeque, ops::Range, sync::Arc, time::Duration};
+use tokio::{sync::Mutex, time::Instant};
+
+/// The window in which the rate limits are applied.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Configures how embedding requests are split, retried and throttled.
+#[derive(Debug, Clone)]
+pub struct RequestConfig {
+    /// Maximum amount of inputs sent in a single request.
+    pub batch_size: usize,
+
+    /// Maximum amount of estimated tokens sent in a single request.
+    /// Inputs exceeding the budget on their own are sent in a request of their own.
+    pub batch_tokens: Option<usize>,
+
+    /// How many times a request is retried on rate limit (429), server (5xx),
+    /// connect and timeout errors.
+    pub max_retries: u32,
+
+    /// Backoff for the first retry. Doubled for every subsequent retry.
+    pub backoff_base: Duration,
+
+    /// Upper bound for the exponential backoff.
+    /// Does not apply to delays obtained from `Retry-After` headers.
+    pub backoff_max: Duration,
+
+    /// Upper bound for delays obtained from `Retry-After` headers.
+    pub retry_after_max: Duration,
+
+    /// Timeout for a single request.
+    pub timeout: Duration,
+
+    /// Timeout for establishing a connection.
+    pub connect_timeout: Duration,
+
+    /// Maximum amount of requests sent in a minute. `0` disables the limit.
+    pub requests_per_minute: Option<usize>,
+
+    /// Maximum amount of estimated tokens sent in a minute. `0` disables the limit.
+    pub tokens_per_minute: Option<usize>,
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 256,
+            batch_tokens: Some(100_000),
+            max_retries: 5,
+            backoff_base: Duration::from_millis(500),
+            backoff_max: Duration::from_secs(30),
+            retry_after_max: Duration::from_secs(120),
+            timeout: Duration::from_secs(60),
+            connect_timeout: Duration::from_secs(10),
+            requests_per_minute: None,
+            tokens_per_minute: None,
+        }
+    }
+}
+
+impl RequestConfig {
+    /// Build an HTTP client with the configured timeouts.
+    pub fn client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .build()
+            .expect("error while building http client")
+    }
+
+    /// Create a rate limiter with the configured limits.
+    pub fn limiter(&self) -> RateLimiter {
+        RateLimiter::new(self.requests_per_minute, self.tokens_per_minute)
+    }
+
+    /// Split the input into ranges respecting the configured batch size and token budget.
+    pub fn batches(&self, input: &[&str]) -> Vec<Range<usize>> {
+        let batch_size = self.batch_size.max(1);
+        let mut batches = vec![];
+        let mut start = 0;
+        let mut tokens = 0;
+
+        for (i, item) in input.iter().enumerate() {
+            let item_tokens = estimate_tokens(item);
+
+            let exceeds_size = i - start >= batch_size;
+            let exceeds_tokens = self
+                .batch_tokens
+                .is_some_and(|max| i > start && tokens + item_tokens > max);
+
+            if exceeds_size || exceeds_tokens {
+                batches.push(start..i);
+                start = i;
+                tokens = 0;
+            }
+
+            tokens += item_tokens;
+        }
+
+        if start < input.len() {
+            batches.push(start..input.len());
+        }
+
+        batches
+    }
+
+    /// Send the request obtained from `request`, retrying on retryable errors.
+    ///
+    /// `Retry-After` (seconds) and `retry-after-ms` headers take precedence over
+    /// the exponential backoff and are capped at `retry_after_max`.
+    pub async fn send(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+    ) -> Result<Response, EmbeddingError> {
+        let mut attempt = 0;
+
+        loop {
+            let delay = match request().send().await {
+                Ok(response) if is_retryable(response.status()) && attempt < self.max_retries => {
+                    let delay = retry_after(response.headers())
+                        .map(|delay| delay.min(self.retry_after_max))
+                        .unwrap_or_else(|| self.backoff(attempt));
+                    tracing::warn!(
+                        "Request to {} failed with status {}, retrying in {}ms ({}/{})",
+                        response.url(),
+                        response.status(),
+                        delay.as_millis(),
+                        attempt + 1,
+                        self.max_retries
+                    );
+                    delay
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if (e.is_timeout() || e.is_connect()) && attempt < self.max_retries => {
+                    let delay = self.backoff(attempt);
+                    tracing::warn!(
+                        "Request failed ({e}), retrying in {}ms ({}/{})",
+                        delay.as_millis(),
+                        attempt + 1,
+                        self.max_retries
+                    );
+                    delay
+                }
+                Err(e) => return Err(EmbeddingError::Reqwest(e)),
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff_base
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.backoff_max)
+    }
+}
+
+/// Client side rate limiter using a sliding window of one minute.
+///
+/// Cloning the limiter yields a handle to the same window, so all clones
+/// share the same budget.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    requests_per_minute: Option<usize>,
+    tokens_per_minute: Option<usize>,
+    window: Arc<Mutex<Window>>,
+}
+
+#[derive(Debug, Default)]
+struct Window {
+    /// Timestamps and token amounts of the requests sent in the last minute.
+    requests: VecDeque<(Instant, usize)>,
+
+    /// Sum of the tokens in `requests`.
+    tokens: usize,
+}
+
+impl RateLimiter {
+    /// Create a limiter with the given limits. Limits of `0` are treated as disabled
+    /// since no request could ever be sent with them.
+    pub fn new(requests_per_minute: Option<usize>, tokens_per_minute: Option<usize>) -> Self {
+        Self {
+            requests_per_minute: requests_per_minute.filter(|max| *max > 0),
+            tokens_per_minute: tokens_per_minute.filter(|max| *max > 0),
+            window: Arc::default(),
+        }
+    }
+
+    /// Wait until a request with the given amount of tokens can be sent and record it.
+    ///
+    /// A request exceeding the token limit on its own is let through
+    /// once the window is empty.
+    pub async fn acquire(&self, tokens: usize) {
+        if self.requests_per_minute.is_none() && self.tokens_per_minute.is_none() {
+            return;
+        }
+
+        loop {
+            let wait = {
+                let mut window = self.window.lock().await;
+                let now = Instant::now();
+
+                while let Some((sent_at, amount)) = window.requests.front().copied() {
+                    if now.duration_since(sent_at) < RATE_LIMIT_WINDOW {
+                        break;
+                    }
+                    window.requests.pop_front();
+                    window.tokens -= amount;
+                }
+
+                let requests_ok = self
+                    .requests_per_minute
+                    .is_none_or(|max| window.requests.len() < max);
+
+                let tokens_ok = self
+                    .tokens_per_minute
+                    .is_none_or(|max| window.requests.is_empty() || window.tokens + tokens <= max);
+
+                if requests_ok && tokens_ok {
+                    window.requests.push_back((now, tokens));
+                    window.tokens += tokens;
+                    return;
+                }
+
+                // Wait for the oldest request to leave the window and check again
+                match window.requests.front() {
+                    Some((sent_at, _)) => RATE_LIMIT_WINDOW - now.duration_since(*sent_at),
+                    None => Duration::ZERO,
+                }
+            };
+
+            tracing::debug!("Rate limit reached, waiting {}ms", wait.as_millis());
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Rough token estimate for an input. Errs on the side of caution
+/// by assuming 3 bytes per token.
+pub fn estimate_tokens(input: &str) -> usize {
+    input.len().div_ceil(3)
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Obtain the delay from the `retry-after-ms` or `Retry-After` headers, if any.
+/// Values that are not finite are ignored and values that do not fit in a [Duration]
+/// saturate; the delay is capped by the caller.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+    };
+
+    let secs = match header("retry-after-ms") {
+        Some(ms) => ms / 1000.,
+        None => header("retry-after")?,
+    };
+
+    Some(Duration::try_from_secs_f64(secs.max(0.)).unwrap_or(Duration::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn batches_by_size() {
+        let config = RequestConfig {
+            batch_size: 2,
+            batch_tokens: None,
+            ..Default::default()
+        };
+        let input = ["a", "b", "c", "d", "e"];
+        assert_eq!(vec![0..2, 2..4, 4..5], config.batches(&input));
+    }
+
+    #[test]
+    fn batches_by_tokens() {
+        let config = RequestConfig {
+            batch_size: 10,
+            batch_tokens: Some(2),
+            ..Default::default()
+        };
+        // 1, 1, 4 and 1 estimated tokens
+        let input = ["abc", "abc", "abcabcabcabc", "a"];
+        assert_eq!(vec![0..2, 2..3, 3..4], config.batches(&input));
+    }
+
+    #[test]
+    fn batches_empty() {
+        let config = RequestConfig::default();
+        assert!(config.batches(&[]).is_empty());
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let config = RequestConfig {
+            backoff_base: Duration::from_secs(1),
+            backoff_max: Duration::from_secs(5),
+            ..Default::default()
+        };
+        assert_eq!(Duration::from_secs(1), config.backoff(0));
+        assert_eq!(Duration::from_secs(4), config.backoff(2));
+        assert_eq!(Duration::from_secs(5), config.backoff(3));
+        assert_eq!(Duration::from_secs(5), config.backoff(40));
+    }
+
+    #[test]
+    fn retry_after_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(None, retry_after(&headers));
+
+        headers.insert("retry-after", HeaderValue::from_static("2"));
+        assert_eq!(Some(Duration::from_secs(2)), retry_after(&headers));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("150"));
+        assert_eq!(Some(Duration::from_millis(150)), retry_after(&headers));
+    }
+
+    #[test]
+    fn retry_after_handles_unrepresentable_values() {
+        let mut headers = HeaderMap::new();
+
+        headers.insert("retry-after", HeaderValue::from_static("inf"));
+        assert_eq!(None, retry_after(&headers));
+
+        headers.insert("retry-after", HeaderValue::from_static("NaN"));
+        assert_eq!(None, retry_after(&headers));
+
+        headers.insert("retry-after", HeaderValue::from_static("1e30"));
+        assert_eq!(Some(Duration::MAX), retry_after(&headers));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("-5"));
+        assert_eq!(Some(Duration::ZERO), retry_after(&headers));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limiter_waits_for_window() {
+        let limiter = RateLimiter::new(Some(2), None);
+        let start = Instant::now();
+
+        limiter.acquire(1).await;
+        limiter.clone().acquire(1).await;
+        assert!(start.elapsed() < RATE_LIMIT_WINDOW);
+
+        limiter.acquire(1).await;
+        assert!(start.elapsed() >= RATE_LIMIT_WINDOW);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limiter_ignores_zero_limits() {
+        let limiter = RateLimiter::new(Some(0), Some(0));
+        let start = Instant::now();
+
+        limiter.acquire(10).await;
+        limiter.acquire(10).await;
+        assert_eq!(Duration::ZERO, start.elapsed());
+    }
+}
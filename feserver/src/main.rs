@@ -5,8 +5,11 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
-use chonkit_embedders::fastembed::local::LocalFastEmbedder as FastEmbedder;
+use chonkit_embedders::fastembed::local::{
+    LocalFastEmbedder as FastEmbedder, LocalFastEmbedderConfig,
+};
 use clap::Parser;
+use queue::{InferenceQueue, QueueConfig, QueueError};
 use serde::Deserialize;
 use serde_json::json;
 use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
@@ -14,6 +17,9 @@ use tower_http::{classify::ServerErrorsFailureClass, trace::TraceLayer};
 use tracing::{info, Span};
 use tracing_subscriber::EnvFilter;
 
+mod openai;
+mod queue;
+
 #[tokio::main]
 async fn main() {
     let args = StartArgs::parse();
@@ -23,18 +29,37 @@ async fn main() {
 
     let addr = &args.address;
 
-    let fastembed = Arc::new(FastEmbedder::new());
+    let fastembed = Arc::new(FastEmbedder::with_config(args.fastembed_config()));
+
+    let (embedder, counter) = (fastembed.clone(), fastembed.clone());
+    let queue = InferenceQueue::start(
+        args.queue_config(),
+        Arc::new(move |model: &str, input: &[&str]| {
+            embedder.embed(input, model).map_err(|e| e.to_string())
+        }),
+        Arc::new(move |model: &str, input: &[&str]| {
+            counter
+                .count_tokens(input, model)
+                .map_err(|e| e.to_string())
+        }),
+    );
+
+    let state = AppState { fastembed, queue };
 
     let router = Router::new()
         .route("/_health", get(_health))
         .route("/embed", post(embed))
         .route("/list", get(list_embedding_models))
+        .route("/embed/sparse", post(embed_sparse))
+        .route("/list/sparse", get(list_sparse_models))
+        .route("/v1/embeddings", post(openai::embeddings))
+        .route("/v1/models", get(openai::models))
         .layer(TraceLayer::new_for_http().on_failure(
             |error: ServerErrorsFailureClass, _latency: Duration, _span: &Span| {
                 tracing::error!("{error}")
             },
         ))
-        .with_state(fastembed);
+        .with_state(state);
 
     let listener = tokio::net::TcpListener::bind(addr)
         .await
@@ -51,20 +76,125 @@ async fn main() {
 struct StartArgs {
     #[arg(short, long, default_value = "0.0.0.0:6969")]
     address: String,
+
+    /// Path to a JSON file containing the embedder configuration.
+    /// The other arguments take precedence over the values in the file.
+    #[arg(short, long)]
+    config: Option<String>,
+
+    /// Comma separated list of models to enable. Enables all models if not set.
+    #[arg(short, long)]
+    models: Option<String>,
+
+    /// Comma separated list of sparse models to enable. Enables all sparse models if not set.
+    #[arg(long)]
+    sparse_models: Option<String>,
+
+    /// Directory where downloaded models are cached.
+    #[arg(long)]
+    cache_dir: Option<String>,
+
+    /// Directory containing user provided ONNX models.
+    #[arg(long)]
+    model_dir: Option<String>,
+
+    /// Seconds after which idle models are unloaded.
+    #[arg(long)]
+    idle_timeout: Option<u64>,
+
+    /// Amount of worker threads running inference.
+    #[arg(long, default_value_t = 2)]
+    workers: usize,
+
+    /// Maximum amount of inputs batched together from concurrent requests.
+    #[arg(long, default_value_t = 256)]
+    max_batch_size: usize,
+
+    /// Maximum time in milliseconds to wait for concurrent requests before running a batch.
+    #[arg(long, default_value_t = 10)]
+    max_wait_ms: u64,
+
+    /// Maximum amount of requests waiting for inference. Requests are rejected with 429 when full.
+    #[arg(long, default_value_t = 1024)]
+    queue_size: usize,
+
+    /// Timeout in seconds for a single request, including the time spent in the queue.
+    #[arg(long, default_value_t = 60)]
+    request_timeout: u64,
+}
+
+impl StartArgs {
+    fn queue_config(&self) -> QueueConfig {
+        QueueConfig {
+            workers: self.workers,
+            max_batch_size: self.max_batch_size,
+            max_wait: Duration::from_millis(self.max_wait_ms),
+            capacity: self.queue_size,
+            timeout: Duration::from_secs(self.request_timeout),
+        }
+    }
+
+    fn fastembed_config(&self) -> LocalFastEmbedderConfig {
+        let mut config = match self.config {
+            Some(ref path) => LocalFastEmbedderConfig::from_file(path),
+            None => LocalFastEmbedderConfig::default(),
+        };
+
+        if let Some(ref models) = self.models {
+            config.models = LocalFastEmbedderConfig::parse_models(models);
+        }
+        if let Some(ref models) = self.sparse_models {
+            config.sparse_models = LocalFastEmbedderConfig::parse_models(models);
+        }
+        if let Some(ref cache_dir) = self.cache_dir {
+            config.cache_dir = Some(cache_dir.into());
+        }
+        if let Some(ref model_dir) = self.model_dir {
+            config.model_dir = Some(model_dir.into());
+        }
+        if let Some(idle_timeout) = self.idle_timeout {
+            config.idle_timeout = Some(idle_timeout);
+        }
+
+        config
+    }
 }
 
 // Routes
 
 async fn embed(
-    state: State<Arc<FastEmbedder>>,
-    Json(EmbedRequest { ref model, input }): axum::extract::Json<EmbedRequest>,
+    state: State<AppState>,
+    Json(EmbedRequest { model, input }): axum::extract::Json<EmbedRequest>,
 ) -> (StatusCode, Json<serde_json::Value>) {
-    // Uses the default batch size of 256
-    let content = input.iter().map(|s| s.as_str()).collect::<Vec<_>>();
     info!("Embedding with model '{model}'");
-    match state.embed(&content, model) {
+    match state.queue.embed(model, input).await {
         Ok(embeddings) => (StatusCode::OK, Json(json! {{ "embeddings": embeddings }})),
         Err(e) => {
+            tracing::error!("{e}");
+            let error = e.to_string();
+            (queue_error_status(&e), Json(json! {{ "error": error }}))
+        }
+    }
+}
+
+/// Sparse models are not batched by the queue since they are expected to be
+/// used considerably less than dense ones; inference runs on the blocking pool.
+async fn embed_sparse(
+    state: State<AppState>,
+    Json(EmbedRequest { model, input }): axum::extract::Json<EmbedRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    info!("Embedding with sparse model '{model}'");
+
+    let fastembed = state.fastembed.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let input: Vec<&str> = input.iter().map(String::as_str).collect();
+        fastembed.embed_sparse(&input, &model)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(embeddings)) => (StatusCode::OK, Json(json! {{ "embeddings": embeddings }})),
+        Ok(Err(e)) => {
             tracing::error!("{e}");
             let error = e.to_string();
             (
@@ -72,25 +202,54 @@ async fn embed(
                 Json(json! {{ "error": error }}),
             )
         }
+        Err(e) => {
+            tracing::error!("{e}");
+            let error = e.to_string();
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json! {{ "error": error }}),
+            )
+        }
+    }
+}
+
+fn queue_error_status(error: &QueueError) -> StatusCode {
+    match error {
+        QueueError::Full => StatusCode::TOO_MANY_REQUESTS,
+        QueueError::Timeout | QueueError::Closed => StatusCode::SERVICE_UNAVAILABLE,
+        QueueError::Inference(_) => StatusCode::INTERNAL_SERVER_ERROR,
     }
 }
 
 async fn list_embedding_models(
-    state: axum::extract::State<Arc<FastEmbedder>>,
+    state: axum::extract::State<AppState>,
 ) -> (StatusCode, Json<serde_json::Value>) {
     let models = state
+        .fastembed
         .list_models()
         .into_iter()
-        .map(|model| (model.model_code, model.dim))
         .collect::<HashMap<String, usize>>();
 
     (StatusCode::OK, Json(json! { models }))
 }
 
+async fn list_sparse_models(
+    state: axum::extract::State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let models = state.fastembed.list_sparse_models();
+    (StatusCode::OK, Json(json! { models }))
+}
+
 async fn _health() -> impl IntoResponse {
     StatusCode::NO_CONTENT
 }
 
+#[derive(Clone)]
+struct AppState {
+    fastembed: Arc<FastEmbedder>,
+    queue: InferenceQueue,
+}
+
 // DTO
 
 #[derive(Debug, Deserialize)]
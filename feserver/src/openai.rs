@@ -0,0 +1,280 @@
+//! OpenAI compatible embeddings API.
+//!
+//! Implements `/v1/embeddings` and `/v1/models` so that any OpenAI compatible
+//! client can use the fastembed models.
+
+use crate::{queue::QueueError, AppState};
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+pub async fn embeddings(
+    state: State<AppState>,
+    request: Result<Json<EmbeddingRequest>, JsonRejection>,
+) -> Result<Json<EmbeddingResponse>, ApiError> {
+    let Json(EmbeddingRequest {
+        input,
+        model,
+        encoding_format,
+        dimensions,
+    }) = request.map_err(|e| ApiError::invalid_request(e.body_text(), None))?;
+
+    let input = match input {
+        EmbeddingInput::Single(input) => vec![input],
+        EmbeddingInput::Batch(input) => input,
+    };
+
+    if input.is_empty() {
+        return Err(ApiError::invalid_request(
+            "'input' cannot be an empty array",
+            Some("input"),
+        ));
+    }
+
+    let Some((_, dim)) = state
+        .fastembed
+        .list_models()
+        .into_iter()
+        .find(|(name, _)| name == &model)
+    else {
+        return Err(ApiError::model_not_found(&model));
+    };
+
+    if dimensions.is_some_and(|d| d != dim) {
+        return Err(ApiError::invalid_request(
+            format!("model '{model}' only supports {dim} dimensions"),
+            Some("dimensions"),
+        ));
+    }
+
+    info!("Embedding {} input(s) with model '{model}'", input.len());
+
+    let (embeddings, tokens) = state.queue.embed_with_usage(model.clone(), input).await?;
+
+    let data = embeddings
+        .into_iter()
+        .enumerate()
+        .map(|(index, embedding)| EmbeddingObject {
+            object: "embedding",
+            embedding: match encoding_format {
+                EncodingFormat::Float => EmbeddingData::Float(embedding),
+                EncodingFormat::Base64 => EmbeddingData::Base64(encode_base64(&embedding)),
+            },
+            index,
+        })
+        .collect();
+
+    Ok(Json(EmbeddingResponse {
+        object: "list",
+        data,
+        model,
+        usage: Usage {
+            prompt_tokens: tokens,
+            total_tokens: tokens,
+        },
+    }))
+}
+
+pub async fn models(state: State<AppState>) -> Json<ModelList> {
+    let data = state
+        .fastembed
+        .list_models()
+        .into_iter()
+        .map(|(id, dimensions)| Model {
+            id,
+            object: "model",
+            created: 0,
+            owned_by: "fastembed",
+            dimensions,
+        })
+        .collect();
+
+    Json(ModelList {
+        object: "list",
+        data,
+    })
+}
+
+/// Encode the embedding as little endian `f32`s, the same as OpenAI.
+fn encode_base64(embedding: &[f64]) -> String {
+    let bytes: Vec<u8> = embedding
+        .iter()
+        .flat_map(|e| (*e as f32).to_le_bytes())
+        .collect();
+    STANDARD.encode(bytes)
+}
+
+// DTO
+
+#[derive(Debug, Deserialize)]
+pub struct EmbeddingRequest {
+    input: EmbeddingInput,
+    model: String,
+    #[serde(default)]
+    encoding_format: EncodingFormat,
+    dimensions: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum EmbeddingInput {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum EncodingFormat {
+    #[default]
+    Float,
+    Base64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmbeddingResponse {
+    object: &'static str,
+    data: Vec<EmbeddingObject>,
+    model: String,
+    usage: Usage,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingObject {
+    object: &'static str,
+    embedding: EmbeddingData,
+    index: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum EmbeddingData {
+    Float(Vec<f64>),
+    Base64(String),
+}
+
+#[derive(Debug, Serialize)]
+struct Usage {
+    prompt_tokens: usize,
+    total_tokens: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModelList {
+    object: &'static str,
+    data: Vec<Model>,
+}
+
+/// OpenAI model object. `dimensions` is not part of the OpenAI API and
+/// is returned so clients can obtain the embedding size.
+#[derive(Debug, Serialize)]
+struct Model {
+    id: String,
+    object: &'static str,
+    created: u64,
+    owned_by: &'static str,
+    dimensions: usize,
+}
+
+// Error
+
+/// Error response in the OpenAI error envelope format.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    error: ErrorParams,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorParams {
+    message: String,
+    r#type: &'static str,
+    param: Option<&'static str>,
+    code: Option<&'static str>,
+}
+
+impl ApiError {
+    fn new(
+        status: StatusCode,
+        message: impl Into<String>,
+        r#type: &'static str,
+        param: Option<&'static str>,
+        code: Option<&'static str>,
+    ) -> Self {
+        Self {
+            status,
+            error: ErrorParams {
+                message: message.into(),
+                r#type,
+                param,
+                code,
+            },
+        }
+    }
+
+    fn invalid_request(message: impl Into<String>, param: Option<&'static str>) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            message,
+            "invalid_request_error",
+            param,
+            None,
+        )
+    }
+
+    fn model_not_found(model: &str) -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            format!("The model '{model}' does not exist"),
+            "invalid_request_error",
+            Some("model"),
+            Some("model_not_found"),
+        )
+    }
+
+    fn server_error(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            message,
+            "server_error",
+            None,
+            None,
+        )
+    }
+}
+
+impl From<QueueError> for ApiError {
+    fn from(e: QueueError) -> Self {
+        tracing::error!("{e}");
+        let message = e.to_string();
+        match e {
+            QueueError::Full => Self::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                message,
+                "rate_limit_error",
+                None,
+                Some("queue_full"),
+            ),
+            QueueError::Timeout => Self::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                message,
+                "server_error",
+                None,
+                Some("timeout"),
+            ),
+            QueueError::Closed | QueueError::Inference(_) => Self::server_error(message),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "error": self.error });
+        (self.status, Json(body)).into_response()
+    }
+}
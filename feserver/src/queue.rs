@@ -0,0 +1,464 @@
+//! Inference queue. Concurrent requests for the same model are collected into batches
+//! which are run on a dedicated pool of worker threads, keeping the async runtime free.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Instant,
+};
+
+/// Runs inference for a model on a batch of inputs.
+pub type InferFn = dyn Fn(&str, &[&str]) -> Result<Vec<Vec<f64>>, String> + Send + Sync;
+
+/// Counts the tokens a model's tokenizer produces for the inputs.
+pub type CountFn = dyn Fn(&str, &[&str]) -> Result<usize, String> + Send + Sync;
+
+type Response = Result<Embedded, String>;
+
+/// Embeddings of a request's inputs.
+#[derive(Debug)]
+pub struct Embedded {
+    pub embeddings: Vec<Vec<f64>>,
+
+    /// Amount of tokens in the inputs, if requested.
+    pub tokens: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    /// Amount of worker threads running inference.
+    pub workers: usize,
+
+    /// Maximum amount of inputs in a batch. Requests with more inputs are run on their own.
+    pub max_batch_size: usize,
+
+    /// Maximum time to wait for more requests before running a batch.
+    pub max_wait: Duration,
+
+    /// Maximum amount of requests waiting in the queue.
+    /// Requests are rejected when the queue is full.
+    pub capacity: usize,
+
+    /// Maximum time a request can take, including the time spent in the queue.
+    pub timeout: Duration,
+}
+
+#[derive(Debug)]
+pub enum QueueError {
+    /// The queue is at capacity.
+    Full,
+
+    /// The request timed out.
+    Timeout,
+
+    /// The queue is shut down.
+    Closed,
+
+    /// Inference failed.
+    Inference(String),
+}
+
+impl std::fmt::Display for QueueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueueError::Full => write!(f, "inference queue is full"),
+            QueueError::Timeout => write!(f, "request timed out"),
+            QueueError::Closed => write!(f, "inference queue is closed"),
+            QueueError::Inference(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Handle to the inference queue.
+#[derive(Clone)]
+pub struct InferenceQueue {
+    tx: mpsc::Sender<Job>,
+    timeout: Duration,
+}
+
+struct Job {
+    model: String,
+    input: Vec<String>,
+    /// Whether to count the tokens of the input after inference.
+    count_tokens: bool,
+    respond: oneshot::Sender<Response>,
+}
+
+struct Batch {
+    model: String,
+    jobs: Vec<Job>,
+}
+
+/// Requests for a model waiting to be batched.
+struct Pending {
+    /// When the first request was received.
+    started: Instant,
+
+    /// Total amount of inputs in `jobs`.
+    size: usize,
+
+    jobs: Vec<Job>,
+}
+
+impl InferenceQueue {
+    /// Spawn the worker threads and the batching task.
+    /// Must be called from within a tokio runtime.
+    pub fn start(config: QueueConfig, infer: Arc<InferFn>, count: Arc<CountFn>) -> Self {
+        let QueueConfig {
+            workers,
+            max_batch_size,
+            max_wait,
+            capacity,
+            timeout,
+        } = config;
+
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        let (batch_tx, batch_rx) = mpsc::channel(workers.max(1));
+        let batch_rx = Arc::new(Mutex::new(batch_rx));
+
+        for i in 0..workers.max(1) {
+            let batch_rx = batch_rx.clone();
+            let (infer, count) = (infer.clone(), count.clone());
+            std::thread::Builder::new()
+                .name(format!("inference-{i}"))
+                .spawn(move || work(batch_rx, infer, count))
+                .expect("error while spawning inference worker");
+        }
+
+        tokio::spawn(dispatch(rx, batch_tx, max_batch_size.max(1), max_wait));
+
+        Self { tx, timeout }
+    }
+
+    /// Queue the input for embedding and wait for the result.
+    pub async fn embed(
+        &self,
+        model: String,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f64>>, QueueError> {
+        Ok(self.submit(model, input, false).await?.embeddings)
+    }
+
+    /// Queue the input for embedding and wait for the result along with the amount
+    /// of tokens in the input. Tokens are counted by the worker running the inference.
+    pub async fn embed_with_usage(
+        &self,
+        model: String,
+        input: Vec<String>,
+    ) -> Result<(Vec<Vec<f64>>, usize), QueueError> {
+        let Embedded { embeddings, tokens } = self.submit(model, input, true).await?;
+        Ok((embeddings, tokens.unwrap_or_default()))
+    }
+
+    async fn submit(
+        &self,
+        model: String,
+        input: Vec<String>,
+        count_tokens: bool,
+    ) -> Result<Embedded, QueueError> {
+        let (respond, rx) = oneshot::channel();
+
+        let job = Job {
+            model,
+            input,
+            count_tokens,
+            respond,
+        };
+
+        self.tx.try_send(job).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => QueueError::Full,
+            mpsc::error::TrySendError::Closed(_) => QueueError::Closed,
+        })?;
+
+        match tokio::time::timeout(self.timeout, rx).await {
+            Ok(Ok(response)) => response.map_err(QueueError::Inference),
+            Ok(Err(_)) => Err(QueueError::Closed),
+            Err(_) => Err(QueueError::Timeout),
+        }
+    }
+}
+
+/// Collect jobs into batches per model and send them to the workers once
+/// they are full or the oldest job in them waited for `max_wait`.
+async fn dispatch(
+    mut rx: mpsc::Receiver<Job>,
+    batch_tx: mpsc::Sender<Batch>,
+    max_batch_size: usize,
+    max_wait: Duration,
+) {
+    let mut pending: HashMap<String, Pending> = HashMap::new();
+
+    loop {
+        let deadline = pending.values().map(|p| p.started + max_wait).min();
+
+        let received = match deadline {
+            Some(deadline) => tokio::select! {
+                job = rx.recv() => Some(job),
+                _ = tokio::time::sleep_until(deadline) => None,
+            },
+            None => Some(rx.recv().await),
+        };
+
+        match received {
+            Some(Some(job)) => {
+                let model = job.model.clone();
+
+                if pending
+                    .get(&model)
+                    .is_some_and(|p| p.size + job.input.len() > max_batch_size)
+                {
+                    let full = pending.remove(&model).expect("checked above");
+                    send(&batch_tx, model.clone(), full).await;
+                }
+
+                let entry = pending.entry(model.clone()).or_insert_with(|| Pending {
+                    started: Instant::now(),
+                    size: 0,
+                    jobs: vec![],
+                });
+
+                entry.size += job.input.len();
+                entry.jobs.push(job);
+
+                if entry.size >= max_batch_size {
+                    let full = pending.remove(&model).expect("inserted above");
+                    send(&batch_tx, model, full).await;
+                }
+            }
+
+            // Sender dropped, flush whatever is left and stop
+            Some(None) => {
+                for (model, p) in pending.drain() {
+                    send(&batch_tx, model, p).await;
+                }
+                break;
+            }
+
+            // Deadline reached
+            None => {
+                let now = Instant::now();
+                let expired: Vec<String> = pending
+                    .iter()
+                    .filter(|(_, p)| p.started + max_wait <= now)
+                    .map(|(model, _)| model.clone())
+                    .collect();
+
+                for model in expired {
+                    let p = pending.remove(&model).expect("collected above");
+                    send(&batch_tx, model, p).await;
+                }
+            }
+        }
+    }
+}
+
+/// Send the batch to the workers. Waits if all of them are busy, which in
+/// turn fills up the queue and causes new requests to be rejected.
+async fn send(batch_tx: &mpsc::Sender<Batch>, model: String, pending: Pending) {
+    tracing::debug!(
+        "Dispatching batch of {} input(s) from {} request(s) for '{model}'",
+        pending.size,
+        pending.jobs.len()
+    );
+
+    let batch = Batch {
+        model,
+        jobs: pending.jobs,
+    };
+
+    if batch_tx.send(batch).await.is_err() {
+        tracing::error!("Inference workers stopped, dropping batch");
+    }
+}
+
+fn work(rx: Arc<Mutex<mpsc::Receiver<Batch>>>, infer: Arc<InferFn>, count: Arc<CountFn>) {
+    loop {
+        let batch = rx.lock().unwrap().blocking_recv();
+
+        let Some(Batch { model, mut jobs }) = batch else {
+            break;
+        };
+
+        // Skip requests which timed out while waiting
+        jobs.retain(|job| !job.respond.is_closed());
+
+        if jobs.is_empty() {
+            continue;
+        }
+
+        let input: Vec<&str> = jobs
+            .iter()
+            .flat_map(|job| job.input.iter().map(String::as_str))
+            .collect();
+
+        let embeddings = match infer(&model, &input) {
+            Ok(embeddings) if embeddings.len() == input.len() => embeddings,
+            Ok(embeddings) => {
+                let error = format!(
+                    "expected {} embeddings, got {}",
+                    input.len(),
+                    embeddings.len()
+                );
+                for job in jobs {
+                    let _ = job.respond.send(Err(error.clone()));
+                }
+                continue;
+            }
+            Err(e) => {
+                for job in jobs {
+                    let _ = job.respond.send(Err(e.clone()));
+                }
+                continue;
+            }
+        };
+
+        let mut embeddings = embeddings.into_iter();
+
+        for job in jobs {
+            let embeddings = embeddings.by_ref().take(job.input.len()).collect();
+
+            let tokens = if job.count_tokens {
+                let input: Vec<&str> = job.input.iter().map(String::as_str).collect();
+                match count(&model, &input) {
+                    Ok(tokens) => Some(tokens),
+                    Err(e) => {
+                        let _ = job.respond.send(Err(e));
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+
+            let _ = job.respond.send(Ok(Embedded { embeddings, tokens }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn no_count() -> Arc<CountFn> {
+        Arc::new(|_: &str, _: &[&str]| Ok(0))
+    }
+
+    fn config() -> QueueConfig {
+        QueueConfig {
+            workers: 1,
+            max_batch_size: 8,
+            max_wait: Duration::from_millis(50),
+            capacity: 16,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn batches_concurrent_requests() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_w = calls.clone();
+
+        let queue = InferenceQueue::start(
+            config(),
+            Arc::new(move |_: &str, input: &[&str]| {
+                calls_w.fetch_add(1, Ordering::SeqCst);
+                Ok(input.iter().map(|i| vec![i.len() as f64]).collect())
+            }),
+            no_count(),
+        );
+
+        let (a, b) = tokio::join!(
+            queue.embed("model".to_string(), vec!["a".to_string()]),
+            queue.embed(
+                "model".to_string(),
+                vec!["bb".to_string(), "ccc".to_string()]
+            ),
+        );
+
+        assert_eq!(vec![vec![1.]], a.unwrap());
+        assert_eq!(vec![vec![2.], vec![3.]], b.unwrap());
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn separates_models() {
+        let queue = InferenceQueue::start(
+            config(),
+            Arc::new(|model: &str, input: &[&str]| {
+                Ok(input.iter().map(|_| vec![model.len() as f64]).collect())
+            }),
+            no_count(),
+        );
+
+        let (a, b) = tokio::join!(
+            queue.embed("a".to_string(), vec!["x".to_string()]),
+            queue.embed("bb".to_string(), vec!["x".to_string()]),
+        );
+
+        assert_eq!(vec![vec![1.]], a.unwrap());
+        assert_eq!(vec![vec![2.]], b.unwrap());
+    }
+
+    #[tokio::test]
+    async fn counts_tokens_of_requests() {
+        let queue = InferenceQueue::start(
+            config(),
+            Arc::new(|_: &str, input: &[&str]| Ok(input.iter().map(|_| vec![0.]).collect())),
+            Arc::new(|_: &str, input: &[&str]| Ok(input.iter().map(|i| i.len()).sum())),
+        );
+
+        let (a, b) = tokio::join!(
+            queue.embed_with_usage("model".to_string(), vec!["aa".to_string()]),
+            queue.embed(
+                "model".to_string(),
+                vec!["bbb".to_string(), "c".to_string()]
+            ),
+        );
+
+        assert_eq!((vec![vec![0.]], 2), a.unwrap());
+        assert_eq!(vec![vec![0.], vec![0.]], b.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rejects_when_full_and_times_out() {
+        let queue = InferenceQueue::start(
+            QueueConfig {
+                capacity: 1,
+                max_batch_size: 1,
+                timeout: Duration::from_millis(100),
+                ..config()
+            },
+            Arc::new(|_: &str, input: &[&str]| {
+                std::thread::sleep(Duration::from_millis(300));
+                Ok(input.iter().map(|_| vec![0.]).collect())
+            }),
+            no_count(),
+        );
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let queue = queue.clone();
+                tokio::spawn(async move {
+                    queue
+                        .embed("model".to_string(), vec!["x".to_string()])
+                        .await
+                })
+            })
+            .collect();
+
+        let mut results = vec![];
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
+
+        assert!(results.iter().any(|r| matches!(r, Err(QueueError::Full))));
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, Err(QueueError::Timeout))));
+    }
+}